@@ -0,0 +1,460 @@
+use anyhow::{Result, bail, ensure};
+use std::collections::HashMap;
+
+/// Supported file extension
+pub(crate) const EXTENSION: &str = "cdf";
+
+/// Chromatogram of an ANDI (AIA) file
+///
+/// The FID trace (`ordinate_values`) or the MS total ion current
+/// (`total_intensity`), times are in seconds.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Chromatogram {
+    pub(crate) times: Vec<f64>,
+    pub(crate) values: Vec<f64>,
+}
+
+impl Chromatogram {
+    pub(crate) fn read(bytes: &[u8]) -> Result<Self> {
+        let file = File::new(bytes)?;
+        // MS
+        if file.variables.contains_key("total_intensity") {
+            let times = file.values("scan_acquisition_time")?;
+            let values = file.values("total_intensity")?;
+            ensure!(
+                times.len() == values.len(),
+                "scan times and intensities differ"
+            );
+            return Ok(Self { times, values });
+        }
+        // Chromatography
+        let values = file.values("ordinate_values")?;
+        let times = match file.values("raw_data_retention") {
+            Ok(times) if times.len() == values.len() => times,
+            _ => {
+                let interval = file.scalar("actual_sampling_interval")?;
+                let delay = file.scalar("actual_delay_time").unwrap_or_default();
+                (0..values.len())
+                    .map(|index| delay + index as f64 * interval)
+                    .collect()
+            }
+        };
+        Ok(Self { times, values })
+    }
+}
+
+/// NetCDF classic (CDF-1 and CDF-2) file
+struct File<'a> {
+    bytes: &'a [u8],
+    records: usize,
+    record_size: usize,
+    variables: HashMap<String, Variable>,
+}
+
+impl<'a> File<'a> {
+    fn new(bytes: &'a [u8]) -> Result<Self> {
+        let mut reader = Reader { bytes, offset: 0 };
+        ensure!(reader.take(3)? == b"CDF", "not a netCDF classic file");
+        let version = reader.take(1)?[0];
+        ensure!(
+            version == 1 || version == 2,
+            "unsupported netCDF version {version}"
+        );
+        let records = reader.u32()? as usize;
+        // Dimensions, zero length is the record dimension
+        let mut dimensions = Vec::new();
+        for _ in 0..reader.list(NC_DIMENSION)? {
+            reader.name()?;
+            dimensions.push(reader.u32()? as usize);
+        }
+        // Global attributes
+        reader.attributes()?;
+        // Variables
+        let mut variables = HashMap::new();
+        for _ in 0..reader.list(NC_VARIABLE)? {
+            let name = reader.name()?;
+            let mut shape = Vec::new();
+            for _ in 0..reader.u32()? {
+                let dimension = reader.u32()? as usize;
+                match dimensions.get(dimension) {
+                    Some(&length) => shape.push(length),
+                    None => bail!("variable {name} has an unknown dimension"),
+                }
+            }
+            reader.attributes()?;
+            let kind = reader.u32()?;
+            let size = reader.u32()? as usize;
+            let begin = match version {
+                1 => reader.u32()? as usize,
+                _ => reader.u64()? as usize,
+            };
+            variables.insert(
+                name,
+                Variable {
+                    kind,
+                    shape,
+                    size,
+                    begin,
+                },
+            );
+        }
+        let Some(record_size) = variables
+            .values()
+            .filter(|variable| variable.record())
+            .try_fold(0usize, |sum, variable| sum.checked_add(variable.size))
+        else {
+            bail!("netCDF record size overflows");
+        };
+        Ok(Self {
+            bytes,
+            records,
+            record_size,
+            variables,
+        })
+    }
+
+    fn scalar(&self, name: &str) -> Result<f64> {
+        match self.values(name)?.first() {
+            Some(&value) => Ok(value),
+            None => bail!("variable {name} is empty"),
+        }
+    }
+
+    fn values(&self, name: &str) -> Result<Vec<f64>> {
+        let Some(variable) = self.variables.get(name) else {
+            bail!("variable {name} not found");
+        };
+        let width = width(variable.kind)?;
+        let mut values = Vec::new();
+        if variable.record() {
+            let Some(count) = product(&variable.shape[1..]) else {
+                bail!("variable {name} is too large");
+            };
+            // A single record variable is not padded
+            let record_size = if self.variables.values().filter(|v| v.record()).count() == 1 {
+                count.checked_mul(width)
+            } else {
+                Some(self.record_size)
+            };
+            for record in 0..self.records {
+                let Some(begin) = record_size
+                    .and_then(|record_size| record.checked_mul(record_size))
+                    .and_then(|offset| variable.begin.checked_add(offset))
+                else {
+                    bail!("variable {name} is out of the file");
+                };
+                values.extend(self.read(variable.kind, begin, count)?);
+            }
+        } else {
+            let Some(count) = product(&variable.shape) else {
+                bail!("variable {name} is too large");
+            };
+            values = self.read(variable.kind, variable.begin, count)?;
+        }
+        Ok(values)
+    }
+
+    fn read(&self, kind: u32, begin: usize, count: usize) -> Result<Vec<f64>> {
+        let width = width(kind)?;
+        let Some(bytes) = count
+            .checked_mul(width)
+            .and_then(|length| begin.checked_add(length))
+            .and_then(|end| self.bytes.get(begin..end))
+        else {
+            bail!("variable data is out of the file");
+        };
+        Ok(bytes
+            .chunks_exact(width)
+            .map(|chunk| match kind {
+                NC_BYTE => chunk[0] as i8 as f64,
+                NC_CHAR => chunk[0] as f64,
+                NC_SHORT => i16::from_be_bytes([chunk[0], chunk[1]]) as f64,
+                NC_INT => i32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as f64,
+                NC_FLOAT => f32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as f64,
+                _ => f64::from_be_bytes(chunk.try_into().unwrap_or_default()),
+            })
+            .collect())
+    }
+}
+
+/// Variable
+struct Variable {
+    kind: u32,
+    shape: Vec<usize>,
+    size: usize,
+    begin: usize,
+}
+
+impl Variable {
+    fn record(&self) -> bool {
+        self.shape.first() == Some(&0)
+    }
+}
+
+/// Big-endian header reader
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8]> {
+        let Some(bytes) = self
+            .offset
+            .checked_add(count)
+            .and_then(|end| self.bytes.get(self.offset..end))
+        else {
+            bail!("unexpected end of the netCDF header");
+        };
+        self.offset += count;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into()?))
+    }
+
+    /// Name padded to four bytes
+    fn name(&mut self) -> Result<String> {
+        let length = self.u32()? as usize;
+        let name = String::from_utf8_lossy(self.take(length)?).into_owned();
+        self.take(padding(length))?;
+        Ok(name)
+    }
+
+    /// Number of the list elements, zero for the absent list
+    fn list(&mut self, tag: u32) -> Result<u32> {
+        let found = self.u32()?;
+        let count = self.u32()?;
+        ensure!(found == tag || found == 0, "unexpected netCDF tag {found}");
+        Ok(count)
+    }
+
+    /// Skips the attributes
+    fn attributes(&mut self) -> Result<()> {
+        for _ in 0..self.list(NC_ATTRIBUTE)? {
+            self.name()?;
+            let kind = self.u32()?;
+            let Some(length) = (self.u32()? as usize).checked_mul(width(kind)?) else {
+                bail!("netCDF attribute is too large");
+            };
+            self.take(length)?;
+            self.take(padding(length))?;
+        }
+        Ok(())
+    }
+}
+
+const NC_BYTE: u32 = 1;
+const NC_CHAR: u32 = 2;
+const NC_SHORT: u32 = 3;
+const NC_INT: u32 = 4;
+const NC_FLOAT: u32 = 5;
+const NC_DOUBLE: u32 = 6;
+
+const NC_DIMENSION: u32 = 0x0A;
+const NC_VARIABLE: u32 = 0x0B;
+const NC_ATTRIBUTE: u32 = 0x0C;
+
+fn width(kind: u32) -> Result<usize> {
+    Ok(match kind {
+        NC_BYTE | NC_CHAR => 1,
+        NC_SHORT => 2,
+        NC_INT | NC_FLOAT => 4,
+        NC_DOUBLE => 8,
+        _ => bail!("unsupported netCDF type {kind}"),
+    })
+}
+
+const fn padding(length: usize) -> usize {
+    (4 - length % 4) % 4
+}
+
+/// Number of the values of the shape, `None` on overflow
+fn product(shape: &[usize]) -> Option<usize> {
+    shape
+        .iter()
+        .try_fold(1usize, |product, &length| product.checked_mul(length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// CDF-1 file writer
+    #[derive(Default)]
+    struct Writer {
+        bytes: Vec<u8>,
+    }
+
+    impl Writer {
+        fn u32(&mut self, value: u32) -> &mut Self {
+            self.bytes.extend(value.to_be_bytes());
+            self
+        }
+
+        fn name(&mut self, name: &str) -> &mut Self {
+            self.u32(name.len() as u32);
+            self.bytes.extend(name.as_bytes());
+            self.bytes.extend(vec![0; padding(name.len())]);
+            self
+        }
+    }
+
+    /// Variable: name, dimensions, type, size and data
+    type Data<'a> = (&'a str, &'a [u32], u32, u32, Vec<u8>);
+
+    /// File with the dimensions, a global attribute and the variables
+    ///
+    /// Record variables are interleaved by the record.
+    fn file(records: u32, dimensions: &[(&str, u32)], variables: &[Data]) -> Vec<u8> {
+        let header = |base: u32| {
+            let mut writer = Writer::default();
+            writer.bytes.extend(b"CDF\x01");
+            writer.u32(records);
+            writer.u32(NC_DIMENSION).u32(dimensions.len() as u32);
+            for &(name, length) in dimensions {
+                writer.name(name).u32(length);
+            }
+            writer.u32(NC_ATTRIBUTE).u32(1);
+            writer.name("dataset_origin").u32(NC_CHAR).u32(5);
+            writer.bytes.extend(b"test\0\0\0\0");
+            writer.u32(NC_VARIABLE).u32(variables.len() as u32);
+            let mut begin = base;
+            for (name, shape, kind, size, _) in variables {
+                writer.name(name).u32(shape.len() as u32);
+                for &dimension in *shape {
+                    writer.u32(dimension);
+                }
+                writer.u32(0).u32(0).u32(*kind).u32(*size).u32(begin);
+                begin += size;
+            }
+            writer.bytes
+        };
+        let mut bytes = header(0);
+        bytes = header(bytes.len() as u32);
+        let record = |(_, shape, ..): &&Data| {
+            shape
+                .first()
+                .is_some_and(|&dimension| dimensions[dimension as usize].1 == 0)
+        };
+        let size: usize = variables
+            .iter()
+            .filter(record)
+            .map(|&(.., size, _)| size as usize)
+            .sum();
+        for (.., data) in variables.iter().filter(|variable| !record(variable)) {
+            bytes.extend(data);
+        }
+        for index in 0..records as usize {
+            for (.., data) in variables.iter().filter(record) {
+                let width = data.len() / records as usize;
+                bytes.extend(&data[index * width..(index + 1) * width]);
+            }
+        }
+        assert!(size * records as usize <= bytes.len());
+        bytes
+    }
+
+    fn floats(values: &[f32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect()
+    }
+
+    fn doubles(values: &[f64]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn chromatography() -> Result<()> {
+        let bytes = file(
+            0,
+            &[("point_number", 5)],
+            &[
+                (
+                    "ordinate_values",
+                    &[0],
+                    NC_FLOAT,
+                    20,
+                    floats(&[1.0, 2.0, 3.0, 4.0, 5.0]),
+                ),
+                (
+                    "actual_sampling_interval",
+                    &[],
+                    NC_DOUBLE,
+                    8,
+                    doubles(&[0.5]),
+                ),
+                ("actual_delay_time", &[], NC_DOUBLE, 8, doubles(&[1.0])),
+            ],
+        );
+        let chromatogram = Chromatogram::read(&bytes)?;
+        assert_eq!(chromatogram.values, [1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(chromatogram.times, [1.0, 1.5, 2.0, 2.5, 3.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn mass_spectrometry() -> Result<()> {
+        let bytes = file(
+            3,
+            &[("scan_number", 0)],
+            &[
+                (
+                    "scan_acquisition_time",
+                    &[0],
+                    NC_DOUBLE,
+                    8,
+                    doubles(&[0.1, 0.2, 0.3]),
+                ),
+                (
+                    "total_intensity",
+                    &[0],
+                    NC_FLOAT,
+                    4,
+                    floats(&[10.0, 20.0, 30.0]),
+                ),
+            ],
+        );
+        let chromatogram = Chromatogram::read(&bytes)?;
+        assert_eq!(chromatogram.times, [0.1, 0.2, 0.3]);
+        assert_eq!(chromatogram.values, [10.0, 20.0, 30.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Chromatogram::read(b"").is_err());
+        assert!(Chromatogram::read(b"HDF\x01\0\0\0\0").is_err());
+        // Truncated data
+        let mut bytes = file(
+            0,
+            &[("point_number", 5)],
+            &[(
+                "ordinate_values",
+                &[0],
+                NC_FLOAT,
+                20,
+                floats(&[1.0, 2.0, 3.0, 4.0, 5.0]),
+            )],
+        );
+        bytes.truncate(bytes.len() - 1);
+        assert!(Chromatogram::read(&bytes).is_err());
+        // Overflowing shape
+        let bytes = file(
+            0,
+            &[("x", u32::MAX), ("y", u32::MAX), ("z", u32::MAX)],
+            &[("ordinate_values", &[0, 1, 2], NC_DOUBLE, 8, doubles(&[0.0]))],
+        );
+        assert!(Chromatogram::read(&bytes).is_err());
+    }
+}
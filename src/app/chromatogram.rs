@@ -0,0 +1,341 @@
+use super::{
+    andi::Chromatogram,
+    computers::source::{Computer as SourceComputer, Key as SourceKey},
+    localize,
+    panes::source::settings::Settings as SourceSettings,
+    units::{TimeUnits, time_units},
+};
+use crate::{
+    special::{
+        column::mode::ColumnExt as _,
+        peaks::{Options, Peak, pick},
+        substituent::ColumnExt as _,
+    },
+    utils::HashedDataFrame,
+};
+use egui::{CollapsingHeader, ComboBox, DragValue, Grid, ScrollArea, Slider, Ui};
+use polars::prelude::*;
+use std::collections::BTreeMap;
+
+/// ECL window of the saturated anchors in the first pass
+const ANCHOR_TOLERANCE: f64 = 0.5;
+
+/// Raw chromatograms import
+///
+/// Peaks are picked in every chromatogram and assigned to the fatty acids of
+/// the reference by the nearest ECL. The ECL scale of a chromatogram is first
+/// taken from the reference retention times, then rebuilt from the
+/// saturated fatty acids found in the chromatogram itself.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Chromatograms {
+    pub(crate) names: Vec<String>,
+    pub(crate) chromatograms: Vec<Chromatogram>,
+    pub(crate) options: Options,
+    pub(crate) units: TimeUnits,
+    pub(crate) mode: String,
+    pub(crate) tolerance: f64,
+    peaks: Vec<Vec<Peak>>,
+    picked: Option<Options>,
+}
+
+impl Chromatograms {
+    pub(crate) const fn new() -> Self {
+        Self {
+            names: Vec::new(),
+            chromatograms: Vec::new(),
+            options: Options::new(),
+            units: TimeUnits::Minute,
+            mode: String::new(),
+            tolerance: 0.05,
+            peaks: Vec::new(),
+            picked: None,
+        }
+    }
+
+    pub(crate) fn push(&mut self, name: String, chromatogram: Chromatogram) {
+        self.names.push(name);
+        self.chromatograms.push(chromatogram);
+        self.picked = None;
+    }
+
+    /// Peaks of every chromatogram, times in the reference units
+    fn peaks(&mut self) -> &[Vec<Peak>] {
+        if self.picked != Some(self.options) {
+            self.peaks = self
+                .chromatograms
+                .iter()
+                .map(|chromatogram| {
+                    let times: Vec<f64> = chromatogram
+                        .times
+                        .iter()
+                        .map(|&time| TimeUnits::Second.convert(time, self.units))
+                        .collect();
+                    pick(&times, &chromatogram.values, &self.options)
+                })
+                .collect();
+            self.picked = Some(self.options);
+        }
+        &self.peaks
+    }
+
+    /// Source data frame of the assigned peaks
+    ///
+    /// `Mode`, `FattyAcid` and the `RetentionTime`, `Area` and `Width` of the
+    /// peak in every chromatogram, null in the chromatograms the fatty acid is
+    /// not found in.
+    pub(crate) fn assign(&mut self, reference: &HashedDataFrame) -> PolarsResult<DataFrame> {
+        let references = self.references(reference)?;
+        let tolerance = self.tolerance;
+        let count = self.chromatograms.len();
+        let mut assigned = BTreeMap::<u32, [Vec<Option<f64>>; 3]>::new();
+        for (run, peaks) in self.peaks().iter().enumerate() {
+            for (reference, peak) in assign(&references, peaks, tolerance) {
+                let peak = &peaks[peak];
+                let [times, areas, widths] = assigned
+                    .entry(references[reference].row)
+                    .or_insert_with(|| [vec![None; count], vec![None; count], vec![None; count]]);
+                times[run] = Some(peak.time);
+                areas[run] = Some(peak.area);
+                widths[run] = Some(peak.width);
+            }
+        }
+        let rows = IdxCa::from_vec("Row".into(), assigned.keys().copied().collect());
+        let mut data_frame = reference.select(["Mode", "FattyAcid"])?.take(&rows)?;
+        for (index, name) in ["RetentionTime", "Area", "Width"].into_iter().enumerate() {
+            let values: Vec<Series> = assigned
+                .values()
+                .map(|values| Series::new(PlSmallStr::EMPTY, &values[index]))
+                .collect();
+            data_frame.with_column(Column::new(name.into(), values))?;
+        }
+        Ok(data_frame)
+    }
+
+    /// Fatty acids of the mode with their mean retention time and ECL
+    fn references(&self, reference: &HashedDataFrame) -> PolarsResult<Vec<Reference>> {
+        let data_frame = SourceComputer::try_compute(SourceKey {
+            data_frame: reference,
+            settings: &SourceSettings::new(),
+        })?
+        .select([
+            col("Row"),
+            col("RetentionTime")
+                .struct_()
+                .field_by_name("Absolute")
+                .struct_()
+                .field_by_name("Mean")
+                .alias("Time"),
+            col("ChainLength").struct_().field_by_name("ECL"),
+        ])
+        .collect()?;
+        let bounds = reference["FattyAcid"].struct_()?.field_by_name("Bounds")?;
+        let bounds = bounds.list()?;
+        let mut references = Vec::new();
+        for ((row, time), ecl) in data_frame["Row"]
+            .u32()?
+            .into_iter()
+            .zip(data_frame["Time"].f64()?)
+            .zip(data_frame["ECL"].f64()?)
+        {
+            let (Some(row), Some(time), Some(ecl)) = (row, time, ecl) else {
+                continue;
+            };
+            let index = row as usize;
+            if reference["Mode"].mode().program(index)?.to_string() != self.mode {
+                continue;
+            }
+            let saturated = bounds
+                .get_as_series(index)
+                .is_some_and(|bounds| bounds.is_empty())
+                && reference["FattyAcid"].substituents(index)?.is_empty();
+            references.push(Reference {
+                row,
+                time,
+                ecl,
+                saturated,
+            });
+        }
+        Ok(references)
+    }
+
+    /// Returns the assigned data frame when the peaks are assigned
+    pub(crate) fn ui(
+        &mut self,
+        ui: &mut Ui,
+        reference: Option<&HashedDataFrame>,
+    ) -> PolarsResult<Option<DataFrame>> {
+        Grid::new("Chromatograms")
+            .show(ui, |ui| -> PolarsResult<()> {
+                ui.label(localize!("smoothing"))
+                    .on_hover_text(localize!("smoothing-description"));
+                ui.add(Slider::new(&mut self.options.smoothing, 0..=20));
+                ui.end_row();
+
+                ui.label(localize!("baseline"))
+                    .on_hover_text(localize!("baseline-description"));
+                ui.add(DragValue::new(&mut self.options.baseline).range(3..=usize::MAX));
+                ui.end_row();
+
+                ui.label(localize!("threshold"))
+                    .on_hover_text(localize!("threshold-description"));
+                ui.add(
+                    DragValue::new(&mut self.options.threshold)
+                        .range(0.0..=f64::MAX)
+                        .speed(0.1),
+                );
+                ui.end_row();
+
+                ui.label(localize!("units"))
+                    .on_hover_text(localize!("chromatograms-units-description"));
+                time_units(ui, "ChromatogramsUnits", &mut self.units);
+                ui.end_row();
+
+                ui.label(localize!("mode"))
+                    .on_hover_text(localize!("chromatograms-mode-description"));
+                let mut programs = Vec::new();
+                if let Some(reference) = reference {
+                    let modes = reference["Mode"].mode();
+                    for row in 0..reference.height() {
+                        let program = modes.program(row)?.to_string();
+                        if !programs.contains(&program) {
+                            programs.push(program);
+                        }
+                    }
+                }
+                if !programs.contains(&self.mode) {
+                    self.mode = programs.first().cloned().unwrap_or_default();
+                }
+                ComboBox::from_id_salt("ChromatogramsMode")
+                    .selected_text(&self.mode)
+                    .show_ui(ui, |ui| {
+                        for program in programs {
+                            ui.selectable_value(&mut self.mode, program.clone(), program);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(localize!("tolerance"))
+                    .on_hover_text(localize!("tolerance-description"));
+                ui.add(
+                    DragValue::new(&mut self.tolerance)
+                        .range(0.0..=1.0)
+                        .speed(0.001),
+                );
+                ui.end_row();
+                Ok(())
+            })
+            .inner?;
+        ui.separator();
+        self.peaks();
+        ScrollArea::vertical().show(ui, |ui| {
+            for (index, (name, peaks)) in self.names.iter().zip(&self.peaks).enumerate() {
+                CollapsingHeader::new(format!("{name}: {} {}", peaks.len(), localize!("peaks")))
+                    .id_salt(("ChromatogramPeaks", index))
+                    .show(ui, |ui| {
+                        Grid::new(("ChromatogramPeaks", index))
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.heading(localize!("time"));
+                                ui.heading(localize!("area"));
+                                ui.heading(localize!("width"));
+                                ui.end_row();
+                                for peak in peaks {
+                                    ui.label(format!("{:.3}", peak.time));
+                                    ui.label(format!("{:.1}", peak.area));
+                                    ui.label(format!("{:.4}", peak.width));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+        });
+        ui.separator();
+        let Some(reference) = reference else {
+            ui.label(localize!("chromatograms-description"));
+            return Ok(None);
+        };
+        if ui.button(localize!("load")).clicked() {
+            return self.assign(reference).map(Some);
+        }
+        Ok(None)
+    }
+}
+
+/// Reference fatty acid
+#[derive(Clone, Copy, Debug)]
+struct Reference {
+    row: u32,
+    time: f64,
+    ecl: f64,
+    saturated: bool,
+}
+
+/// Pairs of the reference and the peak indices
+fn assign(references: &[Reference], peaks: &[Peak], tolerance: f64) -> Vec<(usize, usize)> {
+    let ecls = |mut points: Vec<(f64, f64)>| -> Vec<f64> {
+        points.sort_by(|left, right| left.0.total_cmp(&right.0));
+        points.dedup_by(|right, left| right.0 == left.0);
+        peaks.iter().map(|peak| ecl(&points, peak.time)).collect()
+    };
+    let points: Vec<_> = references
+        .iter()
+        .map(|reference| (reference.time, reference.ecl))
+        .collect();
+    let mut peak_ecls = ecls(points);
+    let anchors = nearest(references, &peak_ecls, ANCHOR_TOLERANCE, |reference| {
+        reference.saturated
+    });
+    if anchors.len() > 1 {
+        let points: Vec<_> = anchors
+            .iter()
+            .map(|&(reference, peak)| (peaks[peak].time, references[reference].ecl))
+            .collect();
+        peak_ecls = ecls(points);
+    }
+    nearest(references, &peak_ecls, tolerance, |_| true)
+}
+
+/// Unique pairs of the nearest ECL within the tolerance
+fn nearest(
+    references: &[Reference],
+    ecls: &[f64],
+    tolerance: f64,
+    filter: impl Fn(&Reference) -> bool,
+) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for (reference_index, reference) in references.iter().enumerate() {
+        if !filter(reference) {
+            continue;
+        }
+        for (peak_index, ecl) in ecls.iter().enumerate() {
+            let distance = (ecl - reference.ecl).abs();
+            if distance <= tolerance {
+                pairs.push((distance, reference_index, peak_index));
+            }
+        }
+    }
+    pairs.sort_by(|left, right| left.0.total_cmp(&right.0));
+    let mut assigned = Vec::new();
+    for (_, reference, peak) in pairs {
+        if !assigned.iter().any(|&(assigned_reference, assigned_peak)| {
+            assigned_reference == reference || assigned_peak == peak
+        }) {
+            assigned.push((reference, peak));
+        }
+    }
+    assigned
+}
+
+/// ECL of the time by the piecewise linear interpolation of the points
+/// sorted by time, extrapolated by the outer segments
+fn ecl(points: &[(f64, f64)], time: f64) -> f64 {
+    if points.len() < 2 {
+        return f64::NAN;
+    }
+    let index = points
+        .windows(2)
+        .position(|window| time <= window[1].0)
+        .unwrap_or(points.len() - 2);
+    let ((left_time, left_ecl), (right_time, right_ecl)) = (points[index], points[index + 1]);
+    left_ecl + (time - left_time) / (right_time - left_time) * (right_ecl - left_ecl)
+}
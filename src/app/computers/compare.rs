@@ -0,0 +1,149 @@
+use super::{
+    source::{Computer as SourceComputer, Key as SourceKey},
+    worker::{Background, Interrupt, Task, Worker},
+};
+use crate::{
+    app::panes::{compare::settings::Settings, source::settings::Settings as SourceSettings},
+    utils::{HashedDataFrame, statistics::welch},
+};
+use polars::prelude::*;
+use std::hash::{Hash, Hasher};
+
+/// Compare computed
+pub(crate) type Computed = Worker<Computer>;
+
+/// Compare computer
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(key: Key<'_>, interrupt: &Interrupt) -> PolarsResult<DataFrame> {
+        let settings = SourceSettings {
+            units: key.settings.units,
+            ddof: key.settings.ddof,
+            logarithmic: key.settings.logarithmic,
+            ..SourceSettings::new()
+        };
+        let side = |data_frame: &HashedDataFrame, name: &str| -> PolarsResult<LazyFrame> {
+            let lazy_frame = SourceComputer::try_compute(SourceKey {
+                data_frame,
+                settings: &settings,
+            })?;
+            let absolute = || col("RetentionTime").struct_().field_by_name("Absolute");
+            let chain_length = || col("ChainLength").struct_();
+            Ok(lazy_frame.select([
+                col("Mode"),
+                col("FattyAcid"),
+                as_struct(vec![
+                    absolute().struct_().field_by_name("Mean").alias("Mean"),
+                    absolute()
+                        .struct_()
+                        .field_by_name("StandardDeviation")
+                        .alias("StandardDeviation"),
+                    absolute().struct_().field_by_name("Values").alias("Values"),
+                    chain_length().field_by_name("ECL").alias("ECL"),
+                    chain_length().field_by_name("FCL").alias("FCL"),
+                ])
+                .alias(name),
+            ]))
+        };
+        let field = |side: &str, name: &str| col(side).struct_().field_by_name(name);
+        let delta = |name: &str| (field("Right", name) - field("Left", name)).alias(name);
+        let present = |side: &str| field(side, "Mean").is_not_null();
+        // Hash joins on the nested mode and fatty acid structs are not
+        // implemented, the sides are matched by a group by instead
+        let lazy_frame = concat_lf_diagonal(
+            [side(key.left, "Left")?, side(key.right, "Right")?],
+            UnionArgs::default(),
+        )?
+        .group_by_stable([col("Mode"), col("FattyAcid")])
+        .agg([
+            col("Left").drop_nulls().first(),
+            col("Right").drop_nulls().first(),
+        ])
+        .with_columns([
+            as_struct(vec![
+                (field("Right", "Mean") - field("Left", "Mean")).alias("Time"),
+                delta("ECL"),
+                delta("FCL"),
+            ])
+            .alias("Delta"),
+            as_struct(vec![
+                field("Left", "Values").alias("Left"),
+                field("Right", "Values").alias("Right"),
+            ])
+            .map(p_value, GetOutput::from_type(DataType::Float64))
+            .alias("P"),
+            present("Left").and(present("Right")).alias("Matched"),
+        ])
+        .sort_by_exprs(
+            [
+                col("Mode"),
+                coalesce(&[field("Left", "Mean"), field("Right", "Mean")]),
+            ],
+            SortMultipleOptions::new().with_nulls_last(true),
+        )
+        .with_row_index("Index", None);
+        interrupt.check()?;
+        lazy_frame.collect()
+    }
+}
+
+impl Background<Key<'_>> for Computer {
+    fn task(key: Key<'_>) -> Task {
+        let left = key.left.clone();
+        let right = key.right.clone();
+        let names = key.names.clone();
+        let settings = key.settings.clone();
+        Box::new(move |interrupt| {
+            Self::try_compute(
+                Key {
+                    names: &names,
+                    left: &left,
+                    right: &right,
+                    settings: &settings,
+                },
+                interrupt,
+            )
+        })
+    }
+}
+
+/// Compare key
+#[derive(Clone, Copy, Debug)]
+pub struct Key<'a> {
+    pub(crate) names: &'a [String; 2],
+    pub(crate) left: &'a HashedDataFrame,
+    pub(crate) right: &'a HashedDataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.names.hash(state);
+        self.left.hash(state);
+        self.right.hash(state);
+        self.settings.units.hash(state);
+        self.settings.ddof.hash(state);
+        self.settings.logarithmic.hash(state);
+    }
+}
+
+/// Welch's t-test p-value of the replicate lists
+fn p_value(column: Column) -> PolarsResult<Option<Column>> {
+    let fields = column.struct_()?;
+    let left = fields.field_by_name("Left")?;
+    let right = fields.field_by_name("Right")?;
+    let values = |series: Option<Series>| -> PolarsResult<Vec<f64>> {
+        Ok(match series {
+            Some(series) => series.f64()?.into_iter().flatten().collect(),
+            None => Vec::new(),
+        })
+    };
+    let p_value = left
+        .list()?
+        .into_iter()
+        .zip(right.list()?)
+        .map(|(left, right)| Ok(welch(&values(left)?, &values(right)?).map(|welch| welch.p)))
+        .collect::<PolarsResult<Float64Chunked>>()?;
+    Ok(Some(p_value.with_name(column.name().clone()).into_column()))
+}
@@ -0,0 +1,205 @@
+use super::worker::{Background, Interrupt, Task, Worker};
+use crate::{
+    app::panes::composition::settings::{Settings, name},
+    special::{
+        column::mode::ColumnExt as _,
+        composition::{conversion_factor, response_factor},
+        derivative::Derivative,
+        isotope::ColumnExt as _,
+    },
+    utils::HashedDataFrame,
+};
+use polars::prelude::*;
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+};
+
+/// Composition computed
+pub(crate) type Computed = Worker<Computer>;
+
+/// Composition computer
+///
+/// Area and mass percents of the fatty acids per mode and sample (every
+/// value of the `Area` list is a sample) and the content by the internal
+/// standard. Masses are the areas corrected by the theoretical FID response
+/// factors of the methyl esters, the internal standard is left out of the
+/// percents.
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(key: Key<'_>, interrupt: &Interrupt) -> PolarsResult<DataFrame> {
+        let data_frame = key.data_frame;
+        let standard = &key.settings.standard;
+        let areas = data_frame["Area"].list()?;
+        let mut rows = Vec::with_capacity(data_frame.height());
+        // Modes, compared by the whole program
+        let mut programs = Vec::new();
+        for row in 0..data_frame.height() {
+            let program = data_frame["Mode"].mode().program(row)?;
+            let mode = match programs.iter().position(|mode| *mode == program) {
+                Some(mode) => mode,
+                None => {
+                    programs.push(program);
+                    programs.len() - 1
+                }
+            };
+            let formula = data_frame["FattyAcid"].formula(row)?;
+            let values = match areas.get_as_series(row) {
+                Some(values) => values.f64()?.into_iter().collect(),
+                None => Vec::new(),
+            };
+            rows.push(Row {
+                mode,
+                standard: standard.name.is_some() && name(data_frame, row)? == standard.name,
+                response: response_factor(Derivative::Methyl.formula(formula)),
+                conversion: conversion_factor(formula),
+                areas: values,
+            });
+        }
+        interrupt.check()?;
+        // Sums per mode and sample
+        let mut sums = HashMap::<_, Sum>::new();
+        for row in &rows {
+            for (sample, area) in row.areas.iter().enumerate() {
+                let Some(area) = *area else {
+                    continue;
+                };
+                let sum = sums.entry((row.mode, sample)).or_default();
+                if row.standard {
+                    sum.standard = Some(area * row.response);
+                } else {
+                    sum.area += area;
+                    sum.mass += area * row.response;
+                }
+            }
+        }
+        // Percents and content
+        let mut area_percents = Vec::with_capacity(rows.len());
+        let mut mass_percents = Vec::with_capacity(rows.len());
+        let mut contents = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let mut area_percent = Vec::with_capacity(row.areas.len());
+            let mut mass_percent = Vec::with_capacity(row.areas.len());
+            let mut content = Vec::with_capacity(row.areas.len());
+            for (sample, area) in row.areas.iter().enumerate() {
+                let sum = area.and(sums.get(&(row.mode, sample)));
+                match area.zip(sum).filter(|_| !row.standard) {
+                    Some((area, sum)) => {
+                        let mass = area * row.response;
+                        area_percent.push(Some(area / sum.area * 100.0));
+                        mass_percent.push(Some(mass / sum.mass * 100.0));
+                        // mg per g of the sample
+                        content.push(sum.standard.map(|internal| {
+                            let content = mass / internal * standard.mass / standard.sample;
+                            if standard.fatty_acids {
+                                content * row.conversion
+                            } else {
+                                content
+                            }
+                        }));
+                    }
+                    None => {
+                        area_percent.push(None);
+                        mass_percent.push(None);
+                        content.push(None);
+                    }
+                }
+            }
+            area_percents.push(Series::new(PlSmallStr::EMPTY, area_percent));
+            mass_percents.push(Series::new(PlSmallStr::EMPTY, mass_percent));
+            contents.push(Series::new(PlSmallStr::EMPTY, content));
+        }
+        interrupt.check()?;
+        let ddof = key.settings.ddof;
+        let statistics = |name: &str| {
+            as_struct(vec![
+                col(name).list().mean().alias("Mean"),
+                col(name).list().std(ddof).alias("StandardDeviation"),
+                col(name).alias("Values"),
+            ])
+            .alias(name)
+        };
+        DataFrame::new(vec![
+            data_frame["Mode"].clone(),
+            data_frame["FattyAcid"].clone(),
+            Column::new(
+                "ResponseFactor".into(),
+                rows.iter().map(|row| row.response).collect::<Vec<_>>(),
+            ),
+            Column::new(
+                "ConversionFactor".into(),
+                rows.iter().map(|row| row.conversion).collect::<Vec<_>>(),
+            ),
+            Column::new(
+                "InternalStandard".into(),
+                rows.iter().map(|row| row.standard).collect::<Vec<_>>(),
+            ),
+            Column::new("Area".into(), area_percents),
+            Column::new("Mass".into(), mass_percents),
+            Column::new("Content".into(), contents),
+        ])?
+        .lazy()
+        .select([
+            col("Mode"),
+            col("FattyAcid"),
+            col("ResponseFactor"),
+            col("ConversionFactor"),
+            col("InternalStandard"),
+            statistics("Area"),
+            statistics("Mass"),
+            statistics("Content"),
+        ])
+        .with_row_index("Index", None)
+        .collect()
+    }
+}
+
+impl Background<Key<'_>> for Computer {
+    fn task(key: Key<'_>) -> Task {
+        let data_frame = key.data_frame.clone();
+        let settings = key.settings.clone();
+        Box::new(move |interrupt| {
+            Self::try_compute(
+                Key {
+                    data_frame: &data_frame,
+                    settings: &settings,
+                },
+                interrupt,
+            )
+        })
+    }
+}
+
+/// Composition key
+#[derive(Clone, Copy, Debug)]
+pub struct Key<'a> {
+    pub(crate) data_frame: &'a HashedDataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data_frame.hash(state);
+        self.settings.ddof.hash(state);
+        self.settings.standard.hash(state);
+    }
+}
+
+/// Fatty acid of the source
+struct Row {
+    /// Index of the mode program
+    mode: usize,
+    standard: bool,
+    response: f64,
+    conversion: f64,
+    areas: Vec<Option<f64>>,
+}
+
+/// Sums of a mode and sample
+#[derive(Default)]
+struct Sum {
+    area: f64,
+    mass: f64,
+    standard: Option<f64>,
+}
@@ -1,80 +1,31 @@
-use crate::app::panes::distance::settings::{Order, Settings, Sort};
-use egui::util::cache::{ComputerMut, FrameCache};
+use super::worker::{Background, Interrupt, Task, Worker};
+use crate::{
+    app::panes::distance::settings::{Order, Settings, Sort},
+    utils::HashedDataFrame,
+};
 use polars::prelude::*;
 use std::hash::{Hash, Hasher};
 
 /// Distance computed
-pub(crate) type Computed = FrameCache<DataFrame, Computer>;
+pub(crate) type Computed = Worker<Computer>;
 
 /// Distance computer
-#[derive(Default)]
 pub(crate) struct Computer;
 
 impl Computer {
-    fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<DataFrame> {
-        let mut lazy_frame = key.data_frame.clone().lazy();
-        // Join
-        lazy_frame = lazy_frame
-            .clone()
-            .select([
-                as_struct(vec![col("FattyAcid"), col("Mode")]).alias("From"),
-                col("RetentionTime")
-                    .struct_()
-                    .field_by_name("Absolute")
-                    .struct_()
-                    .field_by_name("Mean")
-                    .alias("FromTime"),
-                col("ChainLength")
-                    .struct_()
-                    .field_by_name("ECL")
-                    .alias("FromECL"),
-            ])
-            .with_row_index("LeftIndex", None)
-            .join_builder()
-            .with(
-                lazy_frame
-                    .select([
-                        as_struct(vec![col("FattyAcid"), col("Mode")]).alias("To"),
-                        col("RetentionTime")
-                            .struct_()
-                            .field_by_name("Absolute")
-                            .struct_()
-                            .field_by_name("Mean")
-                            .alias("ToTime"),
-                        col("ChainLength")
-                            .struct_()
-                            .field_by_name("ECL")
-                            .alias("ToECL"),
-                    ])
-                    .with_row_index("RightIndex", None),
-            )
-            .join_where(vec![
-                // Same modes
-                col("From")
-                    .struct_()
-                    .field_by_name("Mode")
-                    .struct_()
-                    .field_by_name("OnsetTemperature")
-                    .eq(col("To")
-                        .struct_()
-                        .field_by_name("Mode")
-                        .struct_()
-                        .field_by_name("OnsetTemperature"))
-                    .and(
-                        col("From")
-                            .struct_()
-                            .field_by_name("Mode")
-                            .struct_()
-                            .field_by_name("TemperatureStep")
-                            .eq(col("To")
-                                .struct_()
-                                .field_by_name("Mode")
-                                .struct_()
-                                .field_by_name("TemperatureStep")),
-                    ),
-                // Fatty asids not equals combination
-                col("LeftIndex").lt(col("RightIndex")),
-            ]);
+    fn try_compute(key: Key<'_>, interrupt: &Interrupt) -> PolarsResult<DataFrame> {
+        let factor = key.settings.units.factor();
+        // Join each mode separately, so the task can be interrupted between modes
+        let mut lazy_frames = Vec::new();
+        for data_frame in key.data_frame.partition_by_stable(["Mode"], true)? {
+            interrupt.check()?;
+            lazy_frames.push(join(data_frame.lazy(), factor).collect()?.lazy());
+        }
+        let mut lazy_frame = if lazy_frames.is_empty() {
+            join(key.data_frame.clone().into_inner().lazy(), factor)
+        } else {
+            concat(lazy_frames, UnionArgs::default())?
+        };
         // Cache
         lazy_frame = lazy_frame.cache().select([
             col("From").struct_().field_by_name("Mode").alias("Mode"),
@@ -128,25 +79,38 @@ impl Computer {
         };
         // Index
         lazy_frame = lazy_frame.with_row_index("Index", None);
+        interrupt.check()?;
         lazy_frame.collect()
     }
 }
 
-impl ComputerMut<Key<'_>, DataFrame> for Computer {
-    fn compute(&mut self, key: Key<'_>) -> DataFrame {
-        self.try_compute(key).unwrap()
+impl Background<Key<'_>> for Computer {
+    fn task(key: Key<'_>) -> Task {
+        let data_frame = key.data_frame.clone();
+        let settings = key.settings.clone();
+        Box::new(move |interrupt| {
+            Self::try_compute(
+                Key {
+                    data_frame: &data_frame,
+                    settings: &settings,
+                },
+                interrupt,
+            )
+        })
     }
 }
 
 /// Distance key
 #[derive(Clone, Copy, Debug)]
 pub struct Key<'a> {
-    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) data_frame: &'a HashedDataFrame,
     pub(crate) settings: &'a Settings,
 }
 
 impl Hash for Key<'_> {
     fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data_frame.hash(state);
+        self.settings.units.hash(state);
         self.settings.filter_onset_temperature.hash(state);
         self.settings.filter_temperature_step.hash(state);
         self.settings.interpolation.hash(state);
@@ -156,3 +120,69 @@ impl Hash for Key<'_> {
         self.settings.order.hash(state);
     }
 }
+
+fn join(lazy_frame: LazyFrame, factor: f64) -> LazyFrame {
+    lazy_frame
+        .clone()
+        .select([
+            as_struct(vec![col("FattyAcid"), col("Mode")]).alias("From"),
+            (col("RetentionTime")
+                .struct_()
+                .field_by_name("Absolute")
+                .struct_()
+                .field_by_name("Mean")
+                * lit(factor))
+            .alias("FromTime"),
+            col("ChainLength")
+                .struct_()
+                .field_by_name("ECL")
+                .alias("FromECL"),
+        ])
+        .with_row_index("LeftIndex", None)
+        .join_builder()
+        .with(
+            lazy_frame
+                .select([
+                    as_struct(vec![col("FattyAcid"), col("Mode")]).alias("To"),
+                    (col("RetentionTime")
+                        .struct_()
+                        .field_by_name("Absolute")
+                        .struct_()
+                        .field_by_name("Mean")
+                        * lit(factor))
+                    .alias("ToTime"),
+                    col("ChainLength")
+                        .struct_()
+                        .field_by_name("ECL")
+                        .alias("ToECL"),
+                ])
+                .with_row_index("RightIndex", None),
+        )
+        .join_where(vec![
+            // Same modes
+            col("From")
+                .struct_()
+                .field_by_name("Mode")
+                .struct_()
+                .field_by_name("OnsetTemperature")
+                .eq(col("To")
+                    .struct_()
+                    .field_by_name("Mode")
+                    .struct_()
+                    .field_by_name("OnsetTemperature"))
+                .and(
+                    col("From")
+                        .struct_()
+                        .field_by_name("Mode")
+                        .struct_()
+                        .field_by_name("TemperatureStep")
+                        .eq(col("To")
+                            .struct_()
+                            .field_by_name("Mode")
+                            .struct_()
+                            .field_by_name("TemperatureStep")),
+                ),
+            // Fatty asids not equals combination
+            col("LeftIndex").lt(col("RightIndex")),
+        ])
+}
@@ -0,0 +1,169 @@
+use super::{
+    source::{Computer as SourceComputer, Key as SourceKey},
+    worker::{Background, Interrupt, Task, Worker},
+};
+use crate::{
+    app::panes::{increments::settings::Settings, source::settings::Settings as SourceSettings},
+    special::substituent::ExprExt as _,
+    utils::{HashedDataFrame, statistics::least_squares},
+};
+use polars::prelude::*;
+use std::{
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+};
+
+/// Increments computed
+pub(crate) type Computed = Worker<Computer>;
+
+/// Increments computer
+///
+/// Fits the fractional chain length (ECL - carbons) of the straight-chain
+/// unsaturated fatty acids of each mode as the sum of the increments of
+/// their bounds by position and geometry.
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(key: Key<'_>, interrupt: &Interrupt) -> PolarsResult<DataFrame> {
+        let settings = SourceSettings {
+            logarithmic: key.settings.logarithmic,
+            ..SourceSettings::new()
+        };
+        let data_frame = SourceComputer::try_compute(SourceKey {
+            data_frame: key.data_frame,
+            settings: &settings,
+        })?
+        .select([
+            col("Mode"),
+            col("FattyAcid"),
+            col("ChainLength").struct_().field_by_name("ECL"),
+            col("FattyAcid").substituents().alias("Substituents"),
+        ])
+        .filter(col("ECL").is_not_null().and(straight()))
+        .collect()?;
+        let mut data_frames = Vec::new();
+        for data_frame in data_frame.partition_by_stable(["Mode"], true)? {
+            interrupt.check()?;
+            data_frames.push(increments(&data_frame)?.lazy());
+        }
+        if data_frames.is_empty() {
+            return Ok(DataFrame::empty());
+        }
+        concat(data_frames, UnionArgs::default())?
+            .with_row_index("Index", None)
+            .collect()
+    }
+}
+
+impl Background<Key<'_>> for Computer {
+    fn task(key: Key<'_>) -> Task {
+        let data_frame = key.data_frame.clone();
+        let settings = key.settings.clone();
+        Box::new(move |interrupt| {
+            Self::try_compute(
+                Key {
+                    data_frame: &data_frame,
+                    settings: &settings,
+                },
+                interrupt,
+            )
+        })
+    }
+}
+
+/// Increments key
+#[derive(Clone, Copy, Debug)]
+pub struct Key<'a> {
+    pub(crate) data_frame: &'a HashedDataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data_frame.hash(state);
+        self.settings.logarithmic.hash(state);
+    }
+}
+
+/// Increments of one mode
+fn increments(data_frame: &DataFrame) -> PolarsResult<DataFrame> {
+    let fatty_acids = data_frame["FattyAcid"].struct_()?;
+    let carbons = fatty_acids.field_by_name("Carbons")?;
+    let indices = fatty_acids.field_by_name("Indices")?;
+    let bounds = fatty_acids.field_by_name("Bounds")?;
+    let ecl = data_frame["ECL"].f64()?;
+    // Bounds of every fatty acid by position and geometry
+    let mut rows = Vec::new();
+    let mut values = Vec::new();
+    let mut columns = BTreeMap::new();
+    for (((carbons, indices), bounds), ecl) in carbons
+        .u8()?
+        .into_iter()
+        .zip(indices.list()?)
+        .zip(bounds.list()?)
+        .zip(ecl)
+    {
+        let (Some(carbons), Some(indices), Some(bounds), Some(ecl)) =
+            (carbons, indices, bounds, ecl)
+        else {
+            continue;
+        };
+        let row: Vec<(u8, i8)> = indices
+            .u8()?
+            .into_iter()
+            .zip(bounds.i8()?)
+            .filter_map(|(index, bound)| Some((index?, bound?)))
+            .collect();
+        // Saturated acids are the ECL references
+        if row.is_empty() {
+            continue;
+        }
+        for &bound in &row {
+            *columns.entry(bound).or_insert(0u32) += 1;
+        }
+        rows.push(row);
+        values.push(ecl - carbons as f64);
+    }
+    let columns: Vec<((u8, i8), u32)> = columns.into_iter().collect();
+    let rows: Vec<Vec<f64>> = rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|(bound, _)| row.iter().filter(|&item| item == bound).count() as f64)
+                .collect()
+        })
+        .collect();
+    let least_squares = least_squares(&rows, &values);
+    let height = columns.len();
+    DataFrame::new(vec![
+        data_frame["Mode"].new_from_index(0, height),
+        Column::new(
+            "Position".into(),
+            columns
+                .iter()
+                .map(|((index, _), _)| *index)
+                .collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "Bound".into(),
+            columns
+                .iter()
+                .map(|((_, bound), _)| *bound)
+                .collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "Count".into(),
+            columns.iter().map(|(_, count)| *count).collect::<Vec<_>>(),
+        ),
+        Column::new("Increment".into(), least_squares.coefficients),
+        Column::new("StandardError".into(), least_squares.standard_errors),
+        Column::new("Residual".into(), vec![least_squares.residual; height]),
+    ])
+}
+
+/// Not branched, hydroxy or cyclic
+fn straight() -> Expr {
+    let substituents = |name| col("Substituents").struct_().field_by_name(name);
+    (substituents("Branches") + substituents("Hydroxy") + substituents("Rings")).eq(lit(0))
+}
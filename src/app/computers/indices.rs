@@ -0,0 +1,156 @@
+use super::worker::{Background, Interrupt, Task, Worker};
+use crate::{
+    app::panes::composition::settings::Settings,
+    special::{
+        column::mode::ColumnExt as _,
+        indices::{Acid, ColumnExt as _, Indices},
+        program::Program,
+    },
+    utils::HashedDataFrame,
+};
+use polars::prelude::*;
+use std::hash::{Hash, Hasher};
+
+/// Indices computed
+pub(crate) type Computed = Worker<Computer>;
+
+/// Indices computer
+///
+/// Nutritional indices of the composition per mode and sample. The internal
+/// standard is left out.
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(key: Key<'_>, interrupt: &Interrupt) -> PolarsResult<DataFrame> {
+        let data_frame = key.data_frame;
+        let values = data_frame[key.settings.basis.name()]
+            .struct_()?
+            .field_by_name("Values")?;
+        let values = values.list()?;
+        let standards = data_frame["InternalStandard"].bool()?;
+        // Rows of every mode, compared by the whole program
+        let mut modes = Vec::<(Program, Vec<usize>)>::new();
+        for row in 0..data_frame.height() {
+            if standards.get(row) == Some(true) {
+                continue;
+            }
+            let program = data_frame["Mode"].mode().program(row)?;
+            match modes.iter_mut().find(|(mode, _)| *mode == program) {
+                Some((_, rows)) => rows.push(row),
+                None => modes.push((program, vec![row])),
+            }
+        }
+        let acids = (0..data_frame.height())
+            .map(|row| data_frame["FattyAcid"].acid(row))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        interrupt.check()?;
+        let mut firsts = Vec::with_capacity(modes.len());
+        let mut columns = vec![Vec::with_capacity(modes.len()); NAMES.len()];
+        for (_, rows) in &modes {
+            firsts.push(rows[0] as IdxSize);
+            // Percents of every sample
+            let mut samples = Vec::<Vec<(&Acid, f64)>>::new();
+            for &row in rows {
+                let Some(series) = values.get_as_series(row) else {
+                    continue;
+                };
+                for (sample, value) in series.f64()?.into_iter().enumerate() {
+                    if samples.len() <= sample {
+                        samples.resize_with(sample + 1, Vec::new);
+                    }
+                    if let Some(value) = value {
+                        samples[sample].push((&acids[row], value));
+                    }
+                }
+            }
+            let indices: Vec<_> = samples.into_iter().map(Indices::new).collect();
+            for (index, column) in columns.iter_mut().enumerate() {
+                let samples: Vec<_> = indices
+                    .iter()
+                    .map(|indices| value(indices, index).filter(|value| value.is_finite()))
+                    .collect();
+                column.push(Series::new(PlSmallStr::EMPTY, samples));
+            }
+        }
+        interrupt.check()?;
+        let ddof = key.settings.ddof;
+        let mut data_frame = DataFrame::new(vec![
+            data_frame
+                .take(&IdxCa::from_vec(PlSmallStr::EMPTY, firsts))?
+                .column("Mode")?
+                .clone(),
+        ])?;
+        for (name, column) in NAMES.into_iter().zip(columns) {
+            data_frame.with_column(Column::new(name.into(), column))?;
+        }
+        data_frame
+            .lazy()
+            .select(
+                [col("Mode")]
+                    .into_iter()
+                    .chain(NAMES.map(|name| {
+                        as_struct(vec![
+                            col(name).list().mean().alias("Mean"),
+                            col(name).list().std(ddof).alias("StandardDeviation"),
+                            col(name).alias("Values"),
+                        ])
+                        .alias(name)
+                    }))
+                    .collect::<Vec<_>>(),
+            )
+            .with_row_index("Index", None)
+            .collect()
+    }
+}
+
+impl Background<Key<'_>> for Computer {
+    fn task(key: Key<'_>) -> Task {
+        let data_frame = key.data_frame.clone();
+        let settings = key.settings.clone();
+        Box::new(move |interrupt| {
+            Self::try_compute(
+                Key {
+                    data_frame: &data_frame,
+                    settings: &settings,
+                },
+                interrupt,
+            )
+        })
+    }
+}
+
+/// Indices key
+#[derive(Clone, Copy, Debug)]
+pub struct Key<'a> {
+    pub(crate) data_frame: &'a HashedDataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data_frame.hash(state);
+        self.settings.ddof.hash(state);
+        self.settings.standard.hash(state);
+        self.settings.basis.hash(state);
+    }
+}
+
+/// Columns of the indices
+pub(crate) const NAMES: [&str; 10] = [
+    "SFA", "MUFA", "PUFA", "N3", "N6", "N6N3", "UI", "AI", "TI", "HH",
+];
+
+fn value(indices: &Indices, index: usize) -> Option<f64> {
+    match index {
+        0 => Some(indices.saturated),
+        1 => Some(indices.monounsaturated),
+        2 => Some(indices.polyunsaturated),
+        3 => Some(indices.omega3),
+        4 => Some(indices.omega6),
+        5 => indices.ratio(),
+        6 => Some(indices.unsaturation),
+        7 => indices.atherogenic,
+        8 => indices.thrombogenic,
+        _ => indices.hypocholesterolemic,
+    }
+}
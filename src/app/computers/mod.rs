@@ -1,7 +1,23 @@
 pub(crate) use self::{
+    compare::{Computed as CompareComputed, Key as CompareKey},
+    composition::{Computed as CompositionComputed, Key as CompositionKey},
     distance::{Computed as DistanceComputed, Key as DistanceKey},
+    increments::{Computed as IncrementsComputed, Key as IncrementsKey},
+    indices::{Computed as IndicesComputed, Key as IndicesKey},
+    reference::{Computed as ReferenceComputed, Key as ReferenceKey},
+    reproducibility::{Computed as ReproducibilityComputed, Key as ReproducibilityKey},
     source::{Computed as SourceComputed, Key as SourceKey},
+    spectrum::{Computed as SpectrumComputed, Key as SpectrumKey},
+    worker::Status,
 };
 
+pub(crate) mod compare;
+pub(crate) mod composition;
 pub(crate) mod distance;
+pub(crate) mod increments;
+pub(crate) mod indices;
+pub(crate) mod reference;
+pub(crate) mod reproducibility;
 pub(crate) mod source;
+pub(crate) mod spectrum;
+pub(crate) mod worker;
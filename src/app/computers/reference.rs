@@ -0,0 +1,157 @@
+use super::worker::{Background, Interrupt, Task, Worker};
+use crate::{
+    app::panes::composition::settings::Settings,
+    special::{
+        indices::ColumnExt as _,
+        reference::{Range, ranges},
+    },
+    utils::HashedDataFrame,
+};
+use polars::prelude::*;
+use std::hash::{Hash, Hasher};
+
+/// Reference computed
+pub(crate) type Computed = Worker<Computer>;
+
+/// Reference computer
+///
+/// Compares the composition of every sample with the reference ranges of
+/// the fatty acids: out of range flags and the robust z-score.
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(key: Key<'_>, interrupt: &Interrupt) -> PolarsResult<DataFrame> {
+        let data_frame = key.data_frame;
+        let ranges = ranges(key.reference)?;
+        let values = data_frame[key.settings.basis.name()]
+            .struct_()?
+            .field_by_name("Values")?;
+        let values = values.list()?;
+        let standards = data_frame["InternalStandard"].bool()?;
+        let mut rows = Vec::new();
+        let mut matched = Vec::<&Range>::new();
+        let mut deviations = Vec::new();
+        let mut outs = Vec::new();
+        let mut flagged = Vec::new();
+        for row in 0..data_frame.height() {
+            if standards.get(row) == Some(true) {
+                continue;
+            }
+            let acid = data_frame["FattyAcid"].acid(row)?;
+            let Some(range) = ranges.iter().find(|range| range.matches(&acid)) else {
+                continue;
+            };
+            let samples: Vec<Option<f64>> = match values.get_as_series(row) {
+                Some(values) => values.f64()?.into_iter().collect(),
+                None => Vec::new(),
+            };
+            deviations.push(Series::new(
+                PlSmallStr::EMPTY,
+                samples
+                    .iter()
+                    .map(|value| value.and_then(|value| range.deviation(value)))
+                    .collect::<Vec<_>>(),
+            ));
+            outs.push(Series::new(
+                PlSmallStr::EMPTY,
+                samples
+                    .iter()
+                    .map(|value| value.map(|value| range.out(value)))
+                    .collect::<Vec<_>>(),
+            ));
+            flagged.push(
+                samples
+                    .iter()
+                    .filter(|value| value.is_some_and(|value| range.out(value)))
+                    .count() as u32,
+            );
+            rows.push(row as IdxSize);
+            matched.push(range);
+        }
+        interrupt.check()?;
+        let ddof = key.settings.ddof;
+        let statistics = |name: &str| {
+            as_struct(vec![
+                col(name).list().mean().alias("Mean"),
+                col(name).list().std(ddof).alias("StandardDeviation"),
+                col(name).alias("Values"),
+            ])
+            .alias(name)
+        };
+        let taken = data_frame.take(&IdxCa::from_vec(PlSmallStr::EMPTY, rows))?;
+        let reference = |f: fn(&Range) -> Option<f64>| {
+            matched.iter().map(|&range| f(range)).collect::<Vec<_>>()
+        };
+        DataFrame::new(vec![
+            taken["Mode"].clone(),
+            taken["FattyAcid"].clone(),
+            taken[key.settings.basis.name()]
+                .clone()
+                .with_name("Value".into()),
+            Column::new("Median".into(), reference(|range| range.median)),
+            Column::new(
+                "InterquartileRange".into(),
+                reference(|range| range.interquartile_range),
+            ),
+            Column::new("Min".into(), reference(|range| range.min)),
+            Column::new("Max".into(), reference(|range| range.max)),
+            Column::new("Deviation".into(), deviations),
+            Column::new("Out".into(), outs),
+            Column::new("Flagged".into(), flagged),
+        ])?
+        .lazy()
+        .select([
+            col("Mode"),
+            col("FattyAcid"),
+            col("Value"),
+            as_struct(vec![
+                col("Median"),
+                col("InterquartileRange"),
+                col("Min"),
+                col("Max"),
+            ])
+            .alias("Reference"),
+            statistics("Deviation"),
+            col("Out"),
+            col("Flagged"),
+        ])
+        .with_row_index("Index", None)
+        .collect()
+    }
+}
+
+impl Background<Key<'_>> for Computer {
+    fn task(key: Key<'_>) -> Task {
+        let data_frame = key.data_frame.clone();
+        let reference = key.reference.clone();
+        let settings = key.settings.clone();
+        Box::new(move |interrupt| {
+            Self::try_compute(
+                Key {
+                    data_frame: &data_frame,
+                    reference: &reference,
+                    settings: &settings,
+                },
+                interrupt,
+            )
+        })
+    }
+}
+
+/// Reference key
+#[derive(Clone, Copy, Debug)]
+pub struct Key<'a> {
+    pub(crate) data_frame: &'a HashedDataFrame,
+    pub(crate) reference: &'a HashedDataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data_frame.hash(state);
+        self.reference.hash(state);
+        self.settings.ddof.hash(state);
+        self.settings.standard.hash(state);
+        self.settings.basis.hash(state);
+    }
+}
@@ -0,0 +1,175 @@
+use super::worker::{Background, Interrupt, Task, Worker};
+use crate::{
+    app::panes::reproducibility::settings::Settings,
+    utils::{HashedDataFrame, statistics::fisher_f},
+};
+use polars::prelude::*;
+use std::hash::{Hash, Hasher};
+
+/// Reproducibility computed
+pub(crate) type Computed = Worker<Computer>;
+
+/// Reproducibility computer
+///
+/// One-way ANOVA of the retention time per fatty acid and mode with the
+/// batch as the factor (ISO 5725-2).
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(key: Key<'_>, interrupt: &Interrupt) -> PolarsResult<DataFrame> {
+        let factor = key.settings.units.factor();
+        // Groups
+        let lazy_frame = key
+            .data_frame
+            .clone()
+            .into_inner()
+            .lazy()
+            .select([
+                as_struct(vec![
+                    col("OnsetTemperature"),
+                    col("TemperatureStep"),
+                    col("Segments"),
+                    col("MaxTemperature"),
+                ])
+                .alias("Mode"),
+                col("FA").alias("FattyAcid"),
+                col("Time"),
+                col("Batch"),
+            ])
+            .explode([col("Time"), col("Batch")])
+            .filter(col("Time").is_not_null())
+            .with_column(col("Time") * lit(factor))
+            .group_by_stable([col("Mode"), col("FattyAcid"), col("Batch")])
+            .agg([
+                len().cast(DataType::Float64).alias("Count"),
+                col("Time").mean().alias("Mean"),
+                (col("Time") - col("Time").mean())
+                    .pow(2)
+                    .sum()
+                    .alias("SumOfSquares"),
+            ]);
+        interrupt.check()?;
+        // Fatty acids
+        let count = || col("Count");
+        let total = || count().sum();
+        let mean = || (count() * col("Mean")).sum() / total();
+        let lazy_frame = lazy_frame
+            .group_by_stable([col("Mode"), col("FattyAcid")])
+            .agg([
+                len().cast(DataType::Float64).alias("Batches"),
+                total().alias("Count"),
+                mean().alias("Mean"),
+                (count() * (col("Mean") - mean()).pow(2))
+                    .sum()
+                    .alias("Between"),
+                col("SumOfSquares").sum().alias("Within"),
+                count().pow(2).sum().alias("SquaredCounts"),
+            ]);
+        // Variance components
+        let batches = || col("Batches");
+        let count = || col("Count");
+        // The repeatability needs the within-batch degrees of freedom only
+        let repeatable = || count().gt(batches());
+        let valid = || batches().gt(lit(1)).and(repeatable());
+        let within = || col("Within") / (count() - batches());
+        let between = || col("Between") / (batches() - lit(1));
+        // Effective number of values per batch for unbalanced batches
+        let n0 = || (count() - col("SquaredCounts") / count()) / (batches() - lit(1));
+        let batch = || {
+            let variance = (between() - within()) / n0();
+            when(variance.clone().gt(lit(0.0)))
+                .then(variance)
+                .otherwise(lit(0.0))
+        };
+        let lazy_frame = lazy_frame
+            .select([
+                col("Mode"),
+                col("FattyAcid"),
+                batches(),
+                count(),
+                col("Mean"),
+                when(repeatable())
+                    .then(within().sqrt())
+                    .otherwise(lit(NULL))
+                    .alias("Repeatability"),
+                when(valid())
+                    .then(batch().sqrt())
+                    .otherwise(lit(NULL))
+                    .alias("Batch"),
+                when(valid())
+                    .then((within() + batch()).sqrt())
+                    .otherwise(lit(NULL))
+                    .alias("Reproducibility"),
+                when(valid())
+                    .then(between() / within())
+                    .otherwise(lit(NULL))
+                    .alias("F"),
+            ])
+            .with_column(
+                as_struct(vec![
+                    col("F"),
+                    (batches() - lit(1)).alias("Df1"),
+                    (count() - batches()).alias("Df2"),
+                ])
+                .map(p_value, GetOutput::from_type(DataType::Float64))
+                .alias("P"),
+            )
+            .with_columns([
+                batches().cast(DataType::UInt32),
+                count().cast(DataType::UInt32),
+            ])
+            .sort_by_exprs(
+                [col("Mode"), col("Mean")],
+                SortMultipleOptions::new().with_nulls_last(true),
+            )
+            .with_row_index("Index", None);
+        interrupt.check()?;
+        lazy_frame.collect()
+    }
+}
+
+impl Background<Key<'_>> for Computer {
+    fn task(key: Key<'_>) -> Task {
+        let data_frame = key.data_frame.clone();
+        let settings = key.settings.clone();
+        Box::new(move |interrupt| {
+            Self::try_compute(
+                Key {
+                    data_frame: &data_frame,
+                    settings: &settings,
+                },
+                interrupt,
+            )
+        })
+    }
+}
+
+/// Reproducibility key
+#[derive(Clone, Copy, Debug)]
+pub struct Key<'a> {
+    pub(crate) data_frame: &'a HashedDataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data_frame.hash(state);
+        self.settings.units.hash(state);
+    }
+}
+
+/// F-test p-value of the batch effect
+fn p_value(column: Column) -> PolarsResult<Option<Column>> {
+    let fields = column.struct_()?;
+    let f = fields.field_by_name("F")?;
+    let df1 = fields.field_by_name("Df1")?;
+    let df2 = fields.field_by_name("Df2")?;
+    let p_value = f
+        .f64()?
+        .into_iter()
+        .zip(df1.f64()?)
+        .zip(df2.f64()?)
+        .map(|((f, df1), df2)| Some(fisher_f(f.filter(|f| f.is_finite())?, df1?, df2?)))
+        .collect::<Float64Chunked>();
+    Ok(Some(p_value.with_name(column.name().clone()).into_column()))
+}
@@ -1,8 +1,15 @@
-use crate::app::{
-    MAX_TEMPERATURE,
-    panes::source::settings::{Group, Kind, Order, Settings, Sort},
+use super::worker::{Background, Interrupt, Task, Worker};
+use crate::{
+    app::{
+        panes::source::settings::{Group, Kind, Order, Settings, Sort},
+        units::TimeUnits,
+    },
+    special::{
+        alignment::align, column::mode::ModeSeries, derivative::Derivative, fragment::ExprExt as _,
+        substituent::ExprExt as _,
+    },
+    utils::HashedDataFrame,
 };
-use egui::util::cache::{ComputerMut, FrameCache};
 use lipid::fatty_acid::{
     Kind as FattyAcidKind,
     polars::{
@@ -11,22 +18,46 @@ use lipid::fatty_acid::{
     },
 };
 use polars::prelude::*;
-use std::{
-    f64::NAN,
-    hash::{Hash, Hasher},
-};
+use std::hash::{Hash, Hasher};
 
 /// Source computed
-pub(crate) type Computed = FrameCache<DataFrame, Computer>;
+pub(crate) type Computed = Worker<Computer>;
 
 /// Source computer
-#[derive(Default)]
 pub(crate) struct Computer;
 
 impl Computer {
-    fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<LazyFrame> {
-        let mut lazy_frame = key.data_frame.clone().lazy();
+    pub(crate) fn try_compute(key: Key<'_>) -> PolarsResult<LazyFrame> {
+        let units = key.settings.units;
+        let derivative = key.settings.derivative;
+        // Fragments
+        let mut fragments = vec![molecular_ion(derivative).alias("M")];
+        for &(name, loss) in derivative.losses() {
+            fragments.push((molecular_ion(derivative) - lit(loss)).alias(name));
+        }
+        for &(name, ion) in derivative.ions() {
+            fragments.push(lit(ion).alias(name));
+        }
+        fragments.push(
+            if derivative.polyene() {
+                col("FattyAcid").diagnostic()
+            } else {
+                lit(NULL).cast(DataType::List(Box::new(DataType::Float64)))
+            }
+            .alias("Diagnostic"),
+        );
+        let mut lazy_frame = aligned(key.data_frame, key.settings)?.lazy();
         lazy_frame = lazy_frame
+            .with_row_index("Row", None)
+            // Display units
+            .with_columns([
+                col("RetentionTime")
+                    .list()
+                    .eval(col("") * lit(units.factor()), true),
+                col("UnalignedRetentionTime")
+                    .list()
+                    .eval(col("") * lit(units.factor()), true),
+            ])
             .with_columns([
                 // Retention time mean
                 col("RetentionTime")
@@ -38,54 +69,86 @@ impl Computer {
                     .list()
                     .std(key.settings.ddof)
                     .alias("RetentionTimeStandardDeviation"),
+                col("UnalignedRetentionTime")
+                    .list()
+                    .std(key.settings.ddof)
+                    .alias("UnalignedRetentionTimeStandardDeviation"),
+                // Substituents
+                col("FattyAcid").substituents().alias("Substituents"),
             ])
             .with_columns([
-                // Relative retention time
-                relative_time(key.settings)
+                // Reference retention time
+                reference_time(col("RetentionTimeMean"), key.settings)
                     .over(["Mode"])
-                    .alias("RelativeRetentionTime"),
+                    .alias("ReferenceRetentionTimeMean"),
+                reference_time(col("RetentionTimeStandardDeviation"), key.settings)
+                    .over(["Mode"])
+                    .alias("ReferenceRetentionTimeStandardDeviation"),
                 // Delta retention time
                 col("FattyAcid")
                     .fatty_acid()
-                    .delta(col("RetentionTimeMean"))
+                    .delta(straight(col("RetentionTimeMean")))
                     .over(["Mode"])
                     .alias("DeltaRetentionTime"),
                 // Temperature
-                (col("Mode").struct_().field_by_name("OnsetTemperature")
-                    + col("RetentionTimeMean")
-                        * col("Mode").struct_().field_by_name("TemperatureStep"))
-                .clip_max(lit(MAX_TEMPERATURE))
+                as_struct(vec![
+                    col("Mode"),
+                    (col("RetentionTimeMean") * lit(units.display.convert(1.0, TimeUnits::Minute)))
+                        .alias("Time"),
+                ])
+                .map(temperature, GetOutput::from_type(DataType::Float64))
                 .alias("Temperature"),
                 // FCL
-                col("FattyAcid")
-                    .fatty_acid()
-                    .fcl(
-                        col("RetentionTimeMean"),
-                        Options::new().logarithmic(key.settings.logarithmic),
+                when(substituted())
+                    .then(
+                        substituted_ecl(col("RetentionTimeMean"), key.settings.logarithmic)
+                            - carbons(),
                     )
+                    .otherwise(col("FattyAcid").fatty_acid().fcl(
+                        straight(col("RetentionTimeMean")),
+                        Options::new().logarithmic(key.settings.logarithmic),
+                    ))
                     .over(["Mode"])
                     .alias("FCL"),
                 // ECL
-                col("FattyAcid")
-                    .fatty_acid()
-                    .ecl(
+                when(substituted())
+                    .then(substituted_ecl(
                         col("RetentionTimeMean"),
+                        key.settings.logarithmic,
+                    ))
+                    .otherwise(col("FattyAcid").fatty_acid().ecl(
+                        straight(col("RetentionTimeMean")),
                         Options::new().logarithmic(key.settings.logarithmic),
-                    )
+                    ))
                     .over(["Mode"])
                     .alias("ECL"),
-                // ECN
-                col("FattyAcid").fatty_acid().ecn().alias("ECN"),
+                // ECN, a ring counts as a double bond
+                (col("FattyAcid").fatty_acid().ecn().cast(DataType::Int64)
+                    - substituents("Rings").cast(DataType::Int64) * lit(2))
+                .alias("ECN"),
             ])
             .with_columns([
+                // Relative retention time
+                relative_time(key.settings).alias("RelativeRetentionTime"),
+                // Temperature ECL
+                when(substituted())
+                    .then(substituted_ecl(col("Temperature"), false))
+                    .otherwise(
+                        col("FattyAcid")
+                            .fatty_acid()
+                            .ecl(straight(col("Temperature")), Options::new()),
+                    )
+                    .over(["Mode"])
+                    .alias("TECL"),
                 // Slope
                 col("FattyAcid")
                     .fatty_acid()
-                    .slope(col("ECL"), col("RetentionTimeMean"))
+                    .slope(straight(col("ECL")), straight(col("RetentionTimeMean")))
                     .over(["Mode"])
                     .alias("Slope"),
             ])
             .select([
+                col("Row"),
                 col("Mode"),
                 col("FattyAcid"),
                 // Retention time
@@ -93,6 +156,8 @@ impl Computer {
                     as_struct(vec![
                         col("RetentionTimeMean").alias("Mean"),
                         col("RetentionTimeStandardDeviation").alias("StandardDeviation"),
+                        col("UnalignedRetentionTimeStandardDeviation")
+                            .alias("UnalignedStandardDeviation"),
                         col("RetentionTime").alias("Values"),
                     ])
                     .alias("Absolute"),
@@ -103,27 +168,23 @@ impl Computer {
                 // Temperature
                 col("Temperature"),
                 // Chain length
-                as_struct(vec![col("ECL"), col("FCL"), col("ECN")]).alias("ChainLength"),
+                as_struct(vec![col("ECL"), col("FCL"), col("ECN"), col("TECL")])
+                    .alias("ChainLength"),
                 // Mass
                 as_struct(vec![
-                    col("FattyAcid")
-                        .fatty_acid()
-                        .mass(FattyAcidKind::Rco)
+                    (col("FattyAcid").fatty_acid().mass(FattyAcidKind::Rco) + substituents("Mass"))
                         .alias("RCO"),
-                    col("FattyAcid")
-                        .fatty_acid()
-                        .mass(FattyAcidKind::Rcoo)
-                        .alias("RCOO"),
-                    col("FattyAcid")
-                        .fatty_acid()
-                        .mass(FattyAcidKind::Rcooh)
-                        .alias("RCOOH"),
-                    col("FattyAcid")
-                        .fatty_acid()
-                        .mass(FattyAcidKind::Rcooch3)
-                        .alias("RCOOCH3"),
+                    (col("FattyAcid").fatty_acid().mass(FattyAcidKind::Rcoo)
+                        + substituents("Mass"))
+                    .alias("RCOO"),
+                    (col("FattyAcid").fatty_acid().mass(FattyAcidKind::Rcooh)
+                        + substituents("Mass"))
+                    .alias("RCOOH"),
+                    molecular_ion(derivative).alias("Derivative"),
                 ])
                 .alias("Mass"),
+                // Fragments
+                as_struct(fragments).alias("Fragments"),
                 // Derivative
                 as_struct(vec![
                     col("Slope"),
@@ -169,9 +230,9 @@ impl Computer {
     }
 }
 
-impl ComputerMut<Key<'_>, DataFrame> for Computer {
-    fn compute(&mut self, key: Key<'_>) -> DataFrame {
-        let mut lazy_frame = self.try_compute(key).expect("compute source");
+impl Computer {
+    fn try_collect(key: Key<'_>, interrupt: &Interrupt) -> PolarsResult<DataFrame> {
+        let mut lazy_frame = Self::try_compute(key)?;
         lazy_frame = match key.settings.kind {
             Kind::Plot => {
                 lazy_frame = lazy_frame.select([
@@ -187,9 +248,13 @@ impl ComputerMut<Key<'_>, DataFrame> for Computer {
                         .struct_()
                         .field_by_name("ECL")
                         .alias("ECL"),
+                    col("Temperature"),
+                    col("Mode")
+                        .struct_()
+                        .field_by_name("TemperatureStep")
+                        .alias("HeatingRate"),
                 ]);
-                println!("lazy_frame: {}", lazy_frame.clone().collect().unwrap());
-                let lazy_frame = lazy_frame
+                lazy_frame
                     .group_by([match key.settings.group {
                         Group::FattyAcid => col("FattyAcid"),
                         Group::OnsetTemperature => {
@@ -199,31 +264,55 @@ impl ComputerMut<Key<'_>, DataFrame> for Computer {
                             col("Mode").struct_().field_by_name("TemperatureStep")
                         }
                     }])
-                    .agg([col("RetentionTime"), col("ECL")]);
-                println!("lazy_frame: {}", lazy_frame.clone().collect().unwrap());
-                lazy_frame
+                    .agg([
+                        col("RetentionTime"),
+                        col("ECL"),
+                        col("Temperature"),
+                        col("HeatingRate"),
+                    ])
             }
             Kind::Table => lazy_frame,
         };
         // Index
         lazy_frame = lazy_frame.cache().with_row_index("Index", None);
-        lazy_frame.collect().expect("collect source")
+        interrupt.check()?;
+        lazy_frame.collect()
+    }
+}
+
+impl Background<Key<'_>> for Computer {
+    fn task(key: Key<'_>) -> Task {
+        let data_frame = key.data_frame.clone();
+        let settings = key.settings.clone();
+        Box::new(move |interrupt| {
+            Self::try_collect(
+                Key {
+                    data_frame: &data_frame,
+                    settings: &settings,
+                },
+                interrupt,
+            )
+        })
     }
 }
 
 /// Source key
 #[derive(Clone, Copy, Debug)]
 pub struct Key<'a> {
-    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) data_frame: &'a HashedDataFrame,
     pub(crate) settings: &'a Settings,
 }
 
 impl Hash for Key<'_> {
     fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data_frame.hash(state);
+        self.settings.units.hash(state);
+        self.settings.derivative.hash(state);
         self.settings.kind.hash(state);
         self.settings.ddof.hash(state);
         self.settings.logarithmic.hash(state);
-        self.settings.relative.hash(state);
+        self.settings.reference.hash(state);
+        self.settings.alignment.hash(state);
         self.settings.filter.hash(state);
         self.settings.sort.hash(state);
         self.settings.order.hash(state);
@@ -260,30 +349,140 @@ impl LazyFrameExt for LazyFrame {
     }
 }
 
-fn relative_time(settings: &Settings) -> Expr {
-    match &settings.relative {
-        Some(relative) => {
-            col("RetentionTimeMean")
-                / col("RetentionTimeMean")
-                    .filter(
-                        // col("FattyAcid")
-                        //     .fatty_acid()
-                        //     .c()
-                        //     .eq(lit(relative.carbons))
-                        //     .and(col("FattyAcid").fatty_acid().indices().eq(lit(Scalar::new(
-                        //         DataType::List(Box::new(DataType::UInt8)),
-                        //         AnyValue::List(Series::from_iter(relative.indices.iter())),
-                        //     ))))
-                        //     .and(col("FattyAcid").fatty_acid().bounds().eq(lit(Scalar::new(
-                        //         DataType::List(Box::new(DataType::Int8)),
-                        //         AnyValue::List(Series::from_iter(relative.bounds.iter())),
-                        //     )))),
-                        col("FattyAcid").fatty_acid().equal(relative),
-                    )
-                    .first()
+/// Source with the runs aligned by the anchors, the `UnalignedRetentionTime`
+/// keeps the retention times as measured
+fn aligned(data_frame: &DataFrame, settings: &Settings) -> PolarsResult<DataFrame> {
+    let mut data_frame = data_frame.clone();
+    let unaligned = data_frame["RetentionTime"]
+        .clone()
+        .with_name("UnalignedRetentionTime".into());
+    if !settings.alignment.fatty_acids.is_empty() {
+        let mut anchor = lit(false);
+        for fatty_acid in &settings.alignment.fatty_acids {
+            anchor = anchor.or(col("FattyAcid").fatty_acid().equal(fatty_acid));
         }
-        None => lit(NAN),
+        let anchors = data_frame
+            .clone()
+            .lazy()
+            .select([anchor.alias("Anchor")])
+            .collect()?;
+        let retention_time = align(
+            &data_frame["Mode"],
+            &data_frame["RetentionTime"],
+            data_frame.column("Replicate").ok(),
+            anchors["Anchor"].bool()?,
+            settings.alignment.correction,
+        )?
+        .cast(data_frame["RetentionTime"].dtype())?;
+        data_frame.with_column(retention_time)?;
     }
+    data_frame.with_column(unaligned)?;
+    Ok(data_frame)
+}
+
+/// Elution temperature of the mode temperature program
+fn temperature(column: Column) -> PolarsResult<Option<Column>> {
+    let fields = column.struct_()?;
+    let mode = ModeSeries::new(&fields.field_by_name("Mode")?.into_column())?;
+    let time = fields.field_by_name("Time")?;
+    let temperature = time
+        .f64()?
+        .iter()
+        .enumerate()
+        .map(|(row, time)| match time {
+            Some(time) => Ok(Some(mode.program(row)?.temperature(time))),
+            None => Ok(None),
+        })
+        .collect::<PolarsResult<Float64Chunked>>()?;
+    Ok(Some(
+        temperature.with_name(column.name().clone()).into_column(),
+    ))
+}
+
+/// Field of the substituents struct
+fn substituents(name: &str) -> Expr {
+    col("Substituents").struct_().field_by_name(name)
+}
+
+/// Branched, hydroxy or cyclic fatty acid
+fn substituted() -> Expr {
+    (substituents("Branches") + substituents("Hydroxy") + substituents("Rings")).gt(lit(0))
+}
+
+/// Molecular ion of the derivative
+fn molecular_ion(derivative: Derivative) -> Expr {
+    col("FattyAcid").fatty_acid().mass(FattyAcidKind::Rcooh)
+        + substituents("Mass")
+        + lit(derivative.mass())
+}
+
+fn carbons() -> Expr {
+    col("FattyAcid").struct_().field_by_name("Carbons")
+}
+
+/// Masks substituted fatty acids, so they are not taken as references
+///
+/// A saturated branched or cyclic acid is not a straight-chain standard.
+fn straight(expr: Expr) -> Expr {
+    when(substituted()).then(lit(NULL)).otherwise(expr)
+}
+
+/// ECL of a substituted fatty acid
+///
+/// Interpolated between the surrounding straight-chain saturated standards,
+/// so the characteristic offsets (ECL - carbons) of the iso, anteiso,
+/// hydroxy and cyclopropane acids are preserved.
+fn substituted_ecl(expr: Expr, logarithmic: bool) -> Expr {
+    let value = |expr: Expr| {
+        if logarithmic {
+            expr.log(std::f64::consts::E)
+        } else {
+            expr
+        }
+    };
+    // Previous and next straight-chain saturated acids
+    let previous = |expr: Expr| col("FattyAcid").fatty_acid().forward(straight(expr));
+    let next = |expr: Expr| col("FattyAcid").fatty_acid().backward(straight(expr));
+    let carbons = || carbons().cast(DataType::Float64);
+    previous(carbons())
+        + (next(carbons()) - previous(carbons()))
+            * (value(expr.clone()) - value(previous(expr.clone())))
+            / (value(next(expr.clone())) - value(previous(expr)))
+}
+
+/// Retention time of the first reference present in the mode
+fn reference_time(expr: Expr, settings: &Settings) -> Expr {
+    let mut reference = lit(NULL).cast(DataType::Float64);
+    for fatty_acid in settings.reference.fatty_acids.iter().rev() {
+        let first = |expr: Expr| {
+            expr.filter(col("FattyAcid").fatty_acid().equal(fatty_acid))
+                .first()
+        };
+        reference = when(first(col("RetentionTimeMean")).is_not_null())
+            .then(first(expr.clone()))
+            .otherwise(reference);
+    }
+    reference
+}
+
+/// Relative retention time
+///
+/// The ratio of the retention times adjusted by the dead time, the standard
+/// deviation is propagated from the relative standard deviations of the
+/// both.
+fn relative_time(settings: &Settings) -> Expr {
+    let dead_time = || lit(settings.reference.dead_time * settings.units.factor());
+    let adjusted = col("RetentionTimeMean") - dead_time();
+    let reference = col("ReferenceRetentionTimeMean") - dead_time();
+    let relative = adjusted.clone() / reference.clone();
+    as_struct(vec![
+        relative.clone().alias("Mean"),
+        (relative
+            * ((col("RetentionTimeStandardDeviation") / adjusted).pow(2)
+                + (col("ReferenceRetentionTimeStandardDeviation") / reference).pow(2))
+            .sqrt())
+        .alias("StandardDeviation"),
+    ])
 }
 
 /// Saturated
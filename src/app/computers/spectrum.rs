@@ -0,0 +1,209 @@
+use super::{
+    source::{Computer as SourceComputer, Key as SourceKey},
+    worker::{Background, Interrupt, Task, Worker},
+};
+use crate::{
+    app::panes::{source::settings::Settings as SourceSettings, spectrum::settings::Settings},
+    special::{spectrum::Similarity, substituent::ColumnExt as _},
+    utils::HashedDataFrame,
+};
+use lipid::fatty_acid::{
+    display::{COMMON, DisplayWithOptions as _},
+    polars::ColumnExt as _,
+};
+use polars::prelude::*;
+use std::hash::{Hash, Hasher};
+
+/// Spectrum computed
+pub(crate) type Computed = Worker<Computer>;
+
+/// Spectrum computer
+///
+/// Matches the spectra to the fatty acids by name and scores the measured
+/// peaks against the predicted ions of the derivative.
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(key: Key<'_>, interrupt: &Interrupt) -> PolarsResult<DataFrame> {
+        let settings = SourceSettings {
+            derivative: key.settings.derivative,
+            ..SourceSettings::new()
+        };
+        let data_frame = SourceComputer::try_compute(SourceKey {
+            data_frame: key.data_frame,
+            settings: &settings,
+        })?
+        .group_by_stable([col("FattyAcid")])
+        .agg([col("Fragments").first()])
+        .collect()?;
+        interrupt.check()?;
+        let spectra = Spectra::new(key.spectra)?;
+        let fatty_acids = data_frame["FattyAcid"].fatty_acid();
+        let labels = data_frame["FattyAcid"].struct_()?.field_by_name("Label")?;
+        let labels = labels.str()?;
+        let fragments = data_frame["Fragments"].struct_()?;
+        let mut predicted = Vec::with_capacity(data_frame.height());
+        let mut names = Vec::with_capacity(data_frame.height());
+        let mut mass_to_charge = Vec::with_capacity(data_frame.height());
+        let mut intensities = Vec::with_capacity(data_frame.height());
+        let mut forward = Vec::with_capacity(data_frame.height());
+        let mut reverse = Vec::with_capacity(data_frame.height());
+        for row in 0..data_frame.height() {
+            interrupt.check()?;
+            // Predicted ions
+            let mut ions = Vec::new();
+            for field in fragments.fields_as_series() {
+                match field.dtype() {
+                    DataType::List(_) => {
+                        if let Some(values) = field.list()?.get_as_series(row) {
+                            ions.extend(values.f64()?.into_iter().flatten());
+                        }
+                    }
+                    _ => ions.extend(field.f64()?.get(row)),
+                }
+            }
+            // Spectrum
+            let mut candidates = Vec::new();
+            if let Some(fatty_acid) = fatty_acids.get(row)? {
+                let substituents = data_frame["FattyAcid"].substituents(row)?;
+                candidates.push(format!("{substituents}{}", fatty_acid.display(COMMON)));
+                candidates.push(format!("{substituents}{:#}", fatty_acid.display(COMMON)));
+            }
+            candidates.extend(labels.get(row).map(ToOwned::to_owned));
+            let spectrum = candidates
+                .iter()
+                .find_map(|candidate| spectra.find(candidate));
+            match spectrum {
+                Some(index) => {
+                    let peaks = spectra.peaks(index)?;
+                    let similarity = Similarity::new(&peaks, &ions);
+                    names.push(Some(spectra.names[index].clone()));
+                    mass_to_charge.push(Series::new(
+                        PlSmallStr::EMPTY,
+                        peaks.iter().map(|&(value, _)| value).collect::<Vec<_>>(),
+                    ));
+                    intensities.push(Series::new(
+                        PlSmallStr::EMPTY,
+                        peaks.iter().map(|&(_, value)| value).collect::<Vec<_>>(),
+                    ));
+                    forward.push(Some(similarity.forward));
+                    reverse.push(Some(similarity.reverse));
+                }
+                None => {
+                    names.push(None);
+                    mass_to_charge.push(Series::new_empty(PlSmallStr::EMPTY, &DataType::Float64));
+                    intensities.push(Series::new_empty(PlSmallStr::EMPTY, &DataType::Float64));
+                    forward.push(None);
+                    reverse.push(None);
+                }
+            }
+            predicted.push(Series::new(PlSmallStr::EMPTY, ions));
+        }
+        DataFrame::new(vec![
+            data_frame["FattyAcid"].clone(),
+            Column::new("Predicted".into(), predicted),
+            Column::new("Name".into(), names),
+            Column::new("MassToCharge".into(), mass_to_charge),
+            Column::new("Intensity".into(), intensities),
+            Column::new("Match".into(), forward),
+            Column::new("Reverse".into(), reverse),
+        ])?
+        .lazy()
+        .with_row_index("Index", None)
+        .collect()
+    }
+}
+
+impl Background<Key<'_>> for Computer {
+    fn task(key: Key<'_>) -> Task {
+        let data_frame = key.data_frame.clone();
+        let spectra = key.spectra.clone();
+        let settings = key.settings.clone();
+        Box::new(move |interrupt| {
+            Self::try_compute(
+                Key {
+                    data_frame: &data_frame,
+                    spectra: &spectra,
+                    settings: &settings,
+                },
+                interrupt,
+            )
+        })
+    }
+}
+
+/// Spectrum key
+#[derive(Clone, Copy, Debug)]
+pub struct Key<'a> {
+    pub(crate) data_frame: &'a HashedDataFrame,
+    pub(crate) spectra: &'a HashedDataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data_frame.hash(state);
+        self.spectra.hash(state);
+        self.settings.derivative.hash(state);
+    }
+}
+
+/// Spectra with the normalized names
+struct Spectra<'a> {
+    data_frame: &'a DataFrame,
+    names: Vec<String>,
+    keys: Vec<Vec<String>>,
+}
+
+impl<'a> Spectra<'a> {
+    fn new(data_frame: &'a DataFrame) -> PolarsResult<Self> {
+        let mut names = Vec::with_capacity(data_frame.height());
+        let mut keys = Vec::with_capacity(data_frame.height());
+        let synonyms = data_frame["Synonyms"].list()?;
+        for (name, synonyms) in data_frame["Name"].str()?.into_iter().zip(synonyms) {
+            let name = name.unwrap_or_default();
+            let mut row = vec![normalize(name)];
+            if let Some(synonyms) = synonyms {
+                row.extend(synonyms.str()?.into_iter().flatten().map(normalize));
+            }
+            names.push(name.to_owned());
+            keys.push(row);
+        }
+        Ok(Self {
+            data_frame,
+            names,
+            keys,
+        })
+    }
+
+    /// Spectrum with the name or synonym
+    fn find(&self, name: &str) -> Option<usize> {
+        let name = normalize(name);
+        if name.is_empty() {
+            return None;
+        }
+        self.keys.iter().position(|keys| keys.contains(&name))
+    }
+
+    fn peaks(&self, index: usize) -> PolarsResult<Vec<(f64, f64)>> {
+        let mass_to_charge = self.data_frame["MassToCharge"].list()?.get_as_series(index);
+        let intensities = self.data_frame["Intensity"].list()?.get_as_series(index);
+        let (Some(mass_to_charge), Some(intensities)) = (mass_to_charge, intensities) else {
+            return Ok(Vec::new());
+        };
+        Ok(mass_to_charge
+            .f64()?
+            .into_iter()
+            .zip(intensities.f64()?)
+            .filter_map(|(mass_to_charge, intensity)| Some((mass_to_charge?, intensity?)))
+            .collect())
+    }
+}
+
+/// Lowercase alphanumerics and the `:` of the `C18:1` notation
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric() || *c == ':')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
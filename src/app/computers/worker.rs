@@ -0,0 +1,234 @@
+use crate::utils::HashedDataFrame;
+use egui::{
+    Context,
+    util::{cache::CacheTrait, hash},
+};
+use polars::prelude::*;
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    hash::Hash,
+    marker::PhantomData,
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+/// Time a key has to be requested before its task is started
+///
+/// On the web the task runs on the main thread and blocks the event loop, so
+/// the keys of a slider drag are skipped until the drag rests.
+#[cfg(not(target_arch = "wasm32"))]
+const DEBOUNCE: f64 = 0.0;
+#[cfg(target_arch = "wasm32")]
+const DEBOUNCE: f64 = 0.25;
+
+/// Task
+///
+/// Owns everything it needs, so it can be run outside of the frame.
+pub(crate) type Task = Box<dyn FnOnce(&Interrupt) -> PolarsResult<DataFrame> + Send + Sync>;
+
+/// Background computer
+pub(crate) trait Background<Key>: 'static {
+    /// Prepares the task from the borrowed key
+    fn task(key: Key) -> Task;
+}
+
+/// Worker
+///
+/// Like [`FrameCache`](egui::util::cache::FrameCache), but the values are
+/// computed on a worker thread, so the frame is never blocked.
+///
+/// On the web the task is run by the event loop after the frame and blocks
+/// it while it runs. A web worker needs a build with the shared memory and
+/// the atomics of wasm, which the polars and egui builds of the app are not.
+///
+/// The value of a task takes the hash of its key, the key determines the
+/// value, so the value can key the next computer without hashing its cells.
+///
+/// One task is in flight at a time, the other keys wait in a queue. A key
+/// that is not polled for a frame is evicted with its queued task, a running
+/// task of it is interrupted at its next stage.
+pub(crate) struct Worker<Computer> {
+    generation: u32,
+    cache: HashMap<u64, (u32, Entry)>,
+    /// Value of the task in flight
+    running: Option<Arc<OnceLock<PolarsResult<DataFrame>>>>,
+    computer: PhantomData<fn() -> Computer>,
+}
+
+impl<Computer> Worker<Computer> {
+    /// Polls the task for the key, spawning it if the key is new
+    pub(crate) fn get<Key: Hash>(&mut self, ctx: &Context, key: Key) -> Status
+    where
+        Computer: Background<Key>,
+    {
+        let generation = self.generation;
+        let time = ctx.input(|input| input.time);
+        let hash = hash((TypeId::of::<Computer>(), &key));
+        self.cache
+            .entry(hash)
+            .or_insert_with(|| (generation, Entry::new(hash, time, Computer::task(key))))
+            .0 = generation;
+        self.spawn(ctx, time);
+        self.cache[&hash].1.status()
+    }
+
+    /// Spawns the earliest queued task once the worker is free
+    fn spawn(&mut self, ctx: &Context, time: f64) {
+        if self
+            .running
+            .as_ref()
+            .is_some_and(|value| value.get().is_none())
+        {
+            return;
+        }
+        self.running = None;
+        let Some(entry) = self
+            .cache
+            .values_mut()
+            .map(|(_, entry)| entry)
+            .filter(|entry| entry.task.is_some() && !entry.interrupt.is_interrupted())
+            .min_by(|left, right| left.since.total_cmp(&right.since))
+        else {
+            return;
+        };
+        let wait = entry.since + DEBOUNCE - time;
+        if wait > 0.0 {
+            ctx.request_repaint_after(Duration::from_secs_f64(wait));
+            return;
+        }
+        self.running = entry.spawn(ctx);
+    }
+
+    /// Must be called once per frame to evict the unused keys.
+    fn evict(&mut self) {
+        let generation = self.generation;
+        self.cache.retain(|_, (used, entry)| {
+            let retain = *used == generation;
+            if !retain {
+                entry.interrupt.interrupt();
+            }
+            retain
+        });
+        self.generation = self.generation.wrapping_add(1);
+    }
+}
+
+impl<Computer> Default for Worker<Computer> {
+    fn default() -> Self {
+        Self {
+            generation: 0,
+            cache: HashMap::new(),
+            running: None,
+            computer: PhantomData,
+        }
+    }
+}
+
+impl<Computer: 'static> CacheTrait for Worker<Computer> {
+    fn update(&mut self) {
+        self.evict();
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// Task status
+#[derive(Clone, Debug)]
+pub(crate) enum Status {
+    /// The task is queued or running
+    Pending(Interrupt),
+    /// The task is done
+    Ready(HashedDataFrame),
+    /// The task failed
+    Failed(String),
+    /// The task was interrupted
+    Interrupted,
+}
+
+/// Interruption flag shared with the task
+///
+/// The task checks it between its stages, a running stage is finished.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Interrupt(Arc<AtomicBool>);
+
+impl Interrupt {
+    pub(crate) fn interrupt(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_interrupted(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fails if the task is interrupted, checked between the task stages
+    pub(crate) fn check(&self) -> PolarsResult<()> {
+        if self.is_interrupted() {
+            polars_bail!(ComputeError: "interrupted");
+        }
+        Ok(())
+    }
+}
+
+struct Entry {
+    /// Hash of the key
+    hash: u64,
+    interrupt: Interrupt,
+    value: Arc<OnceLock<PolarsResult<DataFrame>>>,
+    /// Time the key was first requested
+    since: f64,
+    /// Task waiting in the queue
+    task: Option<Task>,
+}
+
+impl Entry {
+    fn new(hash: u64, since: f64, task: Task) -> Self {
+        Self {
+            hash,
+            interrupt: Interrupt::default(),
+            value: Arc::default(),
+            since,
+            task: Some(task),
+        }
+    }
+
+    /// Runs the queued task, returns its value
+    fn spawn(&mut self, ctx: &Context) -> Option<Arc<OnceLock<PolarsResult<DataFrame>>>> {
+        let task = self.task.take()?;
+        let interrupt = self.interrupt.clone();
+        let value = self.value.clone();
+        let ctx = ctx.clone();
+        let run = move || {
+            let _ = value.set(task(&interrupt));
+            ctx.request_repaint();
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(run);
+        // Not a thread: the future runs on the main thread, see `Worker`
+        #[cfg(target_arch = "wasm32")]
+        wasm_bindgen_futures::spawn_local(async move { run() });
+        Some(self.value.clone())
+    }
+
+    fn status(&self) -> Status {
+        if self.interrupt.is_interrupted() {
+            return Status::Interrupted;
+        }
+        match self.value.get() {
+            None => Status::Pending(self.interrupt.clone()),
+            Some(Ok(data_frame)) => {
+                Status::Ready(HashedDataFrame::with_hash(data_frame.clone(), self.hash))
+            }
+            Some(Err(error)) => Status::Failed(error.to_string()),
+        }
+    }
+}
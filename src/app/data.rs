@@ -1,3 +1,4 @@
+use crate::special::program::DEFAULT_MAX_TEMPERATURE;
 use anyhow::Result;
 use polars::prelude::*;
 use ron::{extensions::Extensions, ser::PrettyConfig};
@@ -14,54 +15,141 @@ pub(crate) struct Data {
 }
 
 impl Data {
-    pub(crate) fn stack(&mut self, data_frame: &DataFrame) -> Result<()> {
-        // If many vstack operations are done, it is recommended to call DataFrame::align_chunks_par
-        self.data_frame.vstack_mut(&data_frame)?.align_chunks_par();
-        Ok(())
-    }
-
-    pub(crate) fn join(&mut self, data_frame: DataFrame) -> Result<()> {
-        self.data_frame = self
-            .data_frame
-            .clone()
-            .lazy()
-            .unnest(["FA"])
-            .join(
-                data_frame.lazy().unnest(["FA"]),
-                [
-                    col("Carbons"),
-                    col("Indices"),
-                    col("Bounds"),
-                    col("Label"),
-                    col("OnsetTemperature"),
-                    col("TemperatureStep"),
-                    col("Time"),
-                ],
-                [
-                    col("Carbons"),
-                    col("Indices"),
-                    col("Bounds"),
-                    col("Label"),
-                    col("OnsetTemperature"),
-                    col("TemperatureStep"),
-                    col("Time"),
-                ],
-                JoinArgs::new(JoinType::Full).with_coalesce(JoinCoalesce::CoalesceColumns),
+    /// Merges replicate data frames into one row per fatty acid and mode.
+    ///
+    /// Each replicate is tagged with its batch (day, column, …) and its
+    /// index, the tag and the index of every time are kept in the `Batch` and
+    /// `Replicate` lists alongside `Time`. The already merged replicates keep
+    /// their indices, the new ones are numbered after them. Times of the
+    /// repeated rows are concatenated, exact repeats of a time within the same
+    /// replicate are dropped. Returns the fatty acids missing from some
+    /// replicates.
+    pub(crate) fn merge(
+        &mut self,
+        data_frames: impl IntoIterator<Item = (String, DataFrame)>,
+    ) -> Result<DataFrame> {
+        let frame = |data_frame: DataFrame, batch: &str, offset: u32| -> PolarsResult<LazyFrame> {
+            let batch = self::batch(&data_frame, batch);
+            let replicate = replicate(&data_frame, offset);
+            Ok(program(data_frame)?.select([
+                col("FA"),
+                col("OnsetTemperature"),
+                col("TemperatureStep"),
+                col("Segments"),
+                col("MaxTemperature"),
+                col("Time"),
+                batch,
+                replicate,
+            ]))
+        };
+        let mut offset = replicates(&self.data_frame)?;
+        let mut lazy_frames = vec![frame(self.data_frame.clone(), "", 0)?];
+        for (batch, data_frame) in data_frames {
+            let count = replicates(&data_frame)?.max(1);
+            lazy_frames.push(frame(data_frame, &batch, offset)?);
+            offset += count;
+        }
+        let lazy_frame = concat(lazy_frames, UnionArgs::default())?
+            .explode([col("Time"), col("Batch"), col("Replicate")])
+            .filter(col("Time").is_not_null())
+            .unique_stable(
+                Some(vec![
+                    "FA".into(),
+                    "OnsetTemperature".into(),
+                    "TemperatureStep".into(),
+                    "Segments".into(),
+                    "MaxTemperature".into(),
+                    "Replicate".into(),
+                    "Time".into(),
+                ]),
+                UniqueKeepStrategy::First,
+            )
+            .with_column(
+                col("Replicate")
+                    .n_unique()
+                    .over([
+                        col("OnsetTemperature"),
+                        col("TemperatureStep"),
+                        col("Segments"),
+                        col("MaxTemperature"),
+                    ])
+                    .alias("Replicates"),
             )
+            .group_by_stable([
+                col("FA"),
+                col("OnsetTemperature"),
+                col("TemperatureStep"),
+                col("Segments"),
+                col("MaxTemperature"),
+            ])
+            .agg([
+                col("Time"),
+                col("Batch"),
+                col("Replicate"),
+                col("Replicates").first(),
+            ])
+            .cache();
+        let present = || {
+            col("Replicate")
+                .list()
+                .unique()
+                .list()
+                .sort(Default::default())
+        };
+        let missing = lazy_frame
+            .clone()
+            .filter(present().list().len().lt(col("Replicates")))
+            .select([
+                col("FA"),
+                col("OnsetTemperature"),
+                col("TemperatureStep"),
+                col("Segments"),
+                col("MaxTemperature"),
+                present().alias("Present"),
+                col("Replicates"),
+            ])
+            .collect()?;
+        self.data_frame = lazy_frame
             .select([
-                as_struct(vec![
-                    col("Carbons"),
-                    col("Indices"),
-                    col("Bounds"),
-                    col("Label"),
-                ])
-                .alias("FA"),
+                col("FA"),
                 col("OnsetTemperature"),
                 col("TemperatureStep"),
+                col("Segments"),
+                col("MaxTemperature"),
                 col("Time"),
+                col("Batch"),
+                col("Replicate"),
+            ])
+            .collect()?;
+        Ok(missing)
+    }
+
+    /// Replaces the data with the edited source data frame
+    ///
+    /// The mode is unnested into the program columns, the batch and the
+    /// replicate tags of the times are kept.
+    pub(crate) fn replace(&mut self, source: &DataFrame) -> Result<()> {
+        let mut data_frame = source.clone().lazy().unnest(["Mode"]).collect()?;
+        if data_frame.column("FattyAcid").is_ok() {
+            data_frame.rename("FattyAcid", "FA".into())?;
+        }
+        if data_frame.column("RetentionTime").is_ok() {
+            data_frame.rename("RetentionTime", "Time".into())?;
+        }
+        let batch = self::batch(&data_frame, "");
+        let replicate = replicate(&data_frame, 0);
+        self.data_frame = program(data_frame)?
+            .select([
+                col("FA"),
+                col("OnsetTemperature"),
+                col("TemperatureStep"),
+                col("Segments"),
+                col("MaxTemperature"),
+                col("Time").cast(DataType::List(Box::new(DataType::Float64))),
+                batch,
+                replicate,
             ])
             .collect()?;
-        // println!("self.data_frame: {}", self.data_frame);
         Ok(())
     }
 }
@@ -72,6 +160,12 @@ impl Display for Data {
     }
 }
 
+impl PartialEq for Data {
+    fn eq(&self, other: &Self) -> bool {
+        self.data_frame.equals_missing(&other.data_frame)
+    }
+}
+
 impl Default for Data {
     fn default() -> Self {
         Self {
@@ -87,12 +181,109 @@ impl Default for Data {
                 ),
                 Field::new("OnsetTemperature".into(), DataType::Float64),
                 Field::new("TemperatureStep".into(), DataType::Float64),
+                Field::new("Segments".into(), DataType::List(Box::new(segment()))),
+                Field::new("MaxTemperature".into(), DataType::Float64),
                 Field::new("Time".into(), DataType::List(Box::new(DataType::Float64))),
+                Field::new("Batch".into(), DataType::List(Box::new(DataType::String))),
+                Field::new(
+                    "Replicate".into(),
+                    DataType::List(Box::new(DataType::UInt32)),
+                ),
             ])),
         }
     }
 }
 
+/// Temperature program columns
+///
+/// A two-field mode (`OnsetTemperature`, `TemperatureStep`) is read as a
+/// single ramp up to the default maximum temperature. A program without
+/// `TemperatureStep` takes the rate of its first segment.
+pub(crate) fn program(data_frame: DataFrame) -> PolarsResult<LazyFrame> {
+    let missing = |name| data_frame.column(name).is_err();
+    let missing_segments = missing("Segments");
+    let missing_max_temperature = missing("MaxTemperature");
+    let missing_temperature_step = missing("TemperatureStep");
+    let mut lazy_frame = data_frame.lazy();
+    if missing_max_temperature {
+        lazy_frame = lazy_frame.with_column(lit(DEFAULT_MAX_TEMPERATURE).alias("MaxTemperature"));
+    }
+    if missing_segments {
+        lazy_frame = lazy_frame.with_column(
+            concat_list([as_struct(vec![
+                lit(0.0).alias("Hold"),
+                col("TemperatureStep").alias("Rate"),
+                col("MaxTemperature").alias("Target"),
+            ])])?
+            .alias("Segments"),
+        );
+    } else if missing_temperature_step {
+        lazy_frame = lazy_frame.with_column(
+            col("Segments")
+                .list()
+                .first()
+                .struct_()
+                .field_by_name("Rate")
+                .alias("TemperatureStep"),
+        );
+    }
+    Ok(lazy_frame)
+}
+
+/// Batch tag of every time
+///
+/// Already tagged data keeps its tags.
+fn batch(data_frame: &DataFrame, batch: &str) -> Expr {
+    if data_frame.column("Batch").is_ok() {
+        return col("Batch");
+    }
+    lit(batch)
+        .repeat_by(col("Time").list().len())
+        .alias("Batch")
+}
+
+/// Replicate index of every time
+///
+/// Already merged data keeps its indices shifted by the offset, otherwise
+/// the whole data frame is one replicate.
+fn replicate(data_frame: &DataFrame, offset: u32) -> Expr {
+    let indices = if data_frame.column("Replicate").is_ok() {
+        col("Replicate").list().eval(col("") + lit(offset), false)
+    } else {
+        lit(offset).repeat_by(col("Time").list().len())
+    };
+    indices
+        .cast(DataType::List(Box::new(DataType::UInt32)))
+        .alias("Replicate")
+}
+
+/// Number of the replicates, the next free replicate index
+///
+/// Data without the replicate indices is a single replicate.
+fn replicates(data_frame: &DataFrame) -> PolarsResult<u32> {
+    if data_frame.is_empty() {
+        return Ok(0);
+    }
+    match data_frame.column("Replicate") {
+        Ok(replicate) => Ok(replicate
+            .list()?
+            .get_inner()
+            .cast(&DataType::UInt32)?
+            .u32()?
+            .max()
+            .map_or(0, |max| max + 1)),
+        Err(_) => Ok(1),
+    }
+}
+
+fn segment() -> DataType {
+    DataType::Struct(vec![
+        Field::new("Hold".into(), DataType::Float64),
+        Field::new("Rate".into(), DataType::Float64),
+        Field::new("Target".into(), DataType::Float64),
+    ])
+}
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
 pub(crate) enum Format {
     #[default]
@@ -106,7 +297,6 @@ pub(crate) fn save(
     format: Format,
     mut data_frame: DataFrame,
 ) -> Result<()> {
-    println!("data_frame: {:#?}", data_frame.schema());
     match format {
         Format::Bin => {
             let contents = bincode::serialize(&data_frame)?;
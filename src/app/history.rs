@@ -0,0 +1,34 @@
+use super::{data::Data, panes::Pane};
+use egui::util::undoer::{Settings, Undoer};
+use egui_tiles::Tree;
+use serde::{Deserialize, Serialize};
+
+/// History of data, pane settings and layout changes
+///
+/// The history is stored with the app state, the number of the undos bounds
+/// its size.
+pub(crate) type History = Undoer<State>;
+
+/// Creates an empty history
+pub(crate) fn new() -> History {
+    Undoer::with_settings(Settings {
+        max_undos: 100,
+        ..Default::default()
+    })
+}
+
+/// State tracked by the history
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct State {
+    pub(crate) data: Data,
+    pub(crate) tree: Tree<Pane>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            data: Data::default(),
+            tree: Tree::empty("tree"),
+        }
+    }
+}
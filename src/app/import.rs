@@ -0,0 +1,121 @@
+use super::data::program;
+use anyhow::{Result, bail};
+use polars::prelude::*;
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::{error, trace};
+use walkdir::WalkDir;
+
+/// Supported file extensions
+pub(crate) const EXTENSIONS: [&str; 2] = ["bin", "ron"];
+
+/// Folder import
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Import {
+    pub(crate) path: PathBuf,
+    pub(crate) paths: Vec<PathBuf>,
+    pub(crate) batches: Vec<String>,
+    pub(crate) data_frames: Vec<DataFrame>,
+    pub(crate) summary: DataFrame,
+}
+
+impl Import {
+    /// Walks the directory tree and reads every supported file.
+    ///
+    /// Missing `OnsetTemperature` is inferred from the parent directory name
+    /// (`input/data/<onset>/<onset>.<n>.ron`), a two-field mode is read as a
+    /// single ramp. Every file is tagged with its directory name as the batch.
+    pub(crate) fn new(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut paths = Vec::new();
+        let mut data_frames = Vec::new();
+        for entry in WalkDir::new(path).sort_by_file_name() {
+            let entry = entry?;
+            if !entry.file_type().is_file() || !supported(entry.path()) {
+                continue;
+            }
+            match read(entry.path()) {
+                Ok(data_frame) => {
+                    trace!(path = %entry.path().display(), ?data_frame);
+                    paths.push(entry.path().to_path_buf());
+                    data_frames.push(data_frame);
+                }
+                Err(error) => error!(path = %entry.path().display(), %error),
+            }
+        }
+        let summary = summary(&paths, &data_frames)?;
+        let batches = paths.iter().map(|path| batch(path)).collect();
+        Ok(Self {
+            path: path.to_path_buf(),
+            paths,
+            batches,
+            data_frames,
+            summary,
+        })
+    }
+}
+
+/// Reads a data frame from a supported file
+pub(crate) fn read(path: &Path) -> Result<DataFrame> {
+    let mut data_frame: DataFrame = match path.extension().and_then(OsStr::to_str) {
+        Some("bin") => bincode::deserialize(&fs::read(path)?)?,
+        Some("ron") => ron::de::from_str(&fs::read_to_string(path)?)?,
+        _ => bail!("unsupported file extension: {}", path.display()),
+    };
+    if data_frame.column("OnsetTemperature").is_err() {
+        if let Some(onset_temperature) = onset_temperature(path) {
+            data_frame = data_frame
+                .lazy()
+                .with_column(lit(onset_temperature).alias("OnsetTemperature"))
+                .collect()?;
+        }
+    }
+    Ok(program(data_frame)?.collect()?)
+}
+
+/// Default batch tag of the file
+///
+/// The files of a directory are one batch, so the replicates of a run share
+/// it and the within-batch spread is defined without retagging.
+pub(crate) fn batch(path: &Path) -> String {
+    path.parent()
+        .and_then(Path::file_name)
+        .and_then(OsStr::to_str)
+        .map_or_else(String::new, ToOwned::to_owned)
+}
+
+fn supported(path: &Path) -> bool {
+    path.extension()
+        .and_then(OsStr::to_str)
+        .is_some_and(|extension| EXTENSIONS.contains(&extension))
+}
+
+fn onset_temperature(path: &Path) -> Option<f64> {
+    path.parent()?.file_name()?.to_str()?.parse().ok()
+}
+
+/// Files and rows loaded per mode
+fn summary(paths: &[PathBuf], data_frames: &[DataFrame]) -> PolarsResult<DataFrame> {
+    let mut lazy_frames = Vec::with_capacity(data_frames.len());
+    for (path, data_frame) in paths.iter().zip(data_frames) {
+        lazy_frames.push(data_frame.clone().lazy().select([
+            col("OnsetTemperature"),
+            col("TemperatureStep"),
+            lit(path.display().to_string()).alias("File"),
+        ]));
+    }
+    if lazy_frames.is_empty() {
+        return Ok(DataFrame::empty());
+    }
+    concat(lazy_frames, UnionArgs::default())?
+        .group_by([col("OnsetTemperature"), col("TemperatureStep")])
+        .agg([col("File").n_unique().alias("Files"), len().alias("Rows")])
+        .sort(
+            ["OnsetTemperature", "TemperatureStep"],
+            SortMultipleOptions::new(),
+        )
+        .collect()
+}
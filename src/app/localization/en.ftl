@@ -0,0 +1,315 @@
+# Top panel
+language = Language
+reactive_description_enabled = Reactive: the interface is repainted every frame
+reactive_description_disabled = Reactive: the interface is repainted on input only
+undo = Undo
+redo = Redo
+reset_application = Reset application
+reset_gui = Reset interface
+vertical = Vertical
+horizontal = Horizontal
+grid = Grid
+tabs = Tabs
+open_folder = Open folder
+load = Load
+
+reactive = Reactive
+dropping-files = Dropping files:
+import = Import
+files = Files
+rows = Rows
+missing-in-replicates = Missing in replicates
+present = Present
+
+# Project
+project = Project
+open = Open
+open-recent = Open recent
+no-recent-projects = No recent projects
+save = Save
+save-as = Save as
+
+# Presets
+delete-preset = Delete preset
+save-as-preset = Save as preset
+save-as-preset-description = A name and an open source pane are required
+name = Name
+column = Column
+column-description = Name of the column
+phase = Stationary phase
+column-length = Length, m
+column-diameter = Inner diameter, mm
+film-thickness = Film thickness, µm
+carrier-gas = Carrier gas
+temperature-program = Temperature program
+
+# Compare
+source = Source
+left = Left
+right = Right
+swap-datasets = Swap datasets
+present-in-one-dataset = Present in one dataset only
+dataset = Dataset
+welch-p-value-description = Welch's t-test p-value
+
+# Chromatograms
+smoothing = Smoothing
+smoothing-description = Half width of the moving average, points
+baseline = Baseline
+baseline-description = Width of the rolling minimum baseline, points
+threshold = Threshold
+threshold-description = Minimum peak height, noise multiples
+chromatograms-units-description = Retention time units of the reference
+chromatograms-mode-description = Mode of the reference
+tolerance = Tolerance
+tolerance-description = Maximum ECL difference of a peak and a fatty acid
+peaks = peaks
+
+# Headings
+index = Index
+mode = Mode
+fatty-acid = Fatty acid
+fatty-acids = Fatty acids
+time = Time
+retention-time = Retention time
+retention-index = Retention index
+temperature = Temperature
+heating-rate = Heating rate
+area = Area
+mass = Mass
+width = Width
+count = Count
+median = Median
+range = Range
+content = Content
+repeats = Repeats
+units = Units
+display = Display
+calculate = Calculate
+filter = Filter
+sort = Sort
+order = Order
+plot = Plot
+group = Group
+onset = Onset
+step = Step
+source-units = Source units
+source-units-description = Units the retention time is stored in
+display-units = Display units
+display-units-description = Units of tables, plots and exports
+
+# Settings windows
+source-settings = Source settings
+distance-settings = Distance settings
+compare-settings = Compare settings
+reproducibility-settings = Reproducibility settings
+increments-settings = Increments settings
+spectrum-settings = Spectrum settings
+composition-settings = Composition settings
+distance = Distance
+
+# Source
+delete-row = Delete row
+add-value = Add value
+remove-value = Remove value
+absolute = Absolute
+delta = Delta
+chain-length = Chain length
+tecl-description = Equivalent chain length by elution temperature
+fragments = Fragments
+fragments-description = Electron ionization fragment ions of the derivative
+molecular-ion-description = Molecular ion
+losses = Losses
+losses-description = Molecular ion after the neutral losses
+ions = Ions
+ions-description = Characteristic ions of the derivative head group
+diagnostic = Diagnostic
+diagnostic-description = Polyene ion of polyunsaturated acids, ω-3 and ω-6 diagnostic ions
+slope = Slope
+angle = Angle
+mass-to-charge-precision = m/z precision
+mass-to-charge-precision-description = Precision of the fragment ions
+kind = Kind
+formula = Formula
+relative-abundance = Relative abundance
+
+# Distance
+from = From
+to = To
+
+
+# Reproducibility
+repeatability-description = Repeatability standard deviation (within batches)
+between-batches-description = Between batches standard deviation
+reproducibility-deviation-description = Reproducibility standard deviation (intermediate precision)
+batch-p-value-description = ANOVA F-test p-value of the batch effect
+
+# Increments
+predict-ecl = Predict ECL
+bounds = Bounds
+position = Position
+position-description = Position of the bound from the carboxyl carbon
+geometry = Geometry
+bound-count-description = Fatty acids with the bound
+increment = Increment
+increment-description = FCL increment of the bound
+standard-error = Standard error
+not-estimable = Not estimable, the bound always occurs together with other bounds
+
+# Spectrum
+spectrum-name-description = Spectrum with the fatty acid name or synonym
+spectrum-peaks = Peaks
+match = Match
+match-description = Dot product of the spectrum and the predicted ions, of 1000
+reverse = Reverse
+reverse-description = Dot product on the predicted ions only, of 1000
+relative-intensity = Relative intensity
+measured = Measured
+predicted = Predicted
+
+# Panes
+resize = Resize columns
+edit = Edit
+computing = Computing
+interrupt = Stop after the current stage
+interrupted = Stopped, change the settings to compute again
+
+# Edit
+add-row = Add row
+custom-fatty-acid = Custom fatty acid
+custom-fatty-acid-apply = Apply
+carbons = Carbons
+unsaturated-bounds = Unsaturated bounds
+unsaturated-bounds-description = Positions from the carboxyl carbon separated by commas: c or Z for cis, t or E for trans double bounds, cis by default
+label = Label
+label-description = Name of the fatty acid, the substituents are read from it
+save-target = Save the results
+save-source = Save the source
+save-source-description = Save the edited source data
+
+# Settings
+precision = Precision
+sticky = Sticky columns
+truncate = Truncate titles
+logarithmic = Logarithmic
+legend = Legend
+compare = Compare
+compare-description = Open two source panes to compare them
+batches = Batches
+batches-description = Batch (day, column, …) of every file for the reproducibility statistics
+batch-significance-description = Batch effects with an ANOVA F-test p-value below the level are significant
+chromatograms = Chromatograms
+chromatograms-description = Open a source pane with the fatty acids to assign the peaks
+spectrum = Spectrum
+spectrum-description = Open a source pane before the spectra
+relative = Relative
+relative-description = Standard deviations relative to the mean, %
+relative-references = Relative
+relative-references-description = References of the relative retention time, the first present in the mode is used, the next ones are the fallback
+dead-time = Dead time
+dead-time-description = Hold-up time subtracted from the retention times of the relative retention
+alignment = Alignment
+alignment-description = Anchors of the retention time alignment, every run is mapped onto the mean of the runs before the mean and the standard deviation are calculated
+correction = Correction
+correction-linear = Linear
+correction-linear-description = Least squares line through the anchors
+correction-piecewise = Piecewise
+correction-piecewise-description = Broken line through the anchors
+unaligned = Unaligned
+increments = FCL increments
+isotope-pattern = Isotope pattern
+composition = Composition
+internal-standard = Internal standard
+internal-standard-description = Fatty acid added to the samples for the quantitation, left out of the percents
+standard-mass = Internal standard mass
+sample-mass = Sample mass
+free-fatty-acids = Free fatty acids
+free-fatty-acids-description = Content of the free fatty acids instead of the methyl esters
+response-factor-description = Theoretical FID response factor of the methyl ester relative to 16:0
+conversion-factor-description = Methyl ester to free fatty acid conversion factor (RCOOH / RCOOCH3)
+area-percent-description = Peak areas normalized to 100 %
+mass-percent-description = Areas corrected by the response factors, normalized to 100 %
+sample = Sample
+samples = Samples
+mean = Mean
+indices = Nutritional indices
+basis = Basis
+basis-description = Percents of the nutritional indices: areas or masses
+saturated-description = Saturated fatty acids, %
+monounsaturated-description = Monounsaturated fatty acids, %
+polyunsaturated-description = Polyunsaturated fatty acids, %
+omega3-description = n-3 polyunsaturated fatty acids, %
+omega6-description = n-6 polyunsaturated fatty acids, %
+omega-ratio-description = n-6 to n-3 ratio
+unsaturation-index-description = Unsaturation index: double bounds per 100 fatty acids
+atherogenic-index-description = Atherogenic index: (12:0 + 4 × 14:0 + 16:0) / (ΣMUFA + ΣPUFA)
+thrombogenic-index-description = Thrombogenic index: (14:0 + 16:0 + 18:0) / (0.5 × ΣMUFA + 0.5 × n-6 + 3 × n-3 + n-3/n-6)
+hypocholesterolemic-description = Hypocholesterolemic to hypercholesterolemic ratio: (18:1Δ9 + C18-C22 n-3 and n-6 PUFA) / (14:0 + 16:0)
+reference = Reference ranges
+reference-description = Open a composition pane and drop a reference range file (FattyAcid, Median, InterquartileRange, ReferenceRange)
+median-description = Median of the reference population
+reference-range-description = Reference range of the fatty acid
+deviation-description = Robust z-score: (value - median) / (IQR / 1.349)
+flagged = Flagged
+flagged-description = Samples out of the reference range
+out-of-range = Out of the reference range
+reproducibility = Reproducibility
+reproducibility-description = Load data to compute the reproducibility
+significance = Significance level
+significance-description = Differences with a Welch's t-test p-value below the level are significant
+onset-temperature = Onset temperature
+temperature-step = Temperature step
+
+# Derivative
+derivative = Derivative
+derivative-methyl = Methyl ester
+derivative-methyl-description = Fatty acid methyl ester (FAME)
+derivative-ethyl = Ethyl ester
+derivative-ethyl-description = Fatty acid ethyl ester (FAEE)
+derivative-dmox = DMOX
+derivative-dmox-description = 4,4-Dimethyloxazoline derivative
+derivative-picolinyl = Picolinyl ester
+derivative-picolinyl-description = 3-Pyridylcarbinol ester
+derivative-trimethylsilyl = TMS ester
+derivative-trimethylsilyl-description = Trimethylsilyl ester
+
+# Group
+group-fatty-acid = Fatty acid
+group-fatty-acid-description = Group by fatty acid
+group-onset-temperature = Onset temperature
+group-onset-temperature-description = Group by onset temperature
+group-temperature-step = Temperature step
+group-temperature-step-description = Group by temperature step
+
+# Sort
+sort-fatty-acid = Fatty acid
+sort-fatty-acid-description = Sort by fatty acid
+sort-time = Time
+sort-time-description = Sort by equivalent chain length and retention time
+sort-ecl = ECL
+sort-ecl-description = Sort by median ECL distance
+sort-distance-time = Time
+sort-distance-time-description = Sort by median retention time distance
+
+# Axes
+axes = Axes
+axes-retention-time = Retention time
+axes-retention-time-description = Equivalent chain length vs retention time
+axes-temperature = Temperature
+axes-temperature-description = Equivalent chain length vs elution temperature across modes
+axes-heating-rate = Heating rate
+axes-heating-rate-description = Elution temperature vs heating rate per group
+
+# Order
+ascending = Ascending
+ascending-description = Ascending order
+descending = Descending
+descending-description = Descending order
+
+# Units
+millisecond = ms
+millisecond-description = Milliseconds
+second = s
+second-description = Seconds
+minute = min
+minute-description = Minutes
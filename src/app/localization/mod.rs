@@ -0,0 +1,88 @@
+use fluent_bundle::{FluentResource, concurrent::FluentBundle};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    sync::{
+        LazyLock,
+        atomic::{AtomicU8, Ordering},
+    },
+};
+use tracing::warn;
+use unic_langid::{LanguageIdentifier, langid};
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::En as _);
+
+static EN: LazyLock<FluentBundle<FluentResource>> =
+    LazyLock::new(|| bundle(langid!("en"), include_str!("en.ftl")));
+
+static RU: LazyLock<FluentBundle<FluentResource>> =
+    LazyLock::new(|| bundle(langid!("ru"), include_str!("ru.ftl")));
+
+/// Language
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[repr(u8)]
+pub(crate) enum Language {
+    #[default]
+    En,
+    Ru,
+}
+
+impl Language {
+    pub(crate) const ALL: [Self; 2] = [Self::En, Self::Ru];
+
+    /// Current language
+    pub(crate) fn get() -> Self {
+        match LANGUAGE.load(Ordering::Relaxed) {
+            1 => Self::Ru,
+            _ => Self::En,
+        }
+    }
+
+    /// Sets the current language
+    pub(crate) fn set(self) {
+        LANGUAGE.store(self as _, Ordering::Relaxed);
+    }
+
+    /// Native language name
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::En => "English",
+            Self::Ru => "Русский",
+        }
+    }
+
+    fn bundle(self) -> &'static FluentBundle<FluentResource> {
+        match self {
+            Self::En => &EN,
+            Self::Ru => &RU,
+        }
+    }
+}
+
+/// Localizes the key in the current language
+///
+/// Falls back to English and then to the key itself.
+pub(crate) fn localize(key: &str) -> String {
+    format(Language::get().bundle(), key)
+        .or_else(|| format(Language::En.bundle(), key))
+        .map_or_else(|| key.to_owned(), Cow::into_owned)
+}
+
+fn format(bundle: &'static FluentBundle<FluentResource>, key: &str) -> Option<Cow<'static, str>> {
+    let pattern = bundle.get_message(key)?.value()?;
+    let mut errors = Vec::new();
+    let value = bundle.format_pattern(pattern, None, &mut errors);
+    if !errors.is_empty() {
+        warn!(key, ?errors);
+    }
+    Some(value)
+}
+
+fn bundle(language: LanguageIdentifier, source: &str) -> FluentBundle<FluentResource> {
+    let resource = FluentResource::try_new(source.to_owned()).expect("parse fluent resource");
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    // Unicode isolation marks are rendered as boxes by egui
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).expect("add fluent resource");
+    bundle
+}
@@ -0,0 +1,315 @@
+# Top panel
+language = Язык
+reactive_description_enabled = Реактивность: интерфейс перерисовывается каждый кадр
+reactive_description_disabled = Реактивность: интерфейс перерисовывается только при вводе
+undo = Отменить
+redo = Повторить
+reset_application = Сбросить приложение
+reset_gui = Сбросить интерфейс
+vertical = Вертикально
+horizontal = Горизонтально
+grid = Сетка
+tabs = Вкладки
+open_folder = Открыть папку
+load = Загрузить
+
+reactive = Реактивный
+dropping-files = Перетаскиваемые файлы:
+import = Импорт
+files = Файлы
+rows = Строки
+missing-in-replicates = Отсутствуют в повторностях
+present = Присутствует
+
+# Project
+project = Проект
+open = Открыть
+open-recent = Открыть недавние
+no-recent-projects = Нет недавних проектов
+save = Сохранить
+save-as = Сохранить как
+
+# Presets
+delete-preset = Удалить пресет
+save-as-preset = Сохранить как пресет
+save-as-preset-description = Требуются имя и открытая панель исходных данных
+name = Имя
+column = Колонка
+column-description = Название колонки
+phase = Неподвижная фаза
+column-length = Длина, м
+column-diameter = Внутренний диаметр, мм
+film-thickness = Толщина плёнки, мкм
+carrier-gas = Газ-носитель
+temperature-program = Температурная программа
+
+# Compare
+source = Источник
+left = Левый
+right = Правый
+swap-datasets = Поменять наборы данных местами
+present-in-one-dataset = Есть только в одном наборе данных
+dataset = Набор данных
+welch-p-value-description = p-значение t-критерия Уэлча
+
+# Chromatograms
+smoothing = Сглаживание
+smoothing-description = Полуширина скользящего среднего, точки
+baseline = Базовая линия
+baseline-description = Ширина скользящего минимума базовой линии, точки
+threshold = Порог
+threshold-description = Минимальная высота пика, кратная шуму
+chromatograms-units-description = Единицы времени удерживания эталона
+chromatograms-mode-description = Режим эталона
+tolerance = Допуск
+tolerance-description = Максимальная разница ECL пика и жирной кислоты
+peaks = пиков
+
+# Headings
+index = Индекс
+mode = Режим
+fatty-acid = Жирная кислота
+fatty-acids = Жирные кислоты
+time = Время
+retention-time = Время удерживания
+retention-index = Индекс удерживания
+temperature = Температура
+heating-rate = Скорость нагрева
+area = Площадь
+mass = Масса
+width = Ширина
+count = Количество
+median = Медиана
+range = Диапазон
+content = Содержание
+repeats = Повторы
+units = Единицы
+display = Отображение
+calculate = Расчёт
+filter = Фильтр
+sort = Сортировка
+order = Порядок
+plot = График
+group = Группировка
+onset = Начало
+step = Шаг
+source-units = Единицы источника
+source-units-description = Единицы, в которых хранится время удерживания
+display-units = Единицы отображения
+display-units-description = Единицы таблиц, графиков и экспорта
+
+# Settings windows
+source-settings = Настройки источника
+distance-settings = Настройки расстояний
+compare-settings = Настройки сравнения
+reproducibility-settings = Настройки воспроизводимости
+increments-settings = Настройки инкрементов
+spectrum-settings = Настройки спектров
+composition-settings = Настройки состава
+distance = Расстояние
+
+# Source
+delete-row = Удалить строку
+add-value = Добавить значение
+remove-value = Удалить значение
+absolute = Абсолютное
+delta = Разность
+chain-length = Длина цепи
+tecl-description = Эквивалентная длина цепи по температуре элюирования
+fragments = Фрагменты
+fragments-description = Фрагментные ионы производного при электронной ионизации
+molecular-ion-description = Молекулярный ион
+losses = Потери
+losses-description = Молекулярный ион после нейтральных потерь
+ions = Ионы
+ions-description = Характеристические ионы головной группы производного
+diagnostic = Диагностические
+diagnostic-description = Полиеновый ион полиненасыщенных кислот, диагностические ионы ω-3 и ω-6
+slope = Наклон
+angle = Угол
+mass-to-charge-precision = Точность m/z
+mass-to-charge-precision-description = Точность фрагментных ионов
+kind = Вид
+formula = Формула
+relative-abundance = Относительная распространённость
+
+# Distance
+from = От
+to = До
+
+
+# Reproducibility
+repeatability-description = Стандартное отклонение повторяемости (внутри серий)
+between-batches-description = Стандартное отклонение между сериями
+reproducibility-deviation-description = Стандартное отклонение воспроизводимости (промежуточная прецизионность)
+batch-p-value-description = p-значение F-критерия дисперсионного анализа для эффекта серии
+
+# Increments
+predict-ecl = Предсказать ECL
+bounds = Связи
+position = Положение
+position-description = Положение связи от карбоксильного углерода
+geometry = Геометрия
+bound-count-description = Жирные кислоты с этой связью
+increment = Инкремент
+increment-description = Инкремент FCL связи
+standard-error = Стандартная ошибка
+not-estimable = Не оценивается, связь всегда встречается вместе с другими связями
+
+# Spectrum
+spectrum-name-description = Спектр с названием или синонимом жирной кислоты
+spectrum-peaks = Пики
+match = Совпадение
+match-description = Скалярное произведение спектра и предсказанных ионов, из 1000
+reverse = Обратное
+reverse-description = Скалярное произведение только по предсказанным ионам, из 1000
+relative-intensity = Относительная интенсивность
+measured = Измеренный
+predicted = Предсказанный
+
+# Panes
+resize = Изменять ширину столбцов
+edit = Редактировать
+computing = Вычисление
+interrupt = Остановить после текущего этапа
+interrupted = Остановлено, измените настройки, чтобы вычислить снова
+
+# Edit
+add-row = Добавить строку
+custom-fatty-acid = Другая жирная кислота
+custom-fatty-acid-apply = Применить
+carbons = Атомы углерода
+unsaturated-bounds = Ненасыщенные связи
+unsaturated-bounds-description = Положения от карбоксильного углерода через запятую: c или Z для цис-, t или E для транс-двойных связей, по умолчанию цис-
+label = Название
+label-description = Название жирной кислоты, из него читаются заместители
+save-target = Сохранить результаты
+save-source = Сохранить исходные данные
+save-source-description = Сохранить отредактированные исходные данные
+
+# Settings
+precision = Точность
+sticky = Закреплённые столбцы
+truncate = Обрезать заголовки
+logarithmic = Логарифмический
+legend = Легенда
+compare = Сравнить
+compare-description = Откройте две панели источников, чтобы сравнить их
+batches = Серии
+batches-description = Серия (день, колонка, …) каждого файла для статистики воспроизводимости
+batch-significance-description = Эффекты серий с p-значением F-критерия ANOVA ниже уровня значимы
+chromatograms = Хроматограммы
+chromatograms-description = Откройте панель источника с жирными кислотами, чтобы отнести пики
+spectrum = Спектр
+spectrum-description = Откройте панель источника перед спектрами
+relative = Относительные
+relative-description = Стандартные отклонения относительно среднего, %
+relative-references = Относительное
+relative-references-description = Стандарты относительного времени удерживания, используется первый присутствующий в режиме, следующие являются запасными
+dead-time = Мертвое время
+dead-time-description = Время удерживания несорбируемого вещества, вычитаемое из времен удерживания относительного удерживания
+alignment = Выравнивание
+alignment-description = Опорные кислоты выравнивания времени удерживания, каждый прогон приводится к среднему по прогонам до вычисления среднего и стандартного отклонения
+correction = Коррекция
+correction-linear = Линейная
+correction-linear-description = Прямая наименьших квадратов по опорным кислотам
+correction-piecewise = Кусочно-линейная
+correction-piecewise-description = Ломаная по опорным кислотам
+unaligned = Без выравнивания
+increments = Инкременты FCL
+isotope-pattern = Изотопное распределение
+composition = Состав
+internal-standard = Внутренний стандарт
+internal-standard-description = Жирная кислота, добавленная к образцам для количественного определения, не входит в проценты
+standard-mass = Масса внутреннего стандарта
+sample-mass = Масса образца
+free-fatty-acids = Свободные жирные кислоты
+free-fatty-acids-description = Содержание свободных жирных кислот вместо метиловых эфиров
+response-factor-description = Теоретический коэффициент отклика ПИД метилового эфира относительно 16:0
+conversion-factor-description = Коэффициент пересчета метилового эфира в свободную жирную кислоту (RCOOH / RCOOCH3)
+area-percent-description = Площади пиков, нормированные на 100 %
+mass-percent-description = Площади, исправленные на коэффициенты отклика, нормированные к 100 %
+sample = Образец
+samples = Образцы
+mean = Среднее
+indices = Пищевые индексы
+basis = Основа
+basis-description = Проценты пищевых индексов: площади или массы
+saturated-description = Насыщенные жирные кислоты, %
+monounsaturated-description = Мононенасыщенные жирные кислоты, %
+polyunsaturated-description = Полиненасыщенные жирные кислоты, %
+omega3-description = Полиненасыщенные жирные кислоты n-3, %
+omega6-description = Полиненасыщенные жирные кислоты n-6, %
+omega-ratio-description = Отношение n-6 к n-3
+unsaturation-index-description = Индекс ненасыщенности: двойные связи на 100 жирных кислот
+atherogenic-index-description = Индекс атерогенности: (12:0 + 4 × 14:0 + 16:0) / (ΣMUFA + ΣPUFA)
+thrombogenic-index-description = Индекс тромбогенности: (14:0 + 16:0 + 18:0) / (0.5 × ΣMUFA + 0.5 × n-6 + 3 × n-3 + n-3/n-6)
+hypocholesterolemic-description = Отношение гипо- к гиперхолестеринемическим: (18:1Δ9 + ПНЖК n-3 и n-6 C18-C22) / (14:0 + 16:0)
+reference = Референсные интервалы
+reference-description = Откройте панель состава и перетащите файл референсных интервалов (FattyAcid, Median, InterquartileRange, ReferenceRange)
+median-description = Медиана референсной популяции
+reference-range-description = Референсный интервал жирной кислоты
+deviation-description = Робастная z-оценка: (значение - медиана) / (IQR / 1.349)
+flagged = Отмечено
+flagged-description = Образцы вне референсного интервала
+out-of-range = Вне референсного интервала
+reproducibility = Воспроизводимость
+reproducibility-description = Загрузите данные, чтобы вычислить воспроизводимость
+significance = Уровень значимости
+significance-description = Различия с p-значением t-критерия Уэлча ниже уровня значимы
+onset-temperature = Начальная температура
+temperature-step = Шаг температуры
+
+# Derivative
+derivative = Производное
+derivative-methyl = Метиловый эфир
+derivative-methyl-description = Метиловый эфир жирной кислоты (МЭЖК)
+derivative-ethyl = Этиловый эфир
+derivative-ethyl-description = Этиловый эфир жирной кислоты (ЭЭЖК)
+derivative-dmox = ДМОКС
+derivative-dmox-description = 4,4-Диметилоксазолиновое производное
+derivative-picolinyl = Пиколиниловый эфир
+derivative-picolinyl-description = Эфир 3-пиридилкарбинола
+derivative-trimethylsilyl = ТМС эфир
+derivative-trimethylsilyl-description = Триметилсилиловый эфир
+
+# Group
+group-fatty-acid = Жирная кислота
+group-fatty-acid-description = Группировать по жирной кислоте
+group-onset-temperature = Начальная температура
+group-onset-temperature-description = Группировать по начальной температуре
+group-temperature-step = Шаг температуры
+group-temperature-step-description = Группировать по шагу температуры
+
+# Sort
+sort-fatty-acid = Жирная кислота
+sort-fatty-acid-description = Сортировать по жирной кислоте
+sort-time = Время
+sort-time-description = Сортировать по эквивалентной длине цепи и времени удерживания
+sort-ecl = ECL
+sort-ecl-description = Сортировать по медиане расстояния ECL
+sort-distance-time = Время
+sort-distance-time-description = Сортировать по медиане расстояния времени удерживания
+
+# Axes
+axes = Оси
+axes-retention-time = Время удерживания
+axes-retention-time-description = Эквивалентная длина цепи от времени удерживания
+axes-temperature = Температура
+axes-temperature-description = Эквивалентная длина цепи от температуры элюирования по всем режимам
+axes-heating-rate = Скорость нагрева
+axes-heating-rate-description = Температура элюирования от скорости нагрева по группам
+
+# Order
+ascending = По возрастанию
+ascending-description = Порядок по возрастанию
+descending = По убыванию
+descending-description = Порядок по убыванию
+
+# Units
+millisecond = мс
+millisecond-description = Миллисекунды
+second = с
+second-description = Секунды
+minute = мин
+minute-description = Минуты
@@ -1,57 +1,114 @@
-use self::panes::{Pane, behavior::Behavior};
-use crate::presets::AGILENT;
+use self::{
+    localization::Language,
+    panes::{DistancePane, Origin, Pane, behavior::Behavior},
+    units::TimeUnits,
+};
+use crate::{
+    presets::{Metadata, PRESETS, Preset},
+    utils::HashedDataFrame,
+};
+use andi::Chromatogram;
 use anyhow::Result;
+use chromatogram::Chromatograms;
 use data::Data;
 use eframe::{APP_KEY, get_value, set_value};
 use egui::{
-    Align, Align2, CentralPanel, Color32, DroppedFile, FontDefinitions, Id, LayerId, Layout, Order,
-    RichText, ScrollArea, SidePanel, TextStyle, TopBottomPanel, menu::bar, warn_if_debug_build,
+    Align, Align2, Button, CentralPanel, CollapsingHeader, Color32, ComboBox, DragValue,
+    DroppedFile, Event, FontDefinitions, Grid, Id, Key, KeyboardShortcut, LayerId, Layout,
+    Modifiers, Order, Response, RichText, ScrollArea, TextStyle, TopBottomPanel, Ui, Window,
+    menu::bar, warn_if_debug_build,
 };
 use egui_ext::{DroppedFileExt, HoveredFileExt, LightDarkButton};
 use egui_phosphor::{
     Variant, add_to_fonts,
     regular::{
-        ARROWS_CLOCKWISE, DATABASE, FLOPPY_DISK, GRID_FOUR, ROCKET, SIDEBAR_SIMPLE,
-        SQUARE_SPLIT_HORIZONTAL, SQUARE_SPLIT_VERTICAL, TABLE, TABS, TRASH,
+        ARROW_CLOCKWISE, ARROW_COUNTER_CLOCKWISE, ARROWS_CLOCKWISE, CHART_LINE,
+        CLOCK_COUNTER_CLOCKWISE, DATABASE, FILE, FLOPPY_DISK, FOLDER_OPEN, GIT_DIFF, GRID_FOUR,
+        PLUS, ROCKET, SQUARE_SPLIT_HORIZONTAL, SQUARE_SPLIT_VERTICAL, TABS, TARGET, TRANSLATE,
+        TRASH, USER, WARNING,
     },
 };
-use egui_tiles::{ContainerKind, Tile, Tiles, Tree};
+use egui_tiles::{ContainerKind, Tile, Tree};
 use egui_tiles_ext::{TreeExt as _, VERTICAL};
+use history::{History, State};
+use import::Import;
 use polars::prelude::*;
+use project::{Project, Recent};
 use serde::{Deserialize, Serialize};
-use std::{fmt::Write, str, time::Duration};
-use tracing::{error, info, trace};
+use std::{
+    fmt::Write,
+    mem::take,
+    path::{Path, PathBuf},
+    str,
+    time::Duration,
+};
+use tracing::{error, info, trace, warn};
 
 macro localize($text:literal) {
-    $text
+    $crate::app::localization::localize($text)
 }
 
 /// IEEE 754-2008
 const MAX_PRECISION: usize = 16;
-const MAX_TEMPERATURE: f64 = 250.0;
 const _NOTIFICATIONS_DURATION: Duration = Duration::from_secs(15);
 const SIZE: f32 = 32.0;
 
+const UNDO: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+const REDO: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::Z);
+
 #[derive(Deserialize, Serialize)]
 #[serde(default)]
 pub struct App {
     data: Data,
+    #[serde(skip)]
+    import: Option<Import>,
+    #[serde(skip)]
+    chromatograms: Option<Chromatograms>,
+    #[serde(skip)]
+    missing: DataFrame,
     reactive: bool,
+    language: Language,
     // Panels
     left_panel: bool,
     // Panes
     tree: Tree<Pane>,
     behavior: Behavior,
+    // History
+    history: History,
+    /// The state changed outside of an interaction
+    #[serde(skip)]
+    changed: bool,
+    #[serde(skip)]
+    can_undo: bool,
+    #[serde(skip)]
+    can_redo: bool,
+    // Project
+    project: Option<PathBuf>,
+    recent: Recent,
+    // Presets
+    presets: Vec<Preset>,
 }
 
 impl Default for App {
     fn default() -> Self {
         Self {
             data: Data::default(),
+            import: None,
+            chromatograms: None,
+            missing: DataFrame::empty(),
             reactive: true,
+            language: Language::En,
             left_panel: true,
             tree: Tree::empty("tree"),
             behavior: Default::default(),
+            history: history::new(),
+            changed: true,
+            can_undo: false,
+            can_redo: false,
+            project: None,
+            recent: Recent::default(),
+            presets: Vec::new(),
         }
     }
 }
@@ -68,16 +125,19 @@ impl App {
         // return Default::default();
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
-        cc.storage
+        let app: Self = cc
+            .storage
             .and_then(|storage| get_value(storage, APP_KEY))
-            .unwrap_or_default()
+            .unwrap_or_default();
+        app.language.set();
+        app
     }
 
     fn drag_and_drop(&mut self, ctx: &egui::Context) {
         // Preview hovering files
         if let Some(text) = ctx.input(|input| {
             (!input.raw.hovered_files.is_empty()).then(|| {
-                let mut text = String::from("Dropping files:");
+                let mut text = localize!("dropping-files");
                 for file in &input.raw.hovered_files {
                     write!(text, "\n{}", file.display()).ok();
                 }
@@ -101,24 +161,53 @@ impl App {
             (!input.raw.dropped_files.is_empty()).then_some(input.raw.dropped_files.clone())
         }) {
             info!(?dropped_files);
+            let mut data_frames = Vec::with_capacity(dropped_files.len());
             for dropped_file in dropped_files {
+                // Directory
+                if let Some(path) = dropped_file.path.as_ref().filter(|path| path.is_dir()) {
+                    match Import::new(path) {
+                        Ok(import) => self.import = Some(import),
+                        Err(error) => error!(%error),
+                    }
+                    continue;
+                }
+                // Chromatograms
+                if extension(&dropped_file) == Some(andi::EXTENSION) {
+                    match chromatogram(&dropped_file) {
+                        Ok(chromatogram) => self
+                            .chromatograms
+                            .get_or_insert_with(Chromatograms::new)
+                            .push(dropped_file.name.clone(), chromatogram),
+                        Err(error) => error!(%error),
+                    }
+                    continue;
+                }
+                // Spectra
+                if extension(&dropped_file) == Some(msp::EXTENSION) {
+                    match spectra(&dropped_file) {
+                        Ok(spectra) => self.spectra(spectra),
+                        Err(error) => error!(%error),
+                    }
+                    continue;
+                }
                 // let data_frame: DataFrame = match dropped_file.extension().and_then(OsStr::to_str) {
                 //     Some("bin") => bincode::deserialize(&fs::read(&args.path)?)?,
                 //     Some("ron") => ron::de::from_str(&fs::read_to_string(&args.path)?)?,
                 //     _ => panic!("unsupported input file extension"),
                 // };
                 match ron(&dropped_file) {
+                    // Reference ranges
+                    Ok(data_frame) if data_frame.column("ReferenceRange").is_ok() => {
+                        self.reference(data_frame);
+                        continue;
+                    }
                     Ok(data_frame) => {
                         trace!(?data_frame);
-                        self.data.stack(&data_frame).unwrap();
-                        if !self.tree.tiles.is_empty() {
-                            self.tree = Tree::empty("tree");
-                        }
-                        // self.tree
-                        //     .insert_pane(Pane::source(self.data.data_frame.clone()));
-                        // self.tree
-                        //     .insert_pane(Pane::distance(self.data.data_frame.clone()));
-                        trace!(?self.data);
+                        let batch = match &dropped_file.path {
+                            Some(path) => import::batch(path),
+                            None => import::batch(Path::new(&dropped_file.name)),
+                        };
+                        data_frames.push((batch, data_frame));
                     }
                     Err(error) => {
                         error!(%error);
@@ -130,34 +219,65 @@ impl App {
                     }
                 };
             }
-            println!("data_frame: {}", self.data.data_frame);
-            let data_frame = self
-                .data
-                .data_frame
-                .clone()
-                .lazy()
-                .select([
-                    as_struct(vec![
-                        col("OnsetTemperature").alias("OnsetTemperature"),
-                        col("TemperatureStep").alias("TemperatureStep"),
-                    ])
-                    .alias("Mode"),
-                    col("FA"),
-                    col("Time"),
+            if !data_frames.is_empty() {
+                self.load(data_frames);
+            }
+        }
+    }
+
+    fn load(&mut self, data_frames: Vec<(String, DataFrame)>) {
+        self.changed = true;
+        match self.data.merge(data_frames) {
+            Ok(missing) => {
+                if !missing.is_empty() {
+                    warn!(%missing);
+                }
+                self.missing = missing;
+            }
+            Err(error) => error!(%error),
+        }
+        if !self.tree.tiles.is_empty() {
+            self.tree = Tree::empty("tree");
+        }
+        trace!(?self.data);
+        let data_frame = match self
+            .data
+            .data_frame
+            .clone()
+            .lazy()
+            .select([
+                as_struct(vec![
+                    col("OnsetTemperature").alias("OnsetTemperature"),
+                    col("TemperatureStep").alias("TemperatureStep"),
+                    col("Segments").alias("Segments"),
+                    col("MaxTemperature").alias("MaxTemperature"),
                 ])
-                .cache()
-                .sort(["Mode"], SortMultipleOptions::new())
-                .select([all()
-                    .sort_by(&[col("Time").list().mean()], SortMultipleOptions::new())
-                    .over([col("Mode")])])
-                .collect()
-                .unwrap();
-            println!("data_frame: {data_frame}");
-            self.tree
-                .insert_pane::<VERTICAL>(Pane::source(data_frame.clone()));
-            self.tree
-                .insert_pane::<VERTICAL>(Pane::distance(data_frame.clone()));
-            data::save("data_frame.bin", data::Format::Bin, data_frame).unwrap();
+                .alias("Mode"),
+                col("FA"),
+                col("Time"),
+                col("Batch"),
+                col("Replicate"),
+            ])
+            .cache()
+            .sort(["Mode"], SortMultipleOptions::new())
+            .select([all()
+                .sort_by(&[col("Time").list().mean()], SortMultipleOptions::new())
+                .over([col("Mode")])])
+            .collect()
+        {
+            Ok(data_frame) => data_frame,
+            Err(error) => {
+                error!(%error);
+                return;
+            }
+        };
+        trace!(%data_frame);
+        self.tree
+            .insert_pane::<VERTICAL>(Pane::source(data_frame.clone(), Origin::Data));
+        self.tree
+            .insert_pane::<VERTICAL>(Pane::distance(data_frame.clone()));
+        if let Err(error) = data::save("data_frame.bin", data::Format::Bin, data_frame) {
+            error!(%error);
         }
     }
 }
@@ -192,7 +312,7 @@ impl App {
     }
 
     // Left panel
-    fn left_panel(&mut self, ctx: &egui::Context) {
+    fn left_panel(&mut self, _ctx: &egui::Context) {
         // SidePanel::left("left_panel")
         //     .frame(egui::Frame::side_top_panel(&ctx.style()))
         //     .resizable(true)
@@ -209,10 +329,45 @@ impl App {
                     ui.light_dark_button(SIZE);
                     ui.separator();
                     ui.toggle_value(&mut self.reactive, RichText::new(ROCKET).size(SIZE))
-                        .on_hover_text("reactive")
+                        .on_hover_text(localize!("reactive"))
                         .on_hover_text(localize!("reactive_description_enabled"))
                         .on_disabled_hover_text(localize!("reactive_description_disabled"));
                     ui.separator();
+                    ui.menu_button(RichText::new(TRANSLATE).size(SIZE), |ui| {
+                        for language in Language::ALL {
+                            if ui
+                                .selectable_value(&mut self.language, language, language.name())
+                                .clicked()
+                            {
+                                self.language.set();
+                                ui.close_menu();
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(localize!("language"));
+                    ui.separator();
+                    if ui
+                        .add_enabled(
+                            self.can_undo,
+                            Button::new(RichText::new(ARROW_COUNTER_CLOCKWISE).size(SIZE)),
+                        )
+                        .on_hover_text(localize!("undo"))
+                        .clicked()
+                    {
+                        self.undo();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.can_redo,
+                            Button::new(RichText::new(ARROW_CLOCKWISE).size(SIZE)),
+                        )
+                        .on_hover_text(localize!("redo"))
+                        .clicked()
+                    {
+                        self.redo();
+                    }
+                    ui.separator();
                     if ui
                         .button(RichText::new(TRASH).size(SIZE))
                         .on_hover_text(localize!("reset_application"))
@@ -220,6 +375,10 @@ impl App {
                     {
                         *self = Self {
                             reactive: self.reactive,
+                            language: self.language,
+                            history: take(&mut self.history),
+                            recent: take(&mut self.recent),
+                            presets: take(&mut self.presets),
                             ..Default::default()
                         };
                     }
@@ -277,16 +436,43 @@ impl App {
                         }
                     }
                     ui.separator();
-                    ui.menu_button(RichText::new(DATABASE).size(SIZE), |ui| {
-                        if ui
-                            .button(RichText::new(format!("{DATABASE} IPPRAS/Agilent")).heading())
-                            .clicked()
-                        {
-                            self.tree
-                                .insert_pane::<VERTICAL>(Pane::source(AGILENT.clone()));
-                            ui.close_menu();
+                    #[cfg(not(target_arch = "wasm32"))]
+                    self.project_menu(ui);
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui
+                        .button(RichText::new(FOLDER_OPEN).size(SIZE))
+                        .on_hover_text(localize!("open_folder"))
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                            match Import::new(path) {
+                                Ok(import) => self.import = Some(import),
+                                Err(error) => error!(%error),
+                            }
                         }
+                    }
+                    ui.separator();
+                    ui.menu_button(RichText::new(DATABASE).size(SIZE), |ui| {
+                        self.presets_menu(ui);
                     });
+                    ui.menu_button(RichText::new(GIT_DIFF).size(SIZE), |ui| {
+                        self.compare_menu(ui);
+                    })
+                    .response
+                    .on_hover_text(localize!("compare"));
+                    if ui
+                        .add_enabled(
+                            !self.data.data_frame.is_empty(),
+                            Button::new(RichText::new(TARGET).size(SIZE)),
+                        )
+                        .on_hover_text(localize!("reproducibility"))
+                        .on_disabled_hover_text(localize!("reproducibility-description"))
+                        .clicked()
+                    {
+                        self.tree.insert_pane::<VERTICAL>(Pane::reproducibility(
+                            self.data.data_frame.clone(),
+                        ));
+                    }
                     ui.separator();
                 });
             });
@@ -294,11 +480,497 @@ impl App {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+impl App {
+    fn project_menu(&mut self, ui: &mut Ui) {
+        ui.menu_button(RichText::new(FILE).size(SIZE), |ui| {
+            if ui
+                .button(format!("{FOLDER_OPEN} {}…", localize!("open")))
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("amal", &[project::EXTENSION])
+                    .pick_file()
+                {
+                    self.open_project(path);
+                }
+                ui.close_menu();
+            }
+            ui.menu_button(
+                format!("{CLOCK_COUNTER_CLOCKWISE} {}", localize!("open-recent")),
+                |ui| {
+                    if self.recent.is_empty() {
+                        ui.label(localize!("no-recent-projects"));
+                    }
+                    let mut open = None;
+                    for path in self.recent.iter() {
+                        if ui.button(path.display().to_string()).clicked() {
+                            open = Some(path.clone());
+                        }
+                    }
+                    if let Some(path) = open {
+                        self.open_project(path);
+                        ui.close_menu();
+                    }
+                },
+            );
+            ui.separator();
+            if ui
+                .button(format!("{FLOPPY_DISK} {}", localize!("save")))
+                .clicked()
+            {
+                match self.project.clone() {
+                    Some(path) => self.save_project(path),
+                    None => self.save_project_as(),
+                }
+                ui.close_menu();
+            }
+            if ui
+                .button(format!("{FLOPPY_DISK} {}…", localize!("save-as")))
+                .clicked()
+            {
+                self.save_project_as();
+                ui.close_menu();
+            }
+        })
+        .response
+        .on_hover_text(
+            self.project
+                .as_ref()
+                .map_or_else(|| localize!("project"), |path| path.display().to_string()),
+        );
+    }
+
+    fn open_project(&mut self, path: PathBuf) {
+        match Project::load(&path) {
+            Ok(project) => {
+                self.data = project.data;
+                self.tree = project.tree;
+                self.changed = true;
+                self.recent.push(path.clone());
+                self.project = Some(path);
+            }
+            Err(error) => {
+                error!(path = %path.display(), %error);
+                self.recent.remove(&path);
+            }
+        }
+    }
+
+    fn save_project(&mut self, path: PathBuf) {
+        let project = Project {
+            data: self.data.clone(),
+            tree: self.tree.clone(),
+        };
+        match project.save(&path) {
+            Ok(()) => {
+                self.recent.push(path.clone());
+                self.project = Some(path);
+            }
+            Err(error) => error!(path = %path.display(), %error),
+        }
+    }
+
+    fn save_project_as(&mut self) {
+        let file_name = format!("project.{}", project::EXTENSION);
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("amal", &[project::EXTENSION])
+            .set_file_name(file_name)
+            .save_file()
+        {
+            self.save_project(path);
+        }
+    }
+}
+
 impl App {
+    fn presets_menu(&mut self, ui: &mut Ui) {
+        let mut insert = None;
+        for preset in PRESETS.iter() {
+            if ui
+                .button(RichText::new(format!("{DATABASE} {}", preset.name)).heading())
+                .on_hover_ui(|ui| metadata(ui, &preset.metadata))
+                .clicked()
+            {
+                insert = Some(preset.data_frame.clone());
+            }
+        }
+        // User presets
+        if !self.presets.is_empty() {
+            ui.separator();
+        }
+        let mut remove = None;
+        for (index, preset) in self.presets.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .button(RichText::new(format!("{USER} {}", preset.name)).heading())
+                    .on_hover_ui(|ui| metadata(ui, &preset.metadata))
+                    .clicked()
+                {
+                    insert = Some(preset.data_frame.clone());
+                }
+                if ui
+                    .button(TRASH)
+                    .on_hover_text(localize!("delete-preset"))
+                    .clicked()
+                {
+                    remove = Some(index);
+                }
+            });
+        }
+        if let Some(index) = remove {
+            self.presets.remove(index);
+        }
+        if let Some(data_frame) = insert {
+            self.tree
+                .insert_pane::<VERTICAL>(Pane::source(data_frame, Origin::Detached));
+            ui.close_menu();
+        }
+        ui.separator();
+        ui.menu_button(format!("{PLUS} {}", localize!("save-as-preset")), |ui| {
+            let id = ui.auto_id_with("Preset");
+            let (mut name, mut metadata) =
+                ui.data_mut(|data| data.get_temp::<(String, Metadata)>(id).unwrap_or_default());
+            Grid::new("Preset").show(ui, |ui| {
+                ui.label(localize!("name"));
+                ui.text_edit_singleline(&mut name);
+                ui.end_row();
+
+                ui.label(localize!("column"));
+                optional_text(ui, &mut metadata.column)
+                    .on_hover_text(localize!("column-description"));
+                ui.end_row();
+
+                ui.label(localize!("phase"));
+                optional_text(ui, &mut metadata.phase);
+                ui.end_row();
+
+                ui.label(localize!("column-length"));
+                optional_value(ui, &mut metadata.length, 1.0);
+                ui.end_row();
+
+                ui.label(localize!("column-diameter"));
+                optional_value(ui, &mut metadata.diameter, 0.01);
+                ui.end_row();
+
+                ui.label(localize!("film-thickness"));
+                optional_value(ui, &mut metadata.film, 0.01);
+                ui.end_row();
+
+                ui.label(localize!("carrier-gas"));
+                ui.text_edit_singleline(&mut metadata.carrier);
+                ui.end_row();
+
+                ui.label(localize!("temperature-program"));
+                ui.text_edit_singleline(&mut metadata.program);
+                ui.end_row();
+            });
+            let source = self.tree.tiles.iter().find_map(|(_, tile)| match tile {
+                Tile::Pane(Pane::Source(pane)) => Some(pane.source.clone().into_inner()),
+                _ => None,
+            });
+            let enabled = !name.is_empty() && source.is_some();
+            if ui
+                .add_enabled(
+                    enabled,
+                    Button::new(format!("{FLOPPY_DISK} {}", localize!("save"))),
+                )
+                .on_disabled_hover_text(localize!("save-as-preset-description"))
+                .clicked()
+            {
+                if let Some(data_frame) = source {
+                    self.presets.retain(|preset| preset.name != name);
+                    self.presets.push(Preset {
+                        name: take(&mut name),
+                        metadata: take(&mut metadata),
+                        data_frame,
+                    });
+                }
+                ui.close_menu();
+            }
+            ui.data_mut(|data| data.insert_temp(id, (name, metadata)));
+        });
+    }
+
+    /// Compares two open source panes
+    fn compare_menu(&mut self, ui: &mut Ui) {
+        let sources: Vec<_> = self
+            .tree
+            .tiles
+            .iter()
+            .filter_map(|(_, tile)| match tile {
+                Tile::Pane(Pane::Source(pane)) => Some(pane.source.clone().into_inner()),
+                _ => None,
+            })
+            .collect();
+        if sources.len() < 2 {
+            ui.label(localize!("compare-description"));
+            return;
+        }
+        let id = ui.auto_id_with("Compare");
+        let [mut left, mut right] = ui
+            .data_mut(|data| data.get_temp::<[usize; 2]>(id))
+            .unwrap_or([0, 1])
+            .map(|index| index.min(sources.len() - 1));
+        let name = |index: usize| format!("{} {}", localize!("source"), index + 1);
+        Grid::new("Compare").show(ui, |ui| {
+            for (index, (label, selected)) in [
+                (localize!("left"), &mut left),
+                (localize!("right"), &mut right),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                ui.label(label);
+                ComboBox::from_id_salt(("Compare", index))
+                    .selected_text(name(*selected))
+                    .show_ui(ui, |ui| {
+                        for index in 0..sources.len() {
+                            ui.selectable_value(selected, index, name(index));
+                        }
+                    });
+                ui.end_row();
+            }
+        });
+        if ui
+            .add_enabled(
+                left != right,
+                Button::new(format!("{GIT_DIFF} {}", localize!("compare"))),
+            )
+            .clicked()
+        {
+            self.tree.insert_pane::<VERTICAL>(Pane::compare(
+                [name(left), name(right)],
+                sources[left].clone(),
+                sources[right].clone(),
+            ));
+            ui.close_menu();
+        }
+        ui.data_mut(|data| data.insert_temp(id, [left, right]));
+    }
+
+    fn state(&self) -> State {
+        State {
+            data: self.data.clone(),
+            tree: self.tree.clone(),
+        }
+    }
+
+    fn restore(&mut self, state: State) {
+        self.data = state.data;
+        self.tree = state.tree;
+    }
+
+    fn undo(&mut self) {
+        let state = self.state();
+        if let Some(state) = self.history.undo(&state).cloned() {
+            self.restore(state);
+        }
+        self.actions(&self.state());
+    }
+
+    fn redo(&mut self) {
+        let state = self.state();
+        if let Some(state) = self.history.redo(&state).cloned() {
+            self.restore(state);
+        }
+        self.actions(&self.state());
+    }
+
+    /// Updates the availability of undo and redo
+    fn actions(&mut self, state: &State) {
+        self.can_undo = self.history.has_undo(state);
+        self.can_redo = self.history.has_redo(state);
+    }
+
+    fn history(&mut self, ctx: &egui::Context) {
+        // Redo first: `Ctrl+Z` matches `Ctrl+Shift+Z` logically
+        if ctx.input_mut(|input| input.consume_shortcut(&REDO)) {
+            self.redo();
+        } else if ctx.input_mut(|input| input.consume_shortcut(&UNDO)) {
+            self.undo();
+        }
+        // The state is recorded on an explicit change and once an interaction
+        // ends, not every frame
+        let interacted = ctx.input(|input| {
+            input.pointer.any_released()
+                || input
+                    .events
+                    .iter()
+                    .any(|event| matches!(event, Event::Key { pressed: false, .. }))
+        });
+        if take(&mut self.changed) || interacted {
+            let state = self.state();
+            // A new state drops the redos and becomes the undo point at once
+            self.history
+                .feed_state(ctx.input(|input| input.time), &state);
+            self.history.add_undo(&state);
+            self.actions(&state);
+        }
+    }
+
+    fn import(&mut self, ctx: &egui::Context) {
+        let Some(import) = &mut self.import else {
+            return;
+        };
+        let mut open = true;
+        let mut load = false;
+        Window::new(format!("{FOLDER_OPEN} {}", localize!("import")))
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(import.path.display().to_string());
+                ui.label(format!(
+                    "{}: {}",
+                    localize!("files"),
+                    import.data_frames.len()
+                ));
+                ui.separator();
+                ScrollArea::vertical().show(ui, |ui| {
+                    if let Err(error) = summary(ui, &import.summary) {
+                        error!(%error);
+                    }
+                });
+                ui.separator();
+                CollapsingHeader::new(localize!("batches"))
+                    .id_salt("ImportBatches")
+                    .show(ui, |ui| {
+                        ui.label(localize!("batches-description"));
+                        ScrollArea::vertical()
+                            .id_salt("ImportBatches")
+                            .show(ui, |ui| {
+                                Grid::new("ImportBatches").striped(true).show(ui, |ui| {
+                                    for (path, batch) in
+                                        import.paths.iter().zip(&mut import.batches)
+                                    {
+                                        let file_name = path.file_name().unwrap_or_default();
+                                        ui.label(file_name.to_string_lossy())
+                                            .on_hover_text(path.display().to_string());
+                                        ui.text_edit_singleline(batch);
+                                        ui.end_row();
+                                    }
+                                });
+                            });
+                    });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    load = ui.button(localize!("load")).clicked();
+                });
+            });
+        if load {
+            if let Some(import) = self.import.take() {
+                self.load(import.batches.into_iter().zip(import.data_frames).collect());
+            }
+        } else if !open {
+            self.import = None;
+        }
+    }
+
+    fn chromatograms(&mut self, ctx: &egui::Context) {
+        let Some(chromatograms) = &mut self.chromatograms else {
+            return;
+        };
+        let reference = self.tree.tiles.iter().find_map(|(_, tile)| match tile {
+            Tile::Pane(Pane::Source(pane)) => Some(&pane.source),
+            _ => None,
+        });
+        let mut open = true;
+        let mut assigned = None;
+        Window::new(format!("{CHART_LINE} {}", localize!("chromatograms")))
+            .open(&mut open)
+            .show(ctx, |ui| match chromatograms.ui(ui, reference) {
+                Ok(data_frame) => assigned = data_frame,
+                Err(error) => error!(%error),
+            });
+        if let Some(data_frame) = assigned {
+            self.chromatograms = None;
+            self.tree
+                .insert_pane::<VERTICAL>(Pane::source(data_frame, Origin::Detached));
+        } else if !open {
+            self.chromatograms = None;
+        }
+    }
+
+    fn missing(&mut self, ctx: &egui::Context) {
+        let mut open = !self.missing.is_empty();
+        Window::new(format!("{WARNING} {}", localize!("missing-in-replicates")))
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ScrollArea::vertical().show(ui, |ui| {
+                    if let Err(error) = missing(ui, &self.missing) {
+                        error!(%error);
+                    }
+                });
+            });
+        if !open {
+            self.missing = DataFrame::empty();
+        }
+    }
+
+    /// Writes the edited loaded data back to the data
+    fn source(&mut self, ctx: &egui::Context) {
+        if let Some(source) = ctx.data_mut(|data| data.remove_temp::<DataFrame>(Id::new("Source")))
+        {
+            if let Err(error) = self.data.replace(&source) {
+                error!(%error);
+            }
+            self.changed = true;
+        }
+    }
+
     fn distance(&mut self, ctx: &egui::Context) {
-        if let Some(data_frame) = ctx.data_mut(|data| data.remove_temp(Id::new("Distance"))) {
+        if let Some((data_frame, units)) =
+            ctx.data_mut(|data| data.remove_temp::<(DataFrame, TimeUnits)>(Id::new("Distance")))
+        {
+            // The distance source is the source target in its display units
+            let mut pane = DistancePane::new(data_frame);
+            pane.control.settings.units.source = units;
+            pane.control.settings.units.display = units;
+            self.tree.insert_pane::<VERTICAL>(Pane::Distance(pane));
+        }
+    }
+
+    /// Opens the spectra against the first source pane
+    fn spectra(&mut self, spectra: DataFrame) {
+        let source = self.tree.tiles.iter().find_map(|(_, tile)| match tile {
+            Tile::Pane(Pane::Source(pane)) => Some(pane.source.clone().into_inner()),
+            _ => None,
+        });
+        match source {
+            Some(source) => {
+                self.tree
+                    .insert_pane::<VERTICAL>(Pane::spectrum(source, spectra));
+            }
+            None => warn!("{}", localize!("spectrum-description")),
+        }
+    }
+
+    fn composition(&mut self, ctx: &egui::Context) {
+        if let Some(data_frame) =
+            ctx.data_mut(|data| data.remove_temp::<DataFrame>(Id::new("Composition")))
+        {
+            self.tree
+                .insert_pane::<VERTICAL>(Pane::composition(data_frame));
+        }
+    }
+
+    /// Compares the first composition pane with the reference ranges
+    fn reference(&mut self, reference: DataFrame) {
+        let pane = self.tree.tiles.iter_mut().find_map(|(_, tile)| match tile {
+            Tile::Pane(Pane::Composition(pane)) => Some(pane),
+            _ => None,
+        });
+        match pane {
+            Some(pane) => pane.reference = HashedDataFrame::new(reference),
+            None => warn!("{}", localize!("reference-description")),
+        }
+    }
+
+    fn increments(&mut self, ctx: &egui::Context) {
+        if let Some(data_frame) =
+            ctx.data_mut(|data| data.remove_temp::<DataFrame>(Id::new("Increments")))
+        {
             self.tree
-                .insert_pane::<VERTICAL>(Pane::distance(data_frame));
+                .insert_pane::<VERTICAL>(Pane::increments(data_frame));
         }
     }
 }
@@ -311,24 +983,159 @@ impl eframe::App for App {
 
     /// Called each time the UI needs repainting, which may be many times per second.
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.source(ctx);
         self.distance(ctx);
+        self.increments(ctx);
+        self.composition(ctx);
         self.panels(ctx);
+        self.import(ctx);
+        self.chromatograms(ctx);
+        self.missing(ctx);
         self.drag_and_drop(ctx);
+        self.history(ctx);
         if self.reactive {
             ctx.request_repaint();
         }
     }
 }
 
-fn bin(dropped_file: &DroppedFile) -> Result<DataFrame> {
-    Ok(bincode::deserialize(&dropped_file.bytes()?)?)
-}
-
 fn ron(dropped_file: &DroppedFile) -> Result<DataFrame> {
     Ok(ron::de::from_bytes(&dropped_file.bytes()?)?)
 }
 
+fn chromatogram(dropped_file: &DroppedFile) -> Result<Chromatogram> {
+    Chromatogram::read(&dropped_file.bytes()?)
+}
+
+fn spectra(dropped_file: &DroppedFile) -> Result<DataFrame> {
+    Ok(msp::parse(str::from_utf8(&dropped_file.bytes()?)?)?)
+}
+
+fn extension(dropped_file: &DroppedFile) -> Option<&str> {
+    match &dropped_file.path {
+        Some(path) => path.extension()?.to_str(),
+        None => Path::new(&dropped_file.name).extension()?.to_str(),
+    }
+}
+
+fn metadata(ui: &mut Ui, metadata: &Metadata) {
+    Grid::new(ui.next_auto_id()).show(ui, |ui| {
+        if let Some(column) = &metadata.column {
+            ui.label(localize!("column"));
+            ui.label(column);
+            ui.end_row();
+        }
+        if let Some(phase) = &metadata.phase {
+            ui.label(localize!("phase"));
+            ui.label(phase);
+            ui.end_row();
+        }
+        for (name, value) in [
+            (localize!("column-length"), metadata.length),
+            (localize!("column-diameter"), metadata.diameter),
+            (localize!("film-thickness"), metadata.film),
+        ] {
+            if let Some(value) = value {
+                ui.label(name);
+                ui.label(value.to_string());
+                ui.end_row();
+            }
+        }
+        if !metadata.carrier.is_empty() {
+            ui.label(localize!("carrier-gas"));
+            ui.label(&metadata.carrier);
+            ui.end_row();
+        }
+        if !metadata.program.is_empty() {
+            ui.label(localize!("temperature-program"));
+            ui.label(&metadata.program);
+            ui.end_row();
+        }
+    });
+}
+
+/// Text field of an optional value, the empty text unsets it
+fn optional_text(ui: &mut Ui, value: &mut Option<String>) -> Response {
+    let mut text = value.take().unwrap_or_default();
+    let response = ui.text_edit_singleline(&mut text);
+    *value = (!text.is_empty()).then_some(text);
+    response
+}
+
+/// Drag value of an optional value, zero unsets it
+fn optional_value(ui: &mut Ui, value: &mut Option<f64>, speed: f64) -> Response {
+    let mut number = value.unwrap_or_default();
+    let response = ui.add(
+        DragValue::new(&mut number)
+            .range(0.0..=f64::MAX)
+            .speed(speed),
+    );
+    *value = (number > 0.0).then_some(number);
+    response
+}
+
+fn summary(ui: &mut Ui, data_frame: &DataFrame) -> PolarsResult<()> {
+    Grid::new("summary")
+        .striped(true)
+        .show(ui, |ui| {
+            ui.heading(localize!("mode"));
+            ui.heading(localize!("files"));
+            ui.heading(localize!("rows"));
+            ui.end_row();
+            for row in 0..data_frame.height() {
+                ui.label(format!(
+                    "{}/{}",
+                    data_frame["OnsetTemperature"].get(row)?.str_value(),
+                    data_frame["TemperatureStep"].get(row)?.str_value(),
+                ));
+                ui.label(data_frame["Files"].get(row)?.str_value());
+                ui.label(data_frame["Rows"].get(row)?.str_value());
+                ui.end_row();
+            }
+            Ok(())
+        })
+        .inner
+}
+
+fn missing(ui: &mut Ui, data_frame: &DataFrame) -> PolarsResult<()> {
+    Grid::new("missing")
+        .striped(true)
+        .show(ui, |ui| {
+            ui.heading(localize!("mode"));
+            ui.heading(localize!("fatty-acid"));
+            ui.heading(localize!("present"));
+            ui.end_row();
+            let present = data_frame["Present"].list()?;
+            for row in 0..data_frame.height() {
+                ui.label(format!(
+                    "{}/{}",
+                    data_frame["OnsetTemperature"].get(row)?.str_value(),
+                    data_frame["TemperatureStep"].get(row)?.str_value(),
+                ));
+                ui.label(data_frame["FA"].get(row)?.str_value());
+                ui.label(format!(
+                    "{}/{}",
+                    present
+                        .get_as_series(row)
+                        .map_or(0, |present| present.len()),
+                    data_frame["Replicates"].get(row)?.str_value(),
+                ));
+                ui.end_row();
+            }
+            Ok(())
+        })
+        .inner
+}
+
+mod andi;
+mod chromatogram;
 mod computers;
 mod data;
+mod history;
+mod import;
+mod localization;
+mod msp;
 mod panes;
+mod project;
 mod text;
+mod units;
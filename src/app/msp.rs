@@ -0,0 +1,119 @@
+use polars::prelude::*;
+
+/// Supported file extension
+pub(crate) const EXTENSION: &str = "msp";
+
+/// Parses NIST MSP spectra
+///
+/// Every record is a `Name:` field, optional `Synon:` and other fields,
+/// `Num Peaks:` and the `m/z intensity` pairs separated by spaces, tabs,
+/// commas or semicolons, quoted peak annotations are skipped. Records are
+/// separated by blank lines.
+pub(crate) fn parse(text: &str) -> PolarsResult<DataFrame> {
+    let mut names = Vec::new();
+    let mut synonyms = Vec::new();
+    let mut mass_to_charge = Vec::new();
+    let mut intensities = Vec::new();
+    let mut record = Record::default();
+    for line in text.lines().chain([""]) {
+        let line = line.trim();
+        if line.is_empty() {
+            if let Some(name) = record.name.take() {
+                names.push(name);
+                synonyms.push(Series::new(PlSmallStr::EMPTY, &record.synonyms));
+                mass_to_charge.push(Series::new(PlSmallStr::EMPTY, &record.mass_to_charge));
+                intensities.push(Series::new(PlSmallStr::EMPTY, &record.intensities));
+            }
+            record = Record::default();
+        } else if let Some((key, value)) = line
+            .split_once(':')
+            .filter(|(key, _)| key.starts_with(|c: char| c.is_alphabetic()))
+        {
+            match key.trim().to_lowercase().as_str() {
+                "name" => record.name = Some(value.trim().to_owned()),
+                "synon" => record.synonyms.push(value.trim().to_owned()),
+                _ => {}
+            }
+        } else {
+            record.peaks(line);
+        }
+    }
+    DataFrame::new(vec![
+        Column::new("Name".into(), names),
+        Column::new("Synonyms".into(), synonyms),
+        Column::new("MassToCharge".into(), mass_to_charge),
+        Column::new("Intensity".into(), intensities),
+    ])
+}
+
+/// Record
+#[derive(Clone, Debug, Default)]
+struct Record {
+    name: Option<String>,
+    synonyms: Vec<String>,
+    mass_to_charge: Vec<f64>,
+    intensities: Vec<f64>,
+}
+
+impl Record {
+    fn peaks(&mut self, line: &str) {
+        let line: String = line.split('"').step_by(2).collect();
+        let mut values = line
+            .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+            .filter(|value| !value.is_empty())
+            .filter_map(|value| value.parse::<f64>().ok());
+        while let (Some(mass_to_charge), Some(intensity)) = (values.next(), values.next()) {
+            self.mass_to_charge.push(mass_to_charge);
+            self.intensities.push(intensity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "\
+Name: Methyl palmitate
+Synon: C16:0 FAME
+Synon: Hexadecanoic acid, methyl ester
+Formula: C17H34O2
+Num Peaks: 4
+74 999; 87 650
+143 \"M-113\" 120
+270\t80
+
+NAME: Methyl oleate
+Num Peaks: 2
+55,999 264,300
+";
+
+    #[test]
+    fn parse() -> PolarsResult<()> {
+        let data_frame = super::parse(TEXT)?;
+        assert_eq!(data_frame.height(), 2);
+        let names: Vec<_> = data_frame["Name"].str()?.into_iter().flatten().collect();
+        assert_eq!(names, ["Methyl palmitate", "Methyl oleate"]);
+        let synonyms = data_frame["Synonyms"].list()?;
+        let first = synonyms.get_as_series(0).unwrap();
+        let first: Vec<_> = first.str()?.into_iter().flatten().collect();
+        assert_eq!(first, ["C16:0 FAME", "Hexadecanoic acid, methyl ester"]);
+        assert_eq!(synonyms.get_as_series(1).unwrap().len(), 0);
+        let peaks = |name: &str, row| -> PolarsResult<Vec<f64>> {
+            let series = data_frame[name].list()?.get_as_series(row).unwrap();
+            Ok(series.f64()?.into_iter().flatten().collect())
+        };
+        assert_eq!(peaks("MassToCharge", 0)?, [74.0, 87.0, 143.0, 270.0]);
+        assert_eq!(peaks("Intensity", 0)?, [999.0, 650.0, 120.0, 80.0]);
+        assert_eq!(peaks("MassToCharge", 1)?, [55.0, 264.0]);
+        assert_eq!(peaks("Intensity", 1)?, [999.0, 300.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn empty() -> PolarsResult<()> {
+        assert_eq!(super::parse("")?.height(), 0);
+        assert_eq!(super::parse("Num Peaks: 1\n41 100\n")?.height(), 0);
+        Ok(())
+    }
+}
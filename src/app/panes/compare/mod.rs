@@ -0,0 +1,198 @@
+use self::{control::Control, settings::Settings, table::TableView};
+use super::widgets::status::StatusWidget;
+use crate::{
+    app::{
+        computers::{CompareComputed, CompareKey, Status},
+        data::{Format, save},
+        localize,
+    },
+    special::{column::mode::ColumnExt as _, substituent::ColumnExt as _},
+    utils::HashedDataFrame,
+};
+use egui::{CollapsingHeader, Grid, RichText, Ui, Window};
+use egui_phosphor::regular::{ARROWS_HORIZONTAL, ARROWS_LEFT_RIGHT, FLOPPY_DISK, GEAR};
+use lipid::fatty_acid::{
+    display::{COMMON, DisplayWithOptions as _},
+    polars::ColumnExt as _,
+};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::mem::swap;
+use tracing::error;
+
+/// Compare pane
+///
+/// Two datasets aligned by mode and fatty acid.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Pane {
+    pub(crate) names: [String; 2],
+    pub(crate) left: HashedDataFrame,
+    pub(crate) right: HashedDataFrame,
+    #[serde(skip)]
+    pub(crate) target: DataFrame,
+    #[serde(skip)]
+    only: DataFrame,
+    #[serde(skip)]
+    status: Option<Status>,
+    pub(crate) control: Control,
+}
+
+/// Computed target and the settings window state are not part of the history
+impl PartialEq for Pane {
+    fn eq(&self, other: &Self) -> bool {
+        self.names == other.names
+            && self.left == other.left
+            && self.right == other.right
+            && self.control.settings == other.control.settings
+    }
+}
+
+impl Pane {
+    pub(crate) fn new(names: [String; 2], left: DataFrame, right: DataFrame) -> Self {
+        Self {
+            names,
+            left: HashedDataFrame::new(left),
+            right: HashedDataFrame::new(right),
+            target: DataFrame::empty(),
+            only: DataFrame::empty(),
+            status: None,
+            control: Control::new(),
+        }
+    }
+
+    pub(super) fn header(&mut self, ui: &mut Ui) {
+        ui.visuals_mut().button_frame = false;
+        ui.separator();
+        ui.add(StatusWidget::new(self.status.as_ref()));
+        ui.toggle_value(
+            &mut self.control.settings.resizable,
+            RichText::new(ARROWS_HORIZONTAL).heading(),
+        )
+        .on_hover_text(localize!("resize"));
+        ui.toggle_value(&mut self.control.open, RichText::new(GEAR).heading());
+        ui.separator();
+        if ui
+            .button(RichText::new(ARROWS_LEFT_RIGHT).heading())
+            .on_hover_text(localize!("swap-datasets"))
+            .clicked()
+        {
+            let [left, right] = &mut self.names;
+            swap(left, right);
+            swap(&mut self.left, &mut self.right);
+        }
+        ui.separator();
+        ui.menu_button(RichText::new(FLOPPY_DISK).heading(), |ui| {
+            if ui.button("BIN").clicked() {
+                if let Err(error) = save("compare.bin", Format::Bin, self.target.clone()) {
+                    error!(%error);
+                }
+            }
+            if ui.button("RON").clicked() {
+                if let Err(error) = save("compare.ron", Format::Ron, self.target.clone()) {
+                    error!(%error);
+                }
+            }
+        });
+    }
+
+    pub(super) fn content(&mut self, ui: &mut Ui) {
+        self.window(ui);
+        self.compute(ui);
+        // The last result stays on screen until the new one is ready
+        if self.target.width() == 0 {
+            ui.centered_and_justified(|ui| ui.spinner());
+            return;
+        }
+        if let Err(error) = self.only_ui(ui) {
+            error!(%error);
+        }
+        TableView::new(&self.names, &self.target, &self.control.settings).ui(ui);
+    }
+
+    fn compute(&mut self, ui: &mut Ui) {
+        let ctx = ui.ctx().clone();
+        let status = ui.memory_mut(|memory| {
+            memory.caches.cache::<CompareComputed>().get(
+                &ctx,
+                CompareKey {
+                    names: &self.names,
+                    left: &self.left,
+                    right: &self.right,
+                    settings: &self.control.settings,
+                },
+            )
+        });
+        self.status = match status {
+            Status::Ready(data_frame) => match self.split(&data_frame) {
+                Ok(()) => None,
+                Err(error) => Some(Status::Failed(error.to_string())),
+            },
+            status => Some(status),
+        };
+    }
+
+    /// Splits the aligned rows from the rows present in one dataset only
+    fn split(&mut self, data_frame: &DataFrame) -> PolarsResult<()> {
+        let matched = data_frame["Matched"].bool()?;
+        self.target = data_frame.filter(matched)?;
+        self.only = data_frame.filter(&!matched)?;
+        Ok(())
+    }
+
+    /// Fatty acids present in one dataset only
+    fn only_ui(&self, ui: &mut Ui) -> PolarsResult<()> {
+        if self.only.is_empty() {
+            return Ok(());
+        }
+        let left = self.only["Left"].struct_()?.field_by_name("Mean")?;
+        let fatty_acids = self.only["FattyAcid"].fatty_acid();
+        let modes = self.only["Mode"].mode();
+        CollapsingHeader::new(format!(
+            "{} ({})",
+            localize!("present-in-one-dataset"),
+            self.only.height()
+        ))
+        .id_salt(ui.next_auto_id())
+        .show(ui, |ui| {
+            Grid::new(ui.next_auto_id())
+                .striped(true)
+                .show(ui, |ui| -> PolarsResult<()> {
+                    ui.heading(localize!("mode"));
+                    ui.heading(localize!("fatty-acid"));
+                    ui.heading(localize!("dataset"));
+                    ui.end_row();
+                    for row in 0..self.only.height() {
+                        ui.label(modes.program(row)?.to_string());
+                        if let Some(fatty_acid) = fatty_acids.get(row)? {
+                            let substituents = self.only["FattyAcid"].substituents(row)?;
+                            ui.label(format!("{substituents}{:#}", fatty_acid.display(COMMON)));
+                        } else {
+                            ui.label("");
+                        }
+                        let side = if left.f64()?.get(row).is_some() { 0 } else { 1 };
+                        ui.label(&self.names[side]);
+                        ui.end_row();
+                    }
+                    Ok(())
+                })
+                .inner
+        })
+        .body_returned
+        .transpose()?;
+        Ok(())
+    }
+
+    fn window(&mut self, ui: &mut Ui) {
+        Window::new(format!("{GEAR} {}", localize!("compare-settings")))
+            .id(ui.next_auto_id())
+            .open(&mut self.control.open)
+            .show(ui.ctx(), |ui| {
+                self.control.settings.ui(ui, &self.target);
+            });
+    }
+}
+
+pub(crate) mod settings;
+
+mod control;
+mod table;
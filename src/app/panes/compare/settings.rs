@@ -0,0 +1,87 @@
+use crate::app::{MAX_PRECISION, localize, units::Units};
+use egui::{Grid, RichText, Slider, Ui};
+use egui_ext::LabeledSeparator;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Settings
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Settings {
+    pub(crate) precision: usize,
+    pub(crate) resizable: bool,
+    pub(crate) sticky: usize,
+    pub(crate) truncate: bool,
+    pub(crate) units: Units,
+
+    pub(crate) ddof: u8,
+    pub(crate) logarithmic: bool,
+    pub(crate) significance: f64,
+}
+
+impl Settings {
+    pub(crate) const fn new() -> Self {
+        Self {
+            precision: 2,
+            resizable: false,
+            sticky: 1,
+            truncate: false,
+            units: Units::new(),
+
+            ddof: 1,
+            logarithmic: false,
+            significance: 0.05,
+        }
+    }
+
+    pub(crate) fn ui(&mut self, ui: &mut Ui, data_frame: &DataFrame) {
+        Grid::new("compare").show(ui, |ui| {
+            // Precision floats
+            ui.label(localize!("precision"));
+            ui.add(Slider::new(&mut self.precision, 0..=MAX_PRECISION));
+            ui.end_row();
+
+            // Sticky columns
+            ui.label(localize!("sticky"));
+            ui.add(Slider::new(&mut self.sticky, 0..=data_frame.width()));
+            ui.end_row();
+
+            // Truncate titles
+            ui.label(localize!("truncate"));
+            ui.checkbox(&mut self.truncate, "");
+            ui.end_row();
+
+            // Units
+            ui.separator();
+            ui.labeled_separator(RichText::new(localize!("units")).heading());
+            ui.end_row();
+            self.units.ui(ui);
+
+            // Calculate
+            ui.separator();
+            ui.labeled_separator(RichText::new(localize!("calculate")).heading());
+            ui.end_row();
+
+            // https://numpy.org/devdocs/reference/generated/numpy.std.html
+            ui.label("DDOF");
+            ui.add(Slider::new(&mut self.ddof, 0..=2));
+            ui.end_row();
+
+            // Logarithmic
+            ui.label(localize!("logarithmic"));
+            ui.checkbox(&mut self.logarithmic, "");
+            ui.end_row();
+
+            // Significance level
+            ui.label(localize!("significance"))
+                .on_hover_text(localize!("significance-description"));
+            ui.add(Slider::new(&mut self.significance, 0.001..=0.1).logarithmic(true));
+            ui.end_row();
+        });
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
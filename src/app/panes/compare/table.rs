@@ -0,0 +1,216 @@
+use super::Settings;
+use crate::{
+    app::{localize, panes::widgets::float::FloatValue, units::RetentionTimeFormat},
+    special::{column::mode::ColumnExt as _, substituent::ColumnExt as _},
+};
+use egui::{Frame, Id, Margin, RichText, TextStyle, TextWrapMode, Ui, Vec2, vec2};
+use egui_table::{AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate};
+use lipid::fatty_acid::{
+    display::{COMMON, DisplayWithOptions as _},
+    polars::ColumnExt,
+};
+use polars::prelude::*;
+
+const INDEX: usize = 0;
+const MODE: usize = 1;
+const FA: usize = 2;
+const LEFT: usize = 3;
+const RIGHT: usize = 4;
+const TIME: usize = 5;
+const ECL: usize = 6;
+const FCL: usize = 7;
+const P: usize = 8;
+const LEN: usize = 9;
+
+const MARGIN: Vec2 = vec2(4.0, 0.0);
+
+/// Table view
+#[derive(Clone, Debug)]
+pub(crate) struct TableView<'a> {
+    pub(crate) names: &'a [String; 2],
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl<'a> TableView<'a> {
+    pub(crate) const fn new(
+        names: &'a [String; 2],
+        data_frame: &'a DataFrame,
+        settings: &'a Settings,
+    ) -> Self {
+        Self {
+            names,
+            data_frame,
+            settings,
+        }
+    }
+}
+
+impl TableView<'_> {
+    pub(super) fn ui(&mut self, ui: &mut Ui) {
+        ui.visuals_mut().collapsing_header_frame = true;
+        let id_salt = Id::new("CompareTable");
+        let height = ui.text_style_height(&TextStyle::Heading);
+        let num_rows = self.data_frame.height() as _;
+        Table::new()
+            .id_salt(id_salt)
+            .num_rows(num_rows)
+            .columns(vec![
+                Column::default().resizable(self.settings.resizable);
+                LEN
+            ])
+            .num_sticky_cols(self.settings.sticky)
+            .headers([HeaderRow::new(height)])
+            .auto_size_mode(AutoSizeMode::OnParentResize)
+            .show(ui, self);
+    }
+
+    fn header_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) {
+        if self.settings.truncate {
+            ui.style_mut().wrap_mode = Some(TextWrapMode::Truncate);
+        }
+        let units = self.settings.units.display.abbreviation();
+        match (row, column) {
+            (0, INDEX) => {
+                ui.heading(localize!("index"));
+            }
+            (0, MODE) => {
+                ui.heading(localize!("mode"));
+            }
+            (0, FA) => {
+                ui.heading(localize!("fatty-acid"));
+            }
+            (0, LEFT) => {
+                ui.heading(format!("{}, {units}", self.names[0]));
+            }
+            (0, RIGHT) => {
+                ui.heading(format!("{}, {units}", self.names[1]));
+            }
+            (0, TIME) => {
+                ui.heading(format!("Δ {}, {units}", localize!("time")));
+            }
+            (0, ECL) => {
+                ui.heading("Δ ECL");
+            }
+            (0, FCL) => {
+                ui.heading("Δ FCL");
+            }
+            (0, P) => {
+                ui.heading("p")
+                    .on_hover_text(localize!("welch-p-value-description"));
+            }
+            _ => {}
+        }
+    }
+
+    fn body_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) -> PolarsResult<()> {
+        match (row, column) {
+            (row, INDEX) => {
+                let indices = self.data_frame["Index"].u32()?;
+                let value = indices.get(row).unwrap();
+                ui.label(value.to_string());
+            }
+            (row, MODE) => {
+                let program = self.data_frame["Mode"].mode().program(row)?;
+                ui.label(program.to_string());
+            }
+            (row, FA) => {
+                let fatty_acids = self.data_frame["FattyAcid"].fatty_acid();
+                if let Some(fatty_acid) = fatty_acids.get(row)? {
+                    let substituents = self.data_frame["FattyAcid"].substituents(row)?;
+                    ui.label(format!("{substituents}{:#}", fatty_acid.display(COMMON)));
+                }
+            }
+            (row, LEFT) => self.side_ui(ui, row, "Left")?,
+            (row, RIGHT) => self.side_ui(ui, row, "Right")?,
+            (row, TIME) => self.delta_ui(ui, row, "Time")?,
+            (row, ECL) => self.delta_ui(ui, row, "ECL")?,
+            (row, FCL) => self.delta_ui(ui, row, "FCL")?,
+            (row, P) => {
+                let p_value = self.data_frame["P"].f64()?.get(row);
+                self.value_ui(ui, row, p_value)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn side_ui(&self, ui: &mut Ui, row: usize, side: &str) -> PolarsResult<()> {
+        let side = self.data_frame[side].struct_()?;
+        let mean = side.field_by_name("Mean")?;
+        let standard_deviation = side.field_by_name("StandardDeviation")?;
+        let values = side.field_by_name("Values")?;
+        let units = self.settings.units.display;
+        ui.add(FloatValue::new(mean.f64()?.get(row)).precision(Some(self.settings.precision)))
+            .on_hover_ui(|ui| {
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    ui.label(mean.str_value(row).unwrap_or_default());
+                    ui.label("±");
+                    ui.label(standard_deviation.str_value(row).unwrap_or_default());
+                    ui.label(format!(" {}", units.abbreviation()));
+                });
+                ui.heading(localize!("repeats"));
+                if let Some(values) = values
+                    .list()
+                    .ok()
+                    .and_then(|values| values.get_as_series(row))
+                {
+                    for value in values.f64().into_iter().flatten().flatten() {
+                        ui.label(RetentionTimeFormat::new(value).units(units));
+                    }
+                }
+            });
+        Ok(())
+    }
+
+    fn delta_ui(&self, ui: &mut Ui, row: usize, name: &str) -> PolarsResult<()> {
+        let delta = self.data_frame["Delta"].struct_()?.field_by_name(name)?;
+        self.value_ui(ui, row, delta.f64()?.get(row))
+    }
+
+    /// Value in bold if the difference is significant
+    fn value_ui(&self, ui: &mut Ui, row: usize, value: Option<f64>) -> PolarsResult<()> {
+        let text = match value {
+            Some(value) => format!("{value:.*}", self.settings.precision),
+            None => AnyValue::Null.to_string(),
+        };
+        let mut text = RichText::new(text);
+        if self.significant(row)? {
+            text = text.strong();
+        }
+        ui.label(text)
+            .on_hover_text(value.map_or_else(String::new, |value| value.to_string()));
+        Ok(())
+    }
+
+    fn significant(&self, row: usize) -> PolarsResult<bool> {
+        Ok(self.data_frame["P"]
+            .f64()?
+            .get(row)
+            .is_some_and(|p_value| p_value < self.settings.significance))
+    }
+}
+
+impl TableDelegate for TableView<'_> {
+    fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.header_cell_content_ui(ui, cell.row_nr, cell.group_index)
+            });
+    }
+
+    fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        if cell.row_nr % 2 == 0 {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
+        }
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.body_cell_content_ui(ui, cell.row_nr as _, cell.col_nr)
+                    .unwrap()
+            });
+    }
+}
@@ -0,0 +1,125 @@
+use super::{Settings, table::statistics_ui};
+use crate::{
+    app::{computers::indices::NAMES, localize},
+    special::column::mode::ColumnExt as _,
+};
+use egui::{Frame, Id, Margin, TextStyle, TextWrapMode, Ui, Vec2, vec2};
+use egui_table::{AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate};
+use polars::prelude::*;
+
+const INDEX: usize = 0;
+const MODE: usize = 1;
+const LEN: usize = 2 + NAMES.len();
+
+const MARGIN: Vec2 = vec2(4.0, 0.0);
+
+/// Indices view
+#[derive(Clone, Debug)]
+pub(crate) struct IndicesView<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl<'a> IndicesView<'a> {
+    pub(crate) const fn new(data_frame: &'a DataFrame, settings: &'a Settings) -> Self {
+        Self {
+            data_frame,
+            settings,
+        }
+    }
+}
+
+impl IndicesView<'_> {
+    pub(super) fn ui(&mut self, ui: &mut Ui) {
+        let id_salt = Id::new("IndicesTable");
+        let height = ui.text_style_height(&TextStyle::Heading);
+        let num_rows = self.data_frame.height() as _;
+        Table::new()
+            .id_salt(id_salt)
+            .num_rows(num_rows)
+            .columns(vec![
+                Column::default().resizable(self.settings.resizable);
+                LEN
+            ])
+            .num_sticky_cols(self.settings.sticky)
+            .headers([HeaderRow::new(height)])
+            .auto_size_mode(AutoSizeMode::OnParentResize)
+            .show(ui, self);
+    }
+
+    fn header_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) {
+        if self.settings.truncate {
+            ui.style_mut().wrap_mode = Some(TextWrapMode::Truncate);
+        }
+        match (row, column) {
+            (0, INDEX) => {
+                ui.heading(localize!("index"));
+            }
+            (0, MODE) => {
+                ui.heading(localize!("mode"));
+            }
+            (0, column) => {
+                let (heading, description) = heading(column - 2);
+                ui.heading(heading).on_hover_text(description);
+            }
+            _ => {}
+        }
+    }
+
+    fn body_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) -> PolarsResult<()> {
+        match (row, column) {
+            (row, INDEX) => {
+                let indices = self.data_frame["Index"].u32()?;
+                let value = indices.get(row).unwrap();
+                ui.label(value.to_string());
+            }
+            (row, MODE) => {
+                let program = self.data_frame["Mode"].mode().program(row)?;
+                ui.label(program.to_string());
+            }
+            (row, column) => {
+                statistics_ui(ui, self.data_frame, row, NAMES[column - 2], self.settings)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TableDelegate for IndicesView<'_> {
+    fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.header_cell_content_ui(ui, cell.row_nr, cell.group_index)
+            });
+    }
+
+    fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        if cell.row_nr % 2 == 0 {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
+        }
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.body_cell_content_ui(ui, cell.row_nr as _, cell.col_nr)
+                    .unwrap()
+            });
+    }
+}
+
+/// Heading and description of the index
+fn heading(index: usize) -> (&'static str, String) {
+    match index {
+        0 => ("ΣSFA", localize!("saturated-description")),
+        1 => ("ΣMUFA", localize!("monounsaturated-description")),
+        2 => ("ΣPUFA", localize!("polyunsaturated-description")),
+        3 => ("n-3", localize!("omega3-description")),
+        4 => ("n-6", localize!("omega6-description")),
+        5 => ("n-6/n-3", localize!("omega-ratio-description")),
+        6 => ("UI", localize!("unsaturation-index-description")),
+        7 => ("AI", localize!("atherogenic-index-description")),
+        8 => ("TI", localize!("thrombogenic-index-description")),
+        _ => ("h/H", localize!("hypocholesterolemic-description")),
+    }
+}
@@ -0,0 +1,262 @@
+use self::{
+    control::Control,
+    indices::IndicesView,
+    reference::{PlotView, ReferenceView},
+    settings::{Kind, Settings},
+};
+use super::widgets::status::StatusWidget;
+use crate::{
+    app::{
+        computers::{
+            CompositionComputed, CompositionKey, IndicesComputed, IndicesKey, ReferenceComputed,
+            ReferenceKey, Status,
+        },
+        data::{Format, save},
+        localize,
+    },
+    utils::HashedDataFrame,
+};
+use egui::{RichText, Ui, Window};
+use egui_phosphor::regular::{
+    ARROWS_HORIZONTAL, CHART_BAR, FLOPPY_DISK, GEAR, HEARTBEAT, RULER, TABLE,
+};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use table::TableView;
+use tracing::error;
+
+/// Composition pane
+///
+/// Fatty acid composition of the samples from the peak areas, its
+/// nutritional indices and the comparison with the reference ranges.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Pane {
+    pub(crate) source: HashedDataFrame,
+    pub(crate) reference: HashedDataFrame,
+    #[serde(skip)]
+    pub(crate) target: HashedDataFrame,
+    #[serde(skip)]
+    pub(crate) indices: HashedDataFrame,
+    #[serde(skip)]
+    pub(crate) ranges: HashedDataFrame,
+    #[serde(skip)]
+    status: Option<Status>,
+    pub(crate) control: Control,
+}
+
+/// Computed target and the settings window state are not part of the history
+impl PartialEq for Pane {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+            && self.reference == other.reference
+            && self.control.settings == other.control.settings
+    }
+}
+
+impl Pane {
+    pub(crate) fn new(data_frame: DataFrame) -> Self {
+        Self {
+            source: HashedDataFrame::new(data_frame),
+            reference: HashedDataFrame::default(),
+            target: HashedDataFrame::default(),
+            indices: HashedDataFrame::default(),
+            ranges: HashedDataFrame::default(),
+            status: None,
+            control: Control::new(),
+        }
+    }
+
+    pub(super) fn header(&mut self, ui: &mut Ui) {
+        ui.visuals_mut().button_frame = false;
+        ui.separator();
+        ui.add(StatusWidget::new(self.status.as_ref()));
+        ui.toggle_value(
+            &mut self.control.settings.resizable,
+            RichText::new(ARROWS_HORIZONTAL).heading(),
+        )
+        .on_hover_text(localize!("resize"));
+        ui.toggle_value(&mut self.control.open, RichText::new(GEAR).heading());
+        ui.separator();
+        let kind = &mut self.control.settings.kind;
+        ui.selectable_value(kind, Kind::Composition, RichText::new(TABLE).heading())
+            .on_hover_text(localize!("composition"));
+        ui.selectable_value(kind, Kind::Indices, RichText::new(HEARTBEAT).heading())
+            .on_hover_text(localize!("indices"));
+        // Reference ranges are dropped on the pane
+        ui.add_enabled_ui(self.reference.width() != 0, |ui| {
+            ui.selectable_value(kind, Kind::Reference, RichText::new(RULER).heading())
+                .on_hover_text(localize!("reference"))
+                .on_disabled_hover_text(localize!("reference-description"));
+            ui.selectable_value(kind, Kind::Plot, RichText::new(CHART_BAR).heading())
+                .on_hover_text(localize!("reference"))
+                .on_disabled_hover_text(localize!("reference-description"));
+        });
+        ui.separator();
+        ui.menu_button(RichText::new(FLOPPY_DISK).heading(), |ui| {
+            if ui.button("BIN").clicked() {
+                if let Err(error) = save(
+                    "composition.bin",
+                    Format::Bin,
+                    self.target.clone().into_inner(),
+                ) {
+                    error!(%error);
+                }
+            }
+            if ui.button("RON").clicked() {
+                if let Err(error) = save(
+                    "composition.ron",
+                    Format::Ron,
+                    self.target.clone().into_inner(),
+                ) {
+                    error!(%error);
+                }
+            }
+            ui.separator();
+            if ui.button(format!("{} BIN", localize!("indices"))).clicked() {
+                if let Err(error) = save(
+                    "indices.bin",
+                    Format::Bin,
+                    self.indices.clone().into_inner(),
+                ) {
+                    error!(%error);
+                }
+            }
+            if ui.button(format!("{} RON", localize!("indices"))).clicked() {
+                if let Err(error) = save(
+                    "indices.ron",
+                    Format::Ron,
+                    self.indices.clone().into_inner(),
+                ) {
+                    error!(%error);
+                }
+            }
+            ui.separator();
+            if ui
+                .button(format!("{} BIN", localize!("reference")))
+                .clicked()
+            {
+                if let Err(error) = save(
+                    "reference.bin",
+                    Format::Bin,
+                    self.ranges.clone().into_inner(),
+                ) {
+                    error!(%error);
+                }
+            }
+            if ui
+                .button(format!("{} RON", localize!("reference")))
+                .clicked()
+            {
+                if let Err(error) = save(
+                    "reference.ron",
+                    Format::Ron,
+                    self.ranges.clone().into_inner(),
+                ) {
+                    error!(%error);
+                }
+            }
+        });
+    }
+
+    pub(super) fn content(&mut self, ui: &mut Ui) {
+        self.window(ui);
+        self.compute(ui);
+        // The last result stays on screen until the new one is ready
+        match self.control.settings.kind {
+            Kind::Composition if self.target.width() != 0 => {
+                TableView::new(&self.target, &self.control.settings).ui(ui);
+            }
+            Kind::Indices if self.indices.width() != 0 => {
+                IndicesView::new(&self.indices, &self.control.settings).ui(ui);
+            }
+            Kind::Reference if self.ranges.width() != 0 => {
+                ReferenceView::new(&self.ranges, &self.control.settings).ui(ui);
+            }
+            Kind::Plot if self.ranges.width() != 0 => {
+                PlotView::new(&self.ranges, &self.control.settings).ui(ui);
+            }
+            _ => {
+                ui.centered_and_justified(|ui| ui.spinner());
+            }
+        }
+    }
+
+    fn compute(&mut self, ui: &mut Ui) {
+        let ctx = ui.ctx().clone();
+        let status = ui.memory_mut(|memory| {
+            memory.caches.cache::<CompositionComputed>().get(
+                &ctx,
+                CompositionKey {
+                    data_frame: &self.source,
+                    settings: &self.control.settings,
+                },
+            )
+        });
+        self.status = match status {
+            Status::Ready(data_frame) => {
+                self.target = data_frame;
+                None
+            }
+            status => Some(status),
+        };
+        // Indices of the ready composition
+        if self.status.is_some() || self.target.width() == 0 {
+            return;
+        }
+        let status = ui.memory_mut(|memory| {
+            memory.caches.cache::<IndicesComputed>().get(
+                &ctx,
+                IndicesKey {
+                    data_frame: &self.target,
+                    settings: &self.control.settings,
+                },
+            )
+        });
+        self.status = match status {
+            Status::Ready(data_frame) => {
+                self.indices = data_frame;
+                None
+            }
+            status => Some(status),
+        };
+        // Reference ranges of the ready composition
+        if self.status.is_some() || self.reference.width() == 0 {
+            return;
+        }
+        let status = ui.memory_mut(|memory| {
+            memory.caches.cache::<ReferenceComputed>().get(
+                &ctx,
+                ReferenceKey {
+                    data_frame: &self.target,
+                    reference: &self.reference,
+                    settings: &self.control.settings,
+                },
+            )
+        });
+        self.status = match status {
+            Status::Ready(data_frame) => {
+                self.ranges = data_frame;
+                None
+            }
+            status => Some(status),
+        };
+    }
+
+    fn window(&mut self, ui: &mut Ui) {
+        Window::new(format!("{GEAR} {}", localize!("composition-settings")))
+            .id(ui.next_auto_id())
+            .open(&mut self.control.open)
+            .show(ui.ctx(), |ui| {
+                if let Err(error) = self.control.settings.ui(ui, &self.target) {
+                    error!(%error);
+                }
+            });
+    }
+}
+
+pub(crate) mod settings;
+
+mod control;
+mod indices;
+mod reference;
+mod table;
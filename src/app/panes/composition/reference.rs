@@ -0,0 +1,310 @@
+use super::{Settings, settings::name, table::statistics_ui};
+use crate::{
+    app::{localize, panes::widgets::float::FloatValue, text::Text as _},
+    special::column::mode::ColumnExt as _,
+};
+use egui::{Color32, Frame, Id, Margin, RichText, TextStyle, TextWrapMode, Ui, Vec2, vec2};
+use egui_plot::{BoxElem, BoxPlot, BoxSpread, Plot, Points};
+use egui_table::{AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate};
+use polars::prelude::*;
+use tracing::error;
+
+const INDEX: usize = 0;
+const MODE: usize = 1;
+const FA: usize = 2;
+const VALUE: usize = 3;
+const MEDIAN: usize = 4;
+const RANGE: usize = 5;
+const DEVIATION: usize = 6;
+const FLAGGED: usize = 7;
+const LEN: usize = 8;
+
+const MARGIN: Vec2 = vec2(4.0, 0.0);
+
+/// Reference view
+///
+/// Sample compositions against the reference ranges.
+#[derive(Clone, Debug)]
+pub(crate) struct ReferenceView<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl<'a> ReferenceView<'a> {
+    pub(crate) const fn new(data_frame: &'a DataFrame, settings: &'a Settings) -> Self {
+        Self {
+            data_frame,
+            settings,
+        }
+    }
+}
+
+impl ReferenceView<'_> {
+    pub(super) fn ui(&mut self, ui: &mut Ui) {
+        let id_salt = Id::new("ReferenceTable");
+        let height = ui.text_style_height(&TextStyle::Heading);
+        let num_rows = self.data_frame.height() as _;
+        Table::new()
+            .id_salt(id_salt)
+            .num_rows(num_rows)
+            .columns(vec![
+                Column::default().resizable(self.settings.resizable);
+                LEN
+            ])
+            .num_sticky_cols(self.settings.sticky)
+            .headers([HeaderRow::new(height)])
+            .auto_size_mode(AutoSizeMode::OnParentResize)
+            .show(ui, self);
+    }
+
+    fn header_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) {
+        if self.settings.truncate {
+            ui.style_mut().wrap_mode = Some(TextWrapMode::Truncate);
+        }
+        match (row, column) {
+            (0, INDEX) => {
+                ui.heading(localize!("index"));
+            }
+            (0, MODE) => {
+                ui.heading(localize!("mode"));
+            }
+            (0, FA) => {
+                ui.heading(localize!("fatty-acid"));
+            }
+            (0, VALUE) => {
+                ui.heading(format!("{}, %", self.settings.basis.text()));
+            }
+            (0, MEDIAN) => {
+                ui.heading(format!("{}, %", localize!("median")))
+                    .on_hover_text(localize!("median-description"));
+            }
+            (0, RANGE) => {
+                ui.heading(format!("{}, %", localize!("range")))
+                    .on_hover_text(localize!("reference-range-description"));
+            }
+            (0, DEVIATION) => {
+                ui.heading("z")
+                    .on_hover_text(localize!("deviation-description"));
+            }
+            (0, FLAGGED) => {
+                ui.heading(localize!("flagged"))
+                    .on_hover_text(localize!("flagged-description"));
+            }
+            _ => {}
+        }
+    }
+
+    fn body_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) -> PolarsResult<()> {
+        match (row, column) {
+            (row, INDEX) => {
+                let indices = self.data_frame["Index"].u32()?;
+                let value = indices.get(row).unwrap();
+                ui.label(value.to_string());
+            }
+            (row, MODE) => {
+                let program = self.data_frame["Mode"].mode().program(row)?;
+                ui.label(program.to_string());
+            }
+            (row, FA) => {
+                let text = name(self.data_frame, row)?.unwrap_or_default();
+                if self.out(row)? {
+                    ui.label(RichText::new(text).color(ui.visuals().error_fg_color))
+                        .on_hover_text(localize!("out-of-range"));
+                } else {
+                    ui.label(text);
+                }
+            }
+            (row, VALUE) => statistics_ui(ui, self.data_frame, row, "Value", self.settings)?,
+            (row, MEDIAN) => {
+                let reference = self.data_frame["Reference"].struct_()?;
+                let median = reference.field_by_name("Median")?;
+                let interquartile_range = reference.field_by_name("InterquartileRange")?;
+                ui.add(
+                    FloatValue::new(median.f64()?.get(row))
+                        .precision(Some(self.settings.precision)),
+                )
+                .on_hover_ui(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    ui.horizontal(|ui| {
+                        ui.label(median.str_value(row).unwrap_or_default());
+                        ui.label(" IQR ");
+                        ui.label(interquartile_range.str_value(row).unwrap_or_default());
+                    });
+                });
+            }
+            (row, RANGE) => {
+                let reference = self.data_frame["Reference"].struct_()?;
+                let min = reference.field_by_name("Min")?;
+                let max = reference.field_by_name("Max")?;
+                let format = |value: Option<f64>| match value {
+                    Some(value) => format!("{value:.*}", self.settings.precision),
+                    None => AnyValue::Null.to_string(),
+                };
+                ui.label(format!(
+                    "{} – {}",
+                    format(min.f64()?.get(row)),
+                    format(max.f64()?.get(row)),
+                ));
+            }
+            (row, DEVIATION) => {
+                statistics_ui(ui, self.data_frame, row, "Deviation", self.settings)?
+            }
+            (row, FLAGGED) => {
+                ui.label(self.data_frame["Flagged"].get(row)?.str_value());
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Out of range in the selected sample or in any sample
+    fn out(&self, row: usize) -> PolarsResult<bool> {
+        Ok(match self.settings.sample {
+            Some(sample) => self.data_frame["Out"]
+                .list()?
+                .get_as_series(row)
+                .and_then(|outs| outs.bool().ok()?.get(sample))
+                .unwrap_or_default(),
+            None => {
+                self.data_frame["Flagged"]
+                    .u32()?
+                    .get(row)
+                    .unwrap_or_default()
+                    > 0
+            }
+        })
+    }
+}
+
+impl TableDelegate for ReferenceView<'_> {
+    fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.header_cell_content_ui(ui, cell.row_nr, cell.group_index)
+            });
+    }
+
+    fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        if cell.row_nr % 2 == 0 {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
+        }
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.body_cell_content_ui(ui, cell.row_nr as _, cell.col_nr)
+                    .unwrap()
+            });
+    }
+}
+
+/// Reference plot view
+///
+/// The median ± IQR/2 boxes with the reference range whiskers and the
+/// sample values, out of range values in red.
+#[derive(Clone, Debug)]
+pub(crate) struct PlotView<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl<'a> PlotView<'a> {
+    pub(crate) const fn new(data_frame: &'a DataFrame, settings: &'a Settings) -> Self {
+        Self {
+            data_frame,
+            settings,
+        }
+    }
+}
+
+impl PlotView<'_> {
+    pub(super) fn ui(&mut self, ui: &mut Ui) {
+        if let Err(error) = self.try_ui(ui) {
+            error!(%error);
+        }
+    }
+
+    fn try_ui(&mut self, ui: &mut Ui) -> PolarsResult<()> {
+        let reference = self.data_frame["Reference"].struct_()?;
+        let median = reference.field_by_name("Median")?;
+        let interquartile_range = reference.field_by_name("InterquartileRange")?;
+        let min = reference.field_by_name("Min")?;
+        let max = reference.field_by_name("Max")?;
+        let values = self.data_frame["Value"]
+            .struct_()?
+            .field_by_name("Values")?;
+        let outs = self.data_frame["Out"].list()?;
+        let mut names = Vec::with_capacity(self.data_frame.height());
+        let mut boxes = Vec::with_capacity(self.data_frame.height());
+        let mut inside = Vec::new();
+        let mut outside = Vec::new();
+        for row in 0..self.data_frame.height() {
+            let x = row as f64;
+            let name = name(self.data_frame, row)?.unwrap_or_default();
+            if let Some(median) = median.f64()?.get(row) {
+                let half = interquartile_range.f64()?.get(row).unwrap_or_default() / 2.0;
+                let (lower, upper) = (median - half, median + half);
+                let spread = BoxSpread::new(
+                    min.f64()?.get(row).unwrap_or(lower).min(lower),
+                    lower,
+                    median,
+                    upper,
+                    max.f64()?.get(row).unwrap_or(upper).max(upper),
+                );
+                boxes.push(BoxElem::new(x, spread).name(&name).box_width(0.5));
+            }
+            if let Some((values, outs)) = values
+                .list()?
+                .get_as_series(row)
+                .zip(outs.get_as_series(row))
+            {
+                for (sample, (value, out)) in
+                    values.f64()?.into_iter().zip(outs.bool()?).enumerate()
+                {
+                    if self
+                        .settings
+                        .sample
+                        .is_some_and(|selected| selected != sample)
+                    {
+                        continue;
+                    }
+                    let Some(value) = value else {
+                        continue;
+                    };
+                    if out == Some(true) {
+                        outside.push([x, value]);
+                    } else {
+                        inside.push([x, value]);
+                    }
+                }
+            }
+            names.push(name);
+        }
+        Plot::new(ui.next_auto_id())
+            .legend(Default::default())
+            .x_axis_formatter(move |mark, _| {
+                let index = mark.value.round();
+                if (mark.value - index).abs() > f64::EPSILON || index < 0.0 {
+                    return String::new();
+                }
+                names.get(index as usize).cloned().unwrap_or_default()
+            })
+            .y_axis_label(format!("{}, %", self.settings.basis.text()))
+            .show(ui, |ui| {
+                ui.box_plot(BoxPlot::new(boxes).name(localize!("reference")));
+                ui.points(
+                    Points::new(inside)
+                        .name(localize!("samples"))
+                        .radius(3.0_f32),
+                );
+                ui.points(
+                    Points::new(outside)
+                        .name(localize!("out-of-range"))
+                        .color(Color32::from_rgb(200, 60, 60))
+                        .radius(3.0_f32),
+                );
+            });
+        Ok(())
+    }
+}
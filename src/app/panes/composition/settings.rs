@@ -0,0 +1,292 @@
+use crate::{
+    app::{MAX_PRECISION, localize, text::Text},
+    special::substituent::ColumnExt as _,
+};
+use egui::{ComboBox, DragValue, Grid, RichText, Slider, Ui, emath::Float};
+use egui_ext::LabeledSeparator;
+use lipid::fatty_acid::{
+    display::{COMMON, DisplayWithOptions as _},
+    polars::ColumnExt as _,
+};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+
+/// Settings
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Settings {
+    pub(crate) precision: usize,
+    pub(crate) resizable: bool,
+    pub(crate) sticky: usize,
+    pub(crate) truncate: bool,
+
+    pub(crate) kind: Kind,
+    pub(crate) sample: Option<usize>,
+
+    pub(crate) basis: Basis,
+    pub(crate) ddof: u8,
+    pub(crate) standard: Standard,
+}
+
+impl Settings {
+    pub(crate) const fn new() -> Self {
+        Self {
+            precision: 2,
+            resizable: false,
+            sticky: 1,
+            truncate: false,
+
+            kind: Kind::Composition,
+            sample: None,
+
+            basis: Basis::Mass,
+            ddof: 1,
+            standard: Standard::new(),
+        }
+    }
+
+    pub(crate) fn ui(&mut self, ui: &mut Ui, data_frame: &DataFrame) -> PolarsResult<()> {
+        Grid::new("composition")
+            .show(ui, |ui| -> PolarsResult<()> {
+                // Precision floats
+                ui.label(localize!("precision"));
+                ui.add(Slider::new(&mut self.precision, 0..=MAX_PRECISION));
+                ui.end_row();
+
+                // Sticky columns
+                ui.label(localize!("sticky"));
+                ui.add(Slider::new(&mut self.sticky, 0..=data_frame.width()));
+                ui.end_row();
+
+                // Truncate titles
+                ui.label(localize!("truncate"));
+                ui.checkbox(&mut self.truncate, "");
+                ui.end_row();
+
+                // Display
+                ui.separator();
+                ui.labeled_separator(RichText::new(localize!("display")).heading());
+                ui.end_row();
+
+                // Sample
+                ui.label(localize!("sample"));
+                let samples = samples(data_frame)?;
+                ComboBox::from_id_salt(ui.auto_id_with("Sample"))
+                    .selected_text(sample_text(self.sample))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.sample, None, sample_text(None));
+                        for sample in 0..samples {
+                            ui.selectable_value(
+                                &mut self.sample,
+                                Some(sample),
+                                sample_text(Some(sample)),
+                            );
+                        }
+                    });
+                ui.end_row();
+
+                // Calculate
+                ui.separator();
+                ui.labeled_separator(RichText::new(localize!("calculate")).heading());
+                ui.end_row();
+
+                // Basis of the indices
+                ui.label(localize!("basis"))
+                    .on_hover_text(localize!("basis-description"));
+                ComboBox::from_id_salt(ui.auto_id_with("Basis"))
+                    .selected_text(self.basis.text())
+                    .show_ui(ui, |ui| {
+                        for basis in [Basis::Area, Basis::Mass] {
+                            ui.selectable_value(&mut self.basis, basis, basis.text())
+                                .on_hover_text(basis.hover_text());
+                        }
+                    });
+                ui.end_row();
+
+                // DDOF
+                // https://numpy.org/devdocs/reference/generated/numpy.std.html
+                ui.label("DDOF");
+                ui.add(Slider::new(&mut self.ddof, 0..=2));
+                ui.end_row();
+
+                // Internal standard
+                ui.label(localize!("internal-standard"))
+                    .on_hover_text(localize!("internal-standard-description"));
+                let names = names(data_frame)?;
+                ComboBox::from_id_salt(ui.auto_id_with("InternalStandard"))
+                    .selected_text(self.standard.name.as_deref().unwrap_or_default())
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.standard.name, None, "");
+                        for name in names {
+                            ui.selectable_value(&mut self.standard.name, Some(name.clone()), name);
+                        }
+                    });
+                ui.end_row();
+
+                // Internal standard mass
+                ui.label(localize!("standard-mass"));
+                ui.add(
+                    DragValue::new(&mut self.standard.mass)
+                        .range(0.0..=f64::MAX)
+                        .speed(0.01)
+                        .suffix(" mg"),
+                );
+                ui.end_row();
+
+                // Sample mass
+                ui.label(localize!("sample-mass"));
+                ui.add(
+                    DragValue::new(&mut self.standard.sample)
+                        .range(0.0..=f64::MAX)
+                        .speed(0.01)
+                        .suffix(" g"),
+                );
+                ui.end_row();
+
+                // Free fatty acids
+                ui.label(localize!("free-fatty-acids"))
+                    .on_hover_text(localize!("free-fatty-acids-description"));
+                ui.checkbox(&mut self.standard.fatty_acids, "");
+                ui.end_row();
+                Ok(())
+            })
+            .inner
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Kind of the view
+#[derive(Clone, Copy, Debug, Default, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) enum Kind {
+    #[default]
+    Composition,
+    Indices,
+    Reference,
+    Plot,
+}
+
+/// Percents of the indices
+#[derive(Clone, Copy, Debug, Default, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) enum Basis {
+    Area,
+    #[default]
+    Mass,
+}
+
+impl Basis {
+    /// Column of the composition
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Area => "Area",
+            Self::Mass => "Mass",
+        }
+    }
+}
+
+impl Text for Basis {
+    fn text(&self) -> String {
+        match self {
+            Self::Area => localize!("area"),
+            Self::Mass => localize!("mass"),
+        }
+    }
+
+    fn hover_text(&self) -> String {
+        match self {
+            Self::Area => localize!("area-percent-description"),
+            Self::Mass => localize!("mass-percent-description"),
+        }
+    }
+}
+
+/// Internal standard quantitation
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Standard {
+    /// Fatty acid of the internal standard
+    pub(crate) name: Option<String>,
+    /// Added internal standard methyl ester, mg
+    pub(crate) mass: f64,
+    /// Sample, g
+    pub(crate) sample: f64,
+    /// Content of the free fatty acids instead of the methyl esters
+    pub(crate) fatty_acids: bool,
+}
+
+impl Standard {
+    pub(crate) const fn new() -> Self {
+        Self {
+            name: None,
+            mass: 1.0,
+            sample: 1.0,
+            fatty_acids: true,
+        }
+    }
+}
+
+impl Default for Standard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hash for Standard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.mass.ord().hash(state);
+        self.sample.ord().hash(state);
+        self.fatty_acids.hash(state);
+    }
+}
+
+/// Fatty acid name of the row, the key of the internal standard
+pub(crate) fn name(data_frame: &DataFrame, row: usize) -> PolarsResult<Option<String>> {
+    let Some(fatty_acid) = data_frame["FattyAcid"].fatty_acid().get(row)? else {
+        return Ok(None);
+    };
+    let substituents = data_frame["FattyAcid"].substituents(row)?;
+    Ok(Some(format!(
+        "{substituents}{:#}",
+        fatty_acid.display(COMMON)
+    )))
+}
+
+fn names(data_frame: &DataFrame) -> PolarsResult<Vec<String>> {
+    let mut names = Vec::new();
+    if data_frame.width() == 0 {
+        return Ok(names);
+    }
+    for row in 0..data_frame.height() {
+        if let Some(name) = name(data_frame, row)? {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Number of the samples
+fn samples(data_frame: &DataFrame) -> PolarsResult<usize> {
+    if data_frame.width() == 0 {
+        return Ok(0);
+    }
+    let values = data_frame["Area"].struct_()?.field_by_name("Values")?;
+    Ok(values
+        .list()?
+        .into_iter()
+        .map(|values| values.map_or(0, |values| values.len()))
+        .max()
+        .unwrap_or_default())
+}
+
+fn sample_text(sample: Option<usize>) -> String {
+    match sample {
+        Some(sample) => format!("{} {}", localize!("sample"), sample + 1),
+        None => localize!("mean"),
+    }
+}
@@ -0,0 +1,218 @@
+use super::Settings;
+use crate::{
+    app::{localize, panes::widgets::float::FloatValue},
+    special::{column::mode::ColumnExt as _, substituent::ColumnExt as _},
+};
+use egui::{Frame, Id, Margin, RichText, TextStyle, TextWrapMode, Ui, Vec2, vec2};
+use egui_table::{AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate};
+use lipid::fatty_acid::{
+    display::{COMMON, DisplayWithOptions as _},
+    polars::ColumnExt,
+};
+use polars::prelude::*;
+
+const INDEX: usize = 0;
+const MODE: usize = 1;
+const FA: usize = 2;
+const RESPONSE: usize = 3;
+const CONVERSION: usize = 4;
+const AREA: usize = 5;
+const MASS: usize = 6;
+const CONTENT: usize = 7;
+const LEN: usize = 8;
+
+const MARGIN: Vec2 = vec2(4.0, 0.0);
+
+/// Table view
+#[derive(Clone, Debug)]
+pub(crate) struct TableView<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl<'a> TableView<'a> {
+    pub(crate) const fn new(data_frame: &'a DataFrame, settings: &'a Settings) -> Self {
+        Self {
+            data_frame,
+            settings,
+        }
+    }
+}
+
+impl TableView<'_> {
+    pub(super) fn ui(&mut self, ui: &mut Ui) {
+        ui.visuals_mut().collapsing_header_frame = true;
+        let id_salt = Id::new("CompositionTable");
+        let height = ui.text_style_height(&TextStyle::Heading);
+        let num_rows = self.data_frame.height() as _;
+        Table::new()
+            .id_salt(id_salt)
+            .num_rows(num_rows)
+            .columns(vec![
+                Column::default().resizable(self.settings.resizable);
+                LEN
+            ])
+            .num_sticky_cols(self.settings.sticky)
+            .headers([HeaderRow::new(height)])
+            .auto_size_mode(AutoSizeMode::OnParentResize)
+            .show(ui, self);
+    }
+
+    fn header_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) {
+        if self.settings.truncate {
+            ui.style_mut().wrap_mode = Some(TextWrapMode::Truncate);
+        }
+        match (row, column) {
+            (0, INDEX) => {
+                ui.heading(localize!("index"));
+            }
+            (0, MODE) => {
+                ui.heading(localize!("mode"));
+            }
+            (0, FA) => {
+                ui.heading(localize!("fatty-acid"));
+            }
+            (0, RESPONSE) => {
+                ui.heading("RF")
+                    .on_hover_text(localize!("response-factor-description"));
+            }
+            (0, CONVERSION) => {
+                ui.heading("CF")
+                    .on_hover_text(localize!("conversion-factor-description"));
+            }
+            (0, AREA) => {
+                ui.heading(format!("{}, %", localize!("area")));
+            }
+            (0, MASS) => {
+                ui.heading(format!("{}, %", localize!("mass")))
+                    .on_hover_text(localize!("mass-percent-description"));
+            }
+            (0, CONTENT) => {
+                ui.heading(format!("{}, mg/g", localize!("content")));
+            }
+            _ => {}
+        }
+    }
+
+    fn body_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) -> PolarsResult<()> {
+        match (row, column) {
+            (row, INDEX) => {
+                let indices = self.data_frame["Index"].u32()?;
+                let value = indices.get(row).unwrap();
+                ui.label(value.to_string());
+            }
+            (row, MODE) => {
+                let program = self.data_frame["Mode"].mode().program(row)?;
+                ui.label(program.to_string());
+            }
+            (row, FA) => {
+                let fatty_acids = self.data_frame["FattyAcid"].fatty_acid();
+                if let Some(fatty_acid) = fatty_acids.get(row)? {
+                    let substituents = self.data_frame["FattyAcid"].substituents(row)?;
+                    let text = format!("{substituents}{:#}", fatty_acid.display(COMMON));
+                    if self.data_frame["InternalStandard"].bool()?.get(row) == Some(true) {
+                        ui.label(RichText::new(text).strong())
+                            .on_hover_text(localize!("internal-standard"));
+                    } else {
+                        ui.label(text);
+                    }
+                }
+            }
+            (row, RESPONSE) => {
+                let value = self.data_frame["ResponseFactor"].f64()?.get(row);
+                ui.add(
+                    FloatValue::new(value)
+                        .precision(Some(self.settings.precision))
+                        .hover(),
+                );
+            }
+            (row, CONVERSION) => {
+                let value = self.data_frame["ConversionFactor"].f64()?.get(row);
+                ui.add(
+                    FloatValue::new(value)
+                        .precision(Some(self.settings.precision))
+                        .hover(),
+                );
+            }
+            (row, AREA) => statistics_ui(ui, self.data_frame, row, "Area", self.settings)?,
+            (row, MASS) => statistics_ui(ui, self.data_frame, row, "Mass", self.settings)?,
+            (row, CONTENT) => statistics_ui(ui, self.data_frame, row, "Content", self.settings)?,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl TableDelegate for TableView<'_> {
+    fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.header_cell_content_ui(ui, cell.row_nr, cell.group_index)
+            });
+    }
+
+    fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        if cell.row_nr % 2 == 0 {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
+        }
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.body_cell_content_ui(ui, cell.row_nr as _, cell.col_nr)
+                    .unwrap()
+            });
+    }
+}
+
+/// Mean of the samples or the value of the selected sample
+pub(super) fn statistics_ui(
+    ui: &mut Ui,
+    data_frame: &DataFrame,
+    row: usize,
+    name: &str,
+    settings: &Settings,
+) -> PolarsResult<()> {
+    let statistics = data_frame[name].struct_()?;
+    let values: Vec<Option<f64>> = match statistics
+        .field_by_name("Values")?
+        .list()?
+        .get_as_series(row)
+    {
+        Some(values) => values.f64()?.into_iter().collect(),
+        None => Vec::new(),
+    };
+    if let Some(sample) = settings.sample {
+        let value = values.get(sample).copied().flatten();
+        ui.add(
+            FloatValue::new(value)
+                .precision(Some(settings.precision))
+                .hover(),
+        );
+        return Ok(());
+    }
+    let mean = statistics.field_by_name("Mean")?;
+    let standard_deviation = statistics.field_by_name("StandardDeviation")?;
+    ui.add(FloatValue::new(mean.f64()?.get(row)).precision(Some(settings.precision)))
+        .on_hover_ui(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            ui.horizontal(|ui| {
+                ui.label(mean.str_value(row).unwrap_or_default());
+                ui.label("±");
+                ui.label(standard_deviation.str_value(row).unwrap_or_default());
+            });
+        })
+        .on_hover_ui(|ui| {
+            ui.heading(localize!("samples"));
+            ui.vertical(|ui| {
+                for value in values {
+                    match value {
+                        Some(value) => ui.label(format!("{value:.*}", settings.precision)),
+                        None => ui.label(AnyValue::Null.to_string()),
+                    };
+                }
+            });
+        });
+    Ok(())
+}
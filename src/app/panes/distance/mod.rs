@@ -1,8 +1,12 @@
 use self::{control::Control, settings::Settings};
-use crate::app::{
-    computers::{DistanceComputed, DistanceKey},
-    data::{Format, save},
-    localize,
+use super::widgets::status::StatusWidget;
+use crate::{
+    app::{
+        computers::{DistanceComputed, DistanceKey, Status},
+        data::{Format, save},
+        localize,
+    },
+    utils::HashedDataFrame,
 };
 use egui::{RichText, Ui, Window};
 use egui_phosphor::regular::{ARROWS_HORIZONTAL, FLOPPY_DISK, GEAR};
@@ -14,16 +18,27 @@ use tracing::error;
 /// Distance pane
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub(crate) struct Pane {
-    pub(crate) source: DataFrame,
-    pub(crate) target: DataFrame,
+    pub(crate) source: HashedDataFrame,
+    #[serde(skip)]
+    pub(crate) target: HashedDataFrame,
+    #[serde(skip)]
+    status: Option<Status>,
     pub(crate) control: Control,
 }
 
+/// Computed target and the settings window state are not part of the history
+impl PartialEq for Pane {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source && self.control.settings == other.control.settings
+    }
+}
+
 impl Pane {
-    pub(crate) const fn new(data_frame: DataFrame) -> Self {
+    pub(crate) fn new(data_frame: DataFrame) -> Self {
         Self {
-            source: data_frame,
-            target: DataFrame::empty(),
+            source: HashedDataFrame::new(data_frame),
+            target: HashedDataFrame::default(),
+            status: None,
             control: Control::new(),
         }
     }
@@ -31,6 +46,7 @@ impl Pane {
     pub(super) fn header(&mut self, ui: &mut Ui) {
         ui.visuals_mut().button_frame = false;
         ui.separator();
+        ui.add(StatusWidget::new(self.status.as_ref()));
         ui.toggle_value(
             &mut self.control.settings.resizable,
             RichText::new(ARROWS_HORIZONTAL).heading(),
@@ -40,12 +56,12 @@ impl Pane {
         ui.separator();
         ui.menu_button(RichText::new(FLOPPY_DISK).heading(), |ui| {
             if ui.button("BIN").clicked() {
-                if let Err(error) = save("df.bin", Format::Bin, self.target.clone()) {
+                if let Err(error) = save("df.bin", Format::Bin, self.target.clone().into_inner()) {
                     error!(%error);
                 }
             }
             if ui.button("RON").clicked() {
-                if let Err(error) = save("df.ron", Format::Ron, self.target.clone()) {
+                if let Err(error) = save("df.ron", Format::Ron, self.target.clone().into_inner()) {
                     error!(%error);
                 }
             }
@@ -54,17 +70,37 @@ impl Pane {
 
     pub(super) fn content(&mut self, ui: &mut Ui) {
         self.window(ui);
-        self.target = ui.memory_mut(|memory| {
-            memory.caches.cache::<DistanceComputed>().get(DistanceKey {
-                data_frame: &self.source,
-                settings: &self.control.settings,
-            })
-        });
+        self.compute(ui);
+        // The last result stays on screen until the new one is ready
+        if self.target.width() == 0 {
+            ui.centered_and_justified(|ui| ui.spinner());
+            return;
+        }
         TableView::new(&self.target, &self.control.settings).ui(ui);
     }
 
+    fn compute(&mut self, ui: &mut Ui) {
+        let ctx = ui.ctx().clone();
+        let status = ui.memory_mut(|memory| {
+            memory.caches.cache::<DistanceComputed>().get(
+                &ctx,
+                DistanceKey {
+                    data_frame: &self.source,
+                    settings: &self.control.settings,
+                },
+            )
+        });
+        self.status = match status {
+            Status::Ready(data_frame) => {
+                self.target = data_frame;
+                None
+            }
+            status => Some(status),
+        };
+    }
+
     fn window(&mut self, ui: &mut Ui) {
-        Window::new(format!("{GEAR} Distance settings"))
+        Window::new(format!("{GEAR} {}", localize!("distance-settings")))
             .id(ui.next_auto_id())
             .open(&mut self.control.open)
             .show(ui.ctx(), |ui| {
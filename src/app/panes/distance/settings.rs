@@ -1,8 +1,8 @@
 use crate::{
-    app::{MAX_PRECISION, localize},
+    app::{MAX_PRECISION, localize, text::Text, units::Units},
     special::column::mode::ColumnExt as _,
 };
-use egui::{ComboBox, Grid, RichText, Slider, Ui, WidgetText, emath::Float};
+use egui::{ComboBox, Grid, RichText, Slider, Ui, emath::Float};
 use egui_ext::LabeledSeparator;
 use egui_phosphor::regular::TRASH;
 use lipid::fatty_acid::{
@@ -12,14 +12,7 @@ use lipid::fatty_acid::{
 };
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::{
-    fmt::{self, Display, Formatter},
-    hash::{Hash, Hasher},
-};
-use uom::si::{
-    f32::Time,
-    time::{Units, millisecond, minute, second},
-};
+use std::hash::{Hash, Hasher};
 
 /// Settings
 #[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
@@ -28,6 +21,7 @@ pub(crate) struct Settings {
     pub(crate) resizable: bool,
     pub(crate) sticky: usize,
     pub(crate) truncate: bool,
+    pub(crate) units: Units,
 
     pub(crate) sort: Sort,
     pub(crate) order: Order,
@@ -45,6 +39,7 @@ impl Settings {
             resizable: false,
             sticky: 1,
             truncate: false,
+            units: Units::new(),
             sort: Sort::Ecl,
             order: Order::Descending,
 
@@ -72,9 +67,15 @@ impl Settings {
             ui.checkbox(&mut self.truncate, "");
             ui.end_row();
 
+            // Units
+            ui.separator();
+            ui.labeled_separator(RichText::new(localize!("units")).heading());
+            ui.end_row();
+            self.units.ui(ui);
+
             // Filter
             ui.separator();
-            ui.labeled_separator(RichText::new("Filter").heading());
+            ui.labeled_separator(RichText::new(localize!("filter")).heading());
             ui.end_row();
 
             // ui.label("Interpolation");
@@ -92,7 +93,7 @@ impl Settings {
             ));
             ui.end_row();
 
-            ui.label("Filter");
+            ui.label(localize!("filter"));
             ui.horizontal(|ui| {
                 ComboBox::from_id_salt("FilterFattyAcids")
                     // .selected_text(self.sort.text())
@@ -127,20 +128,24 @@ impl Settings {
 
             // Sort
             ui.separator();
-            ui.labeled_separator(RichText::new("Sort").heading());
+            ui.labeled_separator(RichText::new(localize!("sort")).heading());
             ui.end_row();
 
-            ui.label("Sort");
+            ui.label(localize!("sort"));
             ComboBox::from_id_salt(ui.next_auto_id())
-                .selected_text(format!("{:?}", self.sort))
+                .selected_text(self.sort.text())
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.sort, Sort::Ecl, "ECL");
-                    ui.selectable_value(&mut self.sort, Sort::Time, "Time");
-                });
+                    ui.selectable_value(&mut self.sort, Sort::Ecl, Sort::Ecl.text())
+                        .on_hover_text(Sort::Ecl.hover_text());
+                    ui.selectable_value(&mut self.sort, Sort::Time, Sort::Time.text())
+                        .on_hover_text(Sort::Time.hover_text());
+                })
+                .response
+                .on_hover_text(self.sort.hover_text());
             ui.end_row();
 
             // Order
-            ui.label("Order");
+            ui.label(localize!("order"));
             ComboBox::from_id_salt(ui.next_auto_id())
                 .selected_text(self.order.text())
                 .show_ui(ui, |ui| {
@@ -219,146 +224,18 @@ pub(crate) enum Sort {
     Ecl,
 }
 
-/// Mass to charge settings
-#[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
-pub(crate) struct MassToCharge {
-    pub(crate) precision: usize,
-}
-
-impl Default for MassToCharge {
-    fn default() -> Self {
-        Self { precision: 1 }
-    }
-}
-
-impl MassToCharge {
-    pub(crate) fn format(self, value: f32) -> MassToChargeFormat {
-        MassToChargeFormat {
-            value,
-            precision: Some(self.precision),
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, Default)]
-pub(crate) struct MassToChargeFormat {
-    value: f32,
-    precision: Option<usize>,
-}
-
-impl MassToChargeFormat {
-    pub(crate) fn precision(self, precision: Option<usize>) -> Self {
-        Self { precision, ..self }
-    }
-}
-
-impl Display for MassToChargeFormat {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let value = self.value;
-        if let Some(precision) = self.precision {
-            write!(f, "{value:.precision$}")
-        } else {
-            write!(f, "{value}")
-        }
-    }
-}
-
-impl From<MassToChargeFormat> for WidgetText {
-    fn from(value: MassToChargeFormat) -> Self {
-        value.to_string().into()
-    }
-}
-
-/// Retention time settings
-#[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
-pub(crate) struct RetentionTime {
-    pub(crate) precision: usize,
-    pub(crate) units: TimeUnits,
-}
-
-impl RetentionTime {
-    pub(crate) fn format(self, value: f32) -> RetentionTimeFormat {
-        RetentionTimeFormat {
-            value,
-            precision: Some(self.precision),
-            units: self.units,
-        }
-    }
-}
-
-impl Default for RetentionTime {
-    fn default() -> Self {
-        Self {
-            precision: 2,
-            units: Default::default(),
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, Default)]
-pub(crate) struct RetentionTimeFormat {
-    value: f32,
-    precision: Option<usize>,
-    units: TimeUnits,
-}
-
-impl RetentionTimeFormat {
-    pub(crate) fn precision(self, precision: Option<usize>) -> Self {
-        Self { precision, ..self }
-    }
-}
-
-impl Display for RetentionTimeFormat {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let time = Time::new::<millisecond>(self.value as _);
-        let value = match self.units {
-            TimeUnits::Millisecond => time.get::<millisecond>(),
-            TimeUnits::Second => time.get::<second>(),
-            TimeUnits::Minute => time.get::<minute>(),
-        };
-        if let Some(precision) = self.precision {
-            write!(f, "{value:.precision$}")
-        } else {
-            write!(f, "{value}")
+impl Text for Sort {
+    fn text(&self) -> String {
+        match self {
+            Self::Time => localize!("sort-distance-time"),
+            Self::Ecl => localize!("sort-ecl"),
         }
     }
-}
-
-impl From<RetentionTimeFormat> for WidgetText {
-    fn from(value: RetentionTimeFormat) -> Self {
-        value.to_string().into()
-    }
-}
-
-/// Time units
-#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
-pub enum TimeUnits {
-    Millisecond,
-    #[default]
-    Second,
-    Minute,
-}
-
-impl TimeUnits {
-    pub fn abbreviation(&self) -> &'static str {
-        Units::from(*self).abbreviation()
-    }
-
-    pub fn singular(&self) -> &'static str {
-        Units::from(*self).singular()
-    }
 
-    pub fn plural(&self) -> &'static str {
-        Units::from(*self).plural()
-    }
-}
-
-impl From<TimeUnits> for Units {
-    fn from(value: TimeUnits) -> Self {
-        match value {
-            TimeUnits::Millisecond => Units::millisecond(millisecond),
-            TimeUnits::Second => Units::second(second),
-            TimeUnits::Minute => Units::minute(minute),
+    fn hover_text(&self) -> String {
+        match self {
+            Self::Time => localize!("sort-distance-time-description"),
+            Self::Ecl => localize!("sort-ecl-description"),
         }
     }
 }
@@ -370,18 +247,18 @@ pub(in crate::app) enum Order {
     Descending,
 }
 
-impl Order {
-    pub(in crate::app) fn text(self) -> &'static str {
+impl Text for Order {
+    fn text(&self) -> String {
         match self {
-            Self::Ascending => "Ascending",
-            Self::Descending => "Descending",
+            Self::Ascending => localize!("ascending"),
+            Self::Descending => localize!("descending"),
         }
     }
 
-    pub(in crate::app) fn hover_text(self) -> &'static str {
+    fn hover_text(&self) -> String {
         match self {
-            Self::Ascending => "Dscending",
-            Self::Descending => "Descending",
+            Self::Ascending => localize!("ascending-description"),
+            Self::Descending => localize!("descending-description"),
         }
     }
 }
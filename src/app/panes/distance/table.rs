@@ -1,5 +1,8 @@
 use super::Settings;
-use crate::app::panes::widgets::float::FloatValue;
+use crate::{
+    app::{localize, panes::widgets::float::FloatValue, units::RetentionTimeFormat},
+    special::{column::mode::ColumnExt as _, substituent::ColumnExt as _},
+};
 use egui::{Frame, Id, Margin, TextStyle, TextWrapMode, Ui, Vec2, vec2};
 use egui_table::{AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate};
 use lipid::fatty_acid::{
@@ -59,19 +62,23 @@ impl TableView<'_> {
         }
         match (row, column) {
             (0, INDEX) => {
-                ui.heading("Index");
+                ui.heading(localize!("index"));
             }
             (0, MODE) => {
-                ui.heading("Mode");
+                ui.heading(localize!("mode"));
             }
             (0, FROM) => {
-                ui.heading("From");
+                ui.heading(localize!("from"));
             }
             (0, TO) => {
-                ui.heading("To");
+                ui.heading(localize!("to"));
             }
             (0, TIME) => {
-                ui.heading("Δ Time");
+                ui.heading(format!(
+                    "Δ {}, {}",
+                    localize!("time"),
+                    self.settings.units.display.abbreviation()
+                ));
             }
             (0, ECL) => {
                 ui.heading("Δ ECL");
@@ -88,25 +95,21 @@ impl TableView<'_> {
                 ui.label(value.to_string());
             }
             (row, MODE) => {
-                let mode = self.data_frame["Mode"].struct_()?;
-                let onset_temperature = mode.field_by_name("OnsetTemperature")?;
-                let temperature_step = mode.field_by_name("TemperatureStep")?;
-                ui.label(format!(
-                    "{}/{}",
-                    onset_temperature.str_value(row)?,
-                    temperature_step.str_value(row)?
-                ));
+                let program = self.data_frame["Mode"].mode().program(row)?;
+                ui.label(program.to_string());
             }
             (row, FROM) => {
                 let fatty_acids = self.data_frame["From"].fatty_acid();
                 let fatty_acid = fatty_acids.get(row)?.unwrap();
-                ui.label(format!("{:#}", fatty_acid.display(COMMON)));
+                let substituents = self.data_frame["From"].substituents(row)?;
+                ui.label(format!("{substituents}{:#}", fatty_acid.display(COMMON)));
                 // .on_hover_text(fatty_acid.label());
             }
             (row, TO) => {
                 let fatty_acids = self.data_frame["To"].fatty_acid();
                 let fatty_acid = fatty_acids.get(row)?.unwrap();
-                ui.label(format!("{:#}", fatty_acid.display(COMMON)));
+                let substituents = self.data_frame["To"].substituents(row)?;
+                ui.label(format!("{substituents}{:#}", fatty_acid.display(COMMON)));
                 // .on_hover_text(fatty_acid.label());
             }
             (row, TIME) => {
@@ -118,12 +121,17 @@ impl TableView<'_> {
                         .hover(),
                 )
                 .on_hover_ui(|ui| {
+                    let units = self.settings.units.display;
                     let from = retention_time.field_by_name("From").unwrap();
                     let to = retention_time.field_by_name("To").unwrap();
                     ui.horizontal(|ui| {
-                        ui.label(to.str_value(row).unwrap());
+                        if let Some(to) = to.f64().unwrap().get(row) {
+                            ui.label(RetentionTimeFormat::new(to).units(units));
+                        }
                         ui.label("-");
-                        ui.label(from.str_value(row).unwrap());
+                        if let Some(from) = from.f64().unwrap().get(row) {
+                            ui.label(RetentionTimeFormat::new(from).units(units));
+                        }
                     });
                 });
             }
@@ -0,0 +1,135 @@
+use self::{control::Control, prediction::Prediction, settings::Settings};
+use super::widgets::status::StatusWidget;
+use crate::{
+    app::{
+        computers::{IncrementsComputed, IncrementsKey, Status},
+        data::{Format, save},
+        localize,
+    },
+    utils::HashedDataFrame,
+};
+use egui::{RichText, Ui, Window};
+use egui_phosphor::regular::{ARROWS_HORIZONTAL, FLOPPY_DISK, GEAR};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use table::TableView;
+use tracing::error;
+
+/// Increments pane
+///
+/// FCL increments of the bounds by position and geometry.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Pane {
+    pub(crate) source: HashedDataFrame,
+    #[serde(skip)]
+    pub(crate) target: HashedDataFrame,
+    #[serde(skip)]
+    status: Option<Status>,
+    pub(crate) control: Control,
+    #[serde(skip)]
+    prediction: Prediction,
+}
+
+/// Computed target and the settings window state are not part of the history
+impl PartialEq for Pane {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source && self.control.settings == other.control.settings
+    }
+}
+
+impl Pane {
+    pub(crate) fn new(data_frame: DataFrame) -> Self {
+        Self {
+            source: HashedDataFrame::new(data_frame),
+            target: HashedDataFrame::default(),
+            status: None,
+            control: Control::new(),
+            prediction: Prediction::new(),
+        }
+    }
+
+    pub(super) fn header(&mut self, ui: &mut Ui) {
+        ui.visuals_mut().button_frame = false;
+        ui.separator();
+        ui.add(StatusWidget::new(self.status.as_ref()));
+        ui.toggle_value(
+            &mut self.control.settings.resizable,
+            RichText::new(ARROWS_HORIZONTAL).heading(),
+        )
+        .on_hover_text(localize!("resize"));
+        ui.toggle_value(&mut self.control.open, RichText::new(GEAR).heading());
+        ui.separator();
+        ui.menu_button(RichText::new(FLOPPY_DISK).heading(), |ui| {
+            if ui.button("BIN").clicked() {
+                if let Err(error) = save(
+                    "increments.bin",
+                    Format::Bin,
+                    self.target.clone().into_inner(),
+                ) {
+                    error!(%error);
+                }
+            }
+            if ui.button("RON").clicked() {
+                if let Err(error) = save(
+                    "increments.ron",
+                    Format::Ron,
+                    self.target.clone().into_inner(),
+                ) {
+                    error!(%error);
+                }
+            }
+        });
+    }
+
+    pub(super) fn content(&mut self, ui: &mut Ui) {
+        self.window(ui);
+        self.compute(ui);
+        // The last result stays on screen until the new one is ready
+        if self.target.width() == 0 {
+            ui.centered_and_justified(|ui| ui.spinner());
+            return;
+        }
+        if let Err(error) = self
+            .prediction
+            .ui(ui, &self.target, self.control.settings.precision)
+        {
+            error!(%error);
+        }
+        TableView::new(&self.target, &self.control.settings).ui(ui);
+    }
+
+    fn compute(&mut self, ui: &mut Ui) {
+        let ctx = ui.ctx().clone();
+        let status = ui.memory_mut(|memory| {
+            memory.caches.cache::<IncrementsComputed>().get(
+                &ctx,
+                IncrementsKey {
+                    data_frame: &self.source,
+                    settings: &self.control.settings,
+                },
+            )
+        });
+        self.status = match status {
+            Status::Ready(data_frame) => {
+                self.target = data_frame;
+                None
+            }
+            status => Some(status),
+        };
+    }
+
+    fn window(&mut self, ui: &mut Ui) {
+        Window::new(format!("{GEAR} {}", localize!("increments-settings")))
+            .id(ui.next_auto_id())
+            .open(&mut self.control.open)
+            .show(ui.ctx(), |ui| {
+                self.control.settings.ui(ui, &self.target);
+            });
+    }
+}
+
+pub(crate) mod settings;
+
+mod control;
+mod prediction;
+mod table;
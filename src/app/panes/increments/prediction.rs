@@ -0,0 +1,126 @@
+use super::table::geometry;
+use crate::{app::localize, special::column::mode::ColumnExt as _};
+use egui::{Checkbox, CollapsingHeader, ComboBox, DragValue, Grid, Ui};
+use polars::prelude::*;
+use std::collections::BTreeSet;
+
+/// ECL prediction
+///
+/// The ECL of an isomer without a standard is the number of carbons plus the
+/// increments of its bounds.
+#[derive(Clone, Debug)]
+pub(super) struct Prediction {
+    mode: String,
+    carbons: u8,
+    bounds: BTreeSet<u32>,
+}
+
+impl Prediction {
+    pub(super) const fn new() -> Self {
+        Self {
+            mode: String::new(),
+            carbons: 18,
+            bounds: BTreeSet::new(),
+        }
+    }
+
+    pub(super) fn ui(
+        &mut self,
+        ui: &mut Ui,
+        data_frame: &DataFrame,
+        precision: usize,
+    ) -> PolarsResult<()> {
+        let modes = data_frame["Mode"].mode();
+        let mut programs = Vec::with_capacity(data_frame.height());
+        for row in 0..data_frame.height() {
+            programs.push(modes.program(row)?.to_string());
+        }
+        if !programs.contains(&self.mode) {
+            self.mode = programs.first().cloned().unwrap_or_default();
+            self.bounds.clear();
+        }
+        let indices = data_frame["Index"].u32()?;
+        let positions = data_frame["Position"].u8()?;
+        let bounds = data_frame["Bound"].i8()?;
+        let increments = data_frame["Increment"].f64()?;
+        CollapsingHeader::new(localize!("predict-ecl"))
+            .id_salt(ui.next_auto_id())
+            .show(ui, |ui| {
+                Grid::new(ui.next_auto_id()).show(ui, |ui| {
+                    ui.label(localize!("mode"));
+                    ComboBox::from_id_salt(ui.next_auto_id())
+                        .selected_text(&self.mode)
+                        .show_ui(ui, |ui| {
+                            let mut unique = Vec::new();
+                            for program in &programs {
+                                if !unique.contains(&program) {
+                                    unique.push(program);
+                                }
+                            }
+                            for program in unique {
+                                if ui
+                                    .selectable_label(&self.mode == program, program)
+                                    .clicked()
+                                {
+                                    self.mode = program.clone();
+                                    self.bounds.clear();
+                                }
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(localize!("carbons"));
+                    ui.add(DragValue::new(&mut self.carbons).range(1..=u8::MAX));
+                    ui.end_row();
+
+                    ui.label(localize!("bounds"));
+                    ui.vertical(|ui| {
+                        for (row, program) in programs.iter().enumerate() {
+                            if program != &self.mode {
+                                continue;
+                            }
+                            let (Some(index), Some(position), Some(bound)) =
+                                (indices.get(row), positions.get(row), bounds.get(row))
+                            else {
+                                continue;
+                            };
+                            let mut checked = self.bounds.contains(&index);
+                            ui.add_enabled(
+                                increments.get(row).is_some(),
+                                Checkbox::new(
+                                    &mut checked,
+                                    format!("{position} {}", geometry(bound)),
+                                ),
+                            );
+                            if checked {
+                                self.bounds.insert(index);
+                            } else {
+                                self.bounds.remove(&index);
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("ECL");
+                    let mut ecl = self.carbons as f64;
+                    for row in 0..data_frame.height() {
+                        if let Some(index) = indices.get(row) {
+                            if self.bounds.contains(&index) {
+                                ecl += increments.get(row).unwrap_or_default();
+                            }
+                        }
+                    }
+                    ui.label(format!("{ecl:.precision$}"))
+                        .on_hover_text(ecl.to_string());
+                    ui.end_row();
+                });
+            });
+        Ok(())
+    }
+}
+
+impl Default for Prediction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,64 @@
+use crate::app::{MAX_PRECISION, localize};
+use egui::{Grid, RichText, Slider, Ui};
+use egui_ext::LabeledSeparator;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Settings
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Settings {
+    pub(crate) precision: usize,
+    pub(crate) resizable: bool,
+    pub(crate) sticky: usize,
+    pub(crate) truncate: bool,
+
+    pub(crate) logarithmic: bool,
+}
+
+impl Settings {
+    pub(crate) const fn new() -> Self {
+        Self {
+            precision: 3,
+            resizable: false,
+            sticky: 1,
+            truncate: false,
+
+            logarithmic: false,
+        }
+    }
+
+    pub(crate) fn ui(&mut self, ui: &mut Ui, data_frame: &DataFrame) {
+        Grid::new("increments").show(ui, |ui| {
+            // Precision floats
+            ui.label(localize!("precision"));
+            ui.add(Slider::new(&mut self.precision, 0..=MAX_PRECISION));
+            ui.end_row();
+
+            // Sticky columns
+            ui.label(localize!("sticky"));
+            ui.add(Slider::new(&mut self.sticky, 0..=data_frame.width()));
+            ui.end_row();
+
+            // Truncate titles
+            ui.label(localize!("truncate"));
+            ui.checkbox(&mut self.truncate, "");
+            ui.end_row();
+
+            // Calculate
+            ui.separator();
+            ui.labeled_separator(RichText::new(localize!("calculate")).heading());
+            ui.end_row();
+
+            // Logarithmic
+            ui.label(localize!("logarithmic"));
+            ui.checkbox(&mut self.logarithmic, "");
+            ui.end_row();
+        });
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
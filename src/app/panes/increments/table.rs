@@ -0,0 +1,167 @@
+use super::Settings;
+use crate::{
+    app::{localize, panes::widgets::float::FloatValue},
+    special::column::mode::ColumnExt as _,
+};
+use egui::{Frame, Id, Margin, TextStyle, TextWrapMode, Ui, Vec2, vec2};
+use egui_table::{AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate};
+use polars::prelude::*;
+
+const INDEX: usize = 0;
+const MODE: usize = 1;
+const POSITION: usize = 2;
+const GEOMETRY: usize = 3;
+const COUNT: usize = 4;
+const INCREMENT: usize = 5;
+const STANDARD_ERROR: usize = 6;
+const LEN: usize = 7;
+
+const MARGIN: Vec2 = vec2(4.0, 0.0);
+
+/// Table view
+#[derive(Clone, Debug)]
+pub(crate) struct TableView<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl<'a> TableView<'a> {
+    pub(crate) const fn new(data_frame: &'a DataFrame, settings: &'a Settings) -> Self {
+        Self {
+            data_frame,
+            settings,
+        }
+    }
+}
+
+impl TableView<'_> {
+    pub(super) fn ui(&mut self, ui: &mut Ui) {
+        ui.visuals_mut().collapsing_header_frame = true;
+        let id_salt = Id::new("IncrementsTable");
+        let height = ui.text_style_height(&TextStyle::Heading);
+        let num_rows = self.data_frame.height() as _;
+        Table::new()
+            .id_salt(id_salt)
+            .num_rows(num_rows)
+            .columns(vec![
+                Column::default().resizable(self.settings.resizable);
+                LEN
+            ])
+            .num_sticky_cols(self.settings.sticky)
+            .headers([HeaderRow::new(height)])
+            .auto_size_mode(AutoSizeMode::OnParentResize)
+            .show(ui, self);
+    }
+
+    fn header_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) {
+        if self.settings.truncate {
+            ui.style_mut().wrap_mode = Some(TextWrapMode::Truncate);
+        }
+        match (row, column) {
+            (0, INDEX) => {
+                ui.heading(localize!("index"));
+            }
+            (0, MODE) => {
+                ui.heading(localize!("mode"));
+            }
+            (0, POSITION) => {
+                ui.heading(localize!("position"))
+                    .on_hover_text(localize!("position-description"));
+            }
+            (0, GEOMETRY) => {
+                ui.heading(localize!("geometry"));
+            }
+            (0, COUNT) => {
+                ui.heading(localize!("count"))
+                    .on_hover_text(localize!("bound-count-description"));
+            }
+            (0, INCREMENT) => {
+                ui.heading(localize!("increment"))
+                    .on_hover_text(localize!("increment-description"));
+            }
+            (0, STANDARD_ERROR) => {
+                ui.heading(localize!("standard-error"));
+            }
+            _ => {}
+        }
+    }
+
+    fn body_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) -> PolarsResult<()> {
+        match (row, column) {
+            (row, INDEX) => {
+                let indices = self.data_frame["Index"].u32()?;
+                let value = indices.get(row).unwrap();
+                ui.label(value.to_string());
+            }
+            (row, MODE) => {
+                let program = self.data_frame["Mode"].mode().program(row)?;
+                ui.label(program.to_string());
+            }
+            (row, POSITION) => {
+                ui.label(self.data_frame["Position"].get(row)?.str_value());
+            }
+            (row, GEOMETRY) => {
+                let bound = self.data_frame["Bound"].i8()?.get(row);
+                ui.label(bound.map_or("", geometry))
+                    .on_hover_text(self.data_frame["Bound"].get(row)?.str_value());
+            }
+            (row, COUNT) => {
+                ui.label(self.data_frame["Count"].get(row)?.str_value());
+            }
+            (row, INCREMENT) => {
+                let increment = self.data_frame["Increment"].f64()?.get(row);
+                let response = ui.add(
+                    FloatValue::new(increment)
+                        .precision(Some(self.settings.precision))
+                        .hover(),
+                );
+                if increment.is_none() {
+                    response.on_hover_text(localize!("not-estimable"));
+                }
+            }
+            (row, STANDARD_ERROR) => {
+                let standard_error = self.data_frame["StandardError"].f64()?.get(row);
+                ui.add(
+                    FloatValue::new(standard_error)
+                        .precision(Some(self.settings.precision))
+                        .hover(),
+                );
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl TableDelegate for TableView<'_> {
+    fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.header_cell_content_ui(ui, cell.row_nr, cell.group_index)
+            });
+    }
+
+    fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        if cell.row_nr % 2 == 0 {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
+        }
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.body_cell_content_ui(ui, cell.row_nr as _, cell.col_nr)
+                    .unwrap()
+            });
+    }
+}
+
+/// Geometry by the sign of the bound
+pub(super) fn geometry(bound: i8) -> &'static str {
+    match bound {
+        2 => "cis",
+        -2 => "trans",
+        3 | -3 => "triple",
+        _ => "",
+    }
+}
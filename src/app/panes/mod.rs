@@ -1,37 +1,68 @@
-pub(crate) use self::{distance::Pane as DistancePane, source::Pane as SourcePane};
+pub(crate) use self::{
+    compare::Pane as ComparePane,
+    composition::Pane as CompositionPane,
+    distance::Pane as DistancePane,
+    increments::Pane as IncrementsPane,
+    reproducibility::Pane as ReproducibilityPane,
+    source::{Origin, Pane as SourcePane},
+    spectrum::Pane as SpectrumPane,
+};
 
+use crate::app::localize;
 use egui::Ui;
-use egui_phosphor::regular::{CHART_BAR, TABLE};
 use polars::frame::DataFrame;
 use serde::{Deserialize, Serialize};
 
 /// Pane
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub(crate) enum Pane {
     Source(SourcePane),
     Distance(DistancePane),
+    Compare(ComparePane),
+    Reproducibility(ReproducibilityPane),
+    Increments(IncrementsPane),
+    Spectrum(SpectrumPane),
+    Composition(CompositionPane),
 }
 
 impl Pane {
-    pub(crate) fn source(data_frame: DataFrame) -> Self {
-        Self::Source(SourcePane::new(data_frame))
+    pub(crate) fn source(data_frame: DataFrame, origin: Origin) -> Self {
+        Self::Source(SourcePane::new(data_frame, origin))
     }
 
     pub(crate) fn distance(data_frame: DataFrame) -> Self {
         Self::Distance(DistancePane::new(data_frame))
     }
 
-    pub(crate) const fn icon(&self) -> &str {
-        match self {
-            Self::Source(_) => CHART_BAR,
-            Self::Distance(_) => TABLE,
-        }
+    pub(crate) fn compare(names: [String; 2], left: DataFrame, right: DataFrame) -> Self {
+        Self::Compare(ComparePane::new(names, left, right))
+    }
+
+    pub(crate) fn reproducibility(data_frame: DataFrame) -> Self {
+        Self::Reproducibility(ReproducibilityPane::new(data_frame))
+    }
+
+    pub(crate) fn increments(data_frame: DataFrame) -> Self {
+        Self::Increments(IncrementsPane::new(data_frame))
+    }
+
+    pub(crate) fn spectrum(source: DataFrame, spectra: DataFrame) -> Self {
+        Self::Spectrum(SpectrumPane::new(source, spectra))
+    }
+
+    pub(crate) fn composition(data_frame: DataFrame) -> Self {
+        Self::Composition(CompositionPane::new(data_frame))
     }
 
-    pub(crate) const fn title(&self) -> &'static str {
+    pub(crate) fn title(&self) -> String {
         match self {
-            Self::Source(_) => "Source",
-            Self::Distance(_) => "Distance",
+            Self::Source(_) => localize!("source"),
+            Self::Distance(_) => localize!("distance"),
+            Self::Compare(_) => localize!("compare"),
+            Self::Reproducibility(_) => localize!("reproducibility"),
+            Self::Increments(_) => localize!("increments"),
+            Self::Spectrum(_) => localize!("spectrum"),
+            Self::Composition(_) => localize!("composition"),
         }
     }
 }
@@ -41,6 +72,11 @@ impl Pane {
         match self {
             Self::Source(pane) => pane.header(ui),
             Self::Distance(pane) => pane.header(ui),
+            Self::Compare(pane) => pane.header(ui),
+            Self::Reproducibility(pane) => pane.header(ui),
+            Self::Increments(pane) => pane.header(ui),
+            Self::Spectrum(pane) => pane.header(ui),
+            Self::Composition(pane) => pane.header(ui),
         }
     }
 
@@ -48,11 +84,21 @@ impl Pane {
         match self {
             Self::Source(pane) => pane.content(ui),
             Self::Distance(pane) => pane.content(ui),
+            Self::Compare(pane) => pane.content(ui),
+            Self::Reproducibility(pane) => pane.content(ui),
+            Self::Increments(pane) => pane.content(ui),
+            Self::Spectrum(pane) => pane.content(ui),
+            Self::Composition(pane) => pane.content(ui),
         }
     }
 }
 
 pub(crate) mod behavior;
+pub(crate) mod compare;
+pub(crate) mod composition;
 pub(crate) mod distance;
+pub(crate) mod increments;
+pub(crate) mod reproducibility;
 pub(crate) mod source;
+pub(crate) mod spectrum;
 pub(crate) mod widgets;
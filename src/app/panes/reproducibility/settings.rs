@@ -0,0 +1,81 @@
+use crate::app::{MAX_PRECISION, localize, units::Units};
+use egui::{Grid, RichText, Slider, Ui};
+use egui_ext::LabeledSeparator;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Settings
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Settings {
+    pub(crate) precision: usize,
+    pub(crate) resizable: bool,
+    pub(crate) sticky: usize,
+    pub(crate) truncate: bool,
+    pub(crate) units: Units,
+
+    pub(crate) relative: bool,
+    pub(crate) significance: f64,
+}
+
+impl Settings {
+    pub(crate) const fn new() -> Self {
+        Self {
+            precision: 3,
+            resizable: false,
+            sticky: 1,
+            truncate: false,
+            units: Units::new(),
+
+            relative: false,
+            significance: 0.05,
+        }
+    }
+
+    pub(crate) fn ui(&mut self, ui: &mut Ui, data_frame: &DataFrame) {
+        Grid::new("reproducibility").show(ui, |ui| {
+            // Precision floats
+            ui.label(localize!("precision"));
+            ui.add(Slider::new(&mut self.precision, 0..=MAX_PRECISION));
+            ui.end_row();
+
+            // Sticky columns
+            ui.label(localize!("sticky"));
+            ui.add(Slider::new(&mut self.sticky, 0..=data_frame.width()));
+            ui.end_row();
+
+            // Truncate titles
+            ui.label(localize!("truncate"));
+            ui.checkbox(&mut self.truncate, "");
+            ui.end_row();
+
+            // Units
+            ui.separator();
+            ui.labeled_separator(RichText::new(localize!("units")).heading());
+            ui.end_row();
+            self.units.ui(ui);
+
+            // Display
+            ui.separator();
+            ui.labeled_separator(RichText::new(localize!("display")).heading());
+            ui.end_row();
+
+            // Relative standard deviation
+            ui.label(localize!("relative"))
+                .on_hover_text(localize!("relative-description"));
+            ui.checkbox(&mut self.relative, "");
+            ui.end_row();
+
+            // Significance level
+            ui.label(localize!("significance"))
+                .on_hover_text(localize!("batch-significance-description"));
+            ui.add(Slider::new(&mut self.significance, 0.001..=0.1).logarithmic(true));
+            ui.end_row();
+        });
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,197 @@
+use super::Settings;
+use crate::{
+    app::{localize, panes::widgets::float::FloatValue},
+    special::{column::mode::ColumnExt as _, substituent::ColumnExt as _},
+};
+use egui::{Frame, Id, Margin, RichText, TextStyle, TextWrapMode, Ui, Vec2, vec2};
+use egui_table::{AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate};
+use lipid::fatty_acid::{
+    display::{COMMON, DisplayWithOptions as _},
+    polars::ColumnExt,
+};
+use polars::prelude::*;
+
+const INDEX: usize = 0;
+const MODE: usize = 1;
+const FA: usize = 2;
+const BATCHES: usize = 3;
+const COUNT: usize = 4;
+const MEAN: usize = 5;
+const REPEATABILITY: usize = 6;
+const BATCH: usize = 7;
+const REPRODUCIBILITY: usize = 8;
+const P: usize = 9;
+const LEN: usize = 10;
+
+const MARGIN: Vec2 = vec2(4.0, 0.0);
+
+/// Table view
+#[derive(Clone, Debug)]
+pub(crate) struct TableView<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) settings: &'a Settings,
+}
+
+impl<'a> TableView<'a> {
+    pub(crate) const fn new(data_frame: &'a DataFrame, settings: &'a Settings) -> Self {
+        Self {
+            data_frame,
+            settings,
+        }
+    }
+}
+
+impl TableView<'_> {
+    pub(super) fn ui(&mut self, ui: &mut Ui) {
+        ui.visuals_mut().collapsing_header_frame = true;
+        let id_salt = Id::new("ReproducibilityTable");
+        let height = ui.text_style_height(&TextStyle::Heading);
+        let num_rows = self.data_frame.height() as _;
+        Table::new()
+            .id_salt(id_salt)
+            .num_rows(num_rows)
+            .columns(vec![
+                Column::default().resizable(self.settings.resizable);
+                LEN
+            ])
+            .num_sticky_cols(self.settings.sticky)
+            .headers([HeaderRow::new(height)])
+            .auto_size_mode(AutoSizeMode::OnParentResize)
+            .show(ui, self);
+    }
+
+    fn header_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) {
+        if self.settings.truncate {
+            ui.style_mut().wrap_mode = Some(TextWrapMode::Truncate);
+        }
+        let units = if self.settings.relative {
+            "%"
+        } else {
+            self.settings.units.display.abbreviation()
+        };
+        match (row, column) {
+            (0, INDEX) => {
+                ui.heading(localize!("index"));
+            }
+            (0, MODE) => {
+                ui.heading(localize!("mode"));
+            }
+            (0, FA) => {
+                ui.heading(localize!("fatty-acid"));
+            }
+            (0, BATCHES) => {
+                ui.heading(localize!("batches"));
+            }
+            (0, COUNT) => {
+                ui.heading(localize!("count"));
+            }
+            (0, MEAN) => {
+                ui.heading(format!(
+                    "{}, {}",
+                    localize!("mean"),
+                    self.settings.units.display.abbreviation()
+                ));
+            }
+            (0, REPEATABILITY) => {
+                ui.heading(format!("sr, {units}"))
+                    .on_hover_text(localize!("repeatability-description"));
+            }
+            (0, BATCH) => {
+                ui.heading(format!("sL, {units}"))
+                    .on_hover_text(localize!("between-batches-description"));
+            }
+            (0, REPRODUCIBILITY) => {
+                ui.heading(format!("sR, {units}"))
+                    .on_hover_text(localize!("reproducibility-deviation-description"));
+            }
+            (0, P) => {
+                ui.heading("p")
+                    .on_hover_text(localize!("batch-p-value-description"));
+            }
+            _ => {}
+        }
+    }
+
+    fn body_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) -> PolarsResult<()> {
+        match (row, column) {
+            (row, INDEX) => {
+                let indices = self.data_frame["Index"].u32()?;
+                let value = indices.get(row).unwrap();
+                ui.label(value.to_string());
+            }
+            (row, MODE) => {
+                let program = self.data_frame["Mode"].mode().program(row)?;
+                ui.label(program.to_string());
+            }
+            (row, FA) => {
+                let fatty_acids = self.data_frame["FattyAcid"].fatty_acid();
+                if let Some(fatty_acid) = fatty_acids.get(row)? {
+                    let substituents = self.data_frame["FattyAcid"].substituents(row)?;
+                    ui.label(format!("{substituents}{:#}", fatty_acid.display(COMMON)));
+                }
+            }
+            (row, BATCHES) => {
+                ui.label(self.data_frame["Batches"].get(row)?.str_value());
+            }
+            (row, COUNT) => {
+                ui.label(self.data_frame["Count"].get(row)?.str_value());
+            }
+            (row, MEAN) => {
+                let mean = self.data_frame["Mean"].f64()?.get(row);
+                ui.add(FloatValue::new(mean).precision(Some(self.settings.precision)));
+            }
+            (row, REPEATABILITY) => self.standard_deviation_ui(ui, row, "Repeatability")?,
+            (row, BATCH) => self.standard_deviation_ui(ui, row, "Batch")?,
+            (row, REPRODUCIBILITY) => self.standard_deviation_ui(ui, row, "Reproducibility")?,
+            (row, P) => {
+                let p_value = self.data_frame["P"].f64()?.get(row);
+                let text = match p_value {
+                    Some(p_value) => format!("{p_value:.*}", self.settings.precision),
+                    None => AnyValue::Null.to_string(),
+                };
+                let mut text = RichText::new(text);
+                if p_value.is_some_and(|p_value| p_value < self.settings.significance) {
+                    text = text.strong();
+                }
+                ui.label(text)
+                    .on_hover_text(p_value.map_or_else(String::new, |p_value| p_value.to_string()));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Absolute or relative to the mean standard deviation
+    fn standard_deviation_ui(&self, ui: &mut Ui, row: usize, name: &str) -> PolarsResult<()> {
+        let mut value = self.data_frame[name].f64()?.get(row);
+        if self.settings.relative {
+            let mean = self.data_frame["Mean"].f64()?.get(row);
+            value = value.zip(mean).map(|(value, mean)| value / mean * 100.0);
+        }
+        ui.add(FloatValue::new(value).precision(Some(self.settings.precision)));
+        Ok(())
+    }
+}
+
+impl TableDelegate for TableView<'_> {
+    fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.header_cell_content_ui(ui, cell.row_nr, cell.group_index)
+            });
+    }
+
+    fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        if cell.row_nr % 2 == 0 {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
+        }
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.body_cell_content_ui(ui, cell.row_nr as _, cell.col_nr)
+                    .unwrap()
+            });
+    }
+}
@@ -0,0 +1,352 @@
+use polars::prelude::*;
+
+/// Edit of the source data
+///
+/// Rows refer to the source data frame. The batch and replicate tags of the
+/// retention times are kept in step with the values.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Edit {
+    /// Replace the fatty acid of `row`
+    FattyAcid { row: usize, fatty_acid: Identity },
+    /// Set the replicate value at `index`
+    Value {
+        row: usize,
+        index: usize,
+        value: f64,
+    },
+    /// Push a new replicate value
+    PushValue { row: usize, value: f64 },
+    /// Remove the replicate value at `index`
+    RemoveValue { row: usize, index: usize },
+    /// Add a row with the mode of `mode` and the fatty acid
+    AddRow { mode: usize, fatty_acid: Identity },
+    /// Delete the row
+    DeleteRow { row: usize },
+}
+
+impl Edit {
+    pub(crate) fn apply(self, data_frame: &mut DataFrame) -> PolarsResult<()> {
+        match self {
+            Self::FattyAcid { row, fatty_acid } => {
+                let value = fatty_acid.column(data_frame)?;
+                replace(data_frame, "FattyAcid", row, &value)?;
+            }
+            Self::Value { row, index, value } => {
+                let mut values = values(data_frame, row)?;
+                if index < values.len() {
+                    values[index] = Some(value);
+                }
+                replace_list(
+                    data_frame,
+                    "RetentionTime",
+                    row,
+                    Series::new(PlSmallStr::EMPTY, values),
+                )?;
+            }
+            Self::PushValue { row, value } => {
+                // The first replicate missing from the row
+                let replicate = match data_frame.column("Replicate") {
+                    Ok(_) => {
+                        let replicates =
+                            list(data_frame, "Replicate", row)?.cast(&DataType::UInt32)?;
+                        let replicates: Vec<u32> =
+                            replicates.u32()?.into_iter().flatten().collect();
+                        (0..).find(|index| !replicates.contains(index))
+                    }
+                    Err(_) => None,
+                };
+                if data_frame.column("Batch").is_ok() {
+                    let batch = match replicate {
+                        Some(replicate) => batch(data_frame, replicate)?,
+                        None => None,
+                    };
+                    push(
+                        data_frame,
+                        "Batch",
+                        row,
+                        Series::new(PlSmallStr::EMPTY, [batch]),
+                    )?;
+                }
+                if let Some(replicate) = replicate {
+                    push(
+                        data_frame,
+                        "Replicate",
+                        row,
+                        Series::new(PlSmallStr::EMPTY, [replicate]),
+                    )?;
+                }
+                push(
+                    data_frame,
+                    "RetentionTime",
+                    row,
+                    Series::new(PlSmallStr::EMPTY, [value]),
+                )?;
+            }
+            Self::RemoveValue { row, index } => {
+                for name in lists(data_frame) {
+                    let values = list(data_frame, name, row)?;
+                    if index < values.len() {
+                        let mut head = values.slice(0, index);
+                        head.append(&values.slice(index as i64 + 1, usize::MAX))?;
+                        replace_list(data_frame, name, row, head)?;
+                    }
+                }
+            }
+            Self::AddRow { mode, fatty_acid } => {
+                let mut new = data_frame.slice(mode as _, 1);
+                let value = fatty_acid.column(data_frame)?;
+                replace(&mut new, "FattyAcid", 0, &value)?;
+                for name in lists(data_frame) {
+                    replace_list(&mut new, name, 0, Series::new_null(PlSmallStr::EMPTY, 0))?;
+                }
+                data_frame.vstack_mut(&new)?;
+                data_frame.align_chunks_par();
+            }
+            Self::DeleteRow { row } => {
+                let mask: BooleanChunked =
+                    (0..data_frame.height()).map(|index| index != row).collect();
+                *data_frame = data_frame.filter(&mask)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fatty acid of an edit
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Identity {
+    /// The fatty acid of the source row
+    Row(usize),
+    /// The fatty acid entered by the user
+    Custom(Custom),
+}
+
+impl Identity {
+    fn column(&self, data_frame: &DataFrame) -> PolarsResult<Column> {
+        match self {
+            Self::Row(row) => Ok(data_frame["FattyAcid"].slice(*row as _, 1)),
+            Self::Custom(custom) => custom.column(data_frame["FattyAcid"].dtype()),
+        }
+    }
+}
+
+impl Default for Identity {
+    fn default() -> Self {
+        Self::Row(0)
+    }
+}
+
+/// Fatty acid entered by the user
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Custom {
+    pub(crate) carbons: u8,
+    /// Unsaturated bounds from the carboxyl carbon: `9c,12c`, `9t`
+    pub(crate) bounds: String,
+    /// Name, the substituents are read from it
+    pub(crate) label: String,
+}
+
+impl Custom {
+    pub(crate) const fn new() -> Self {
+        Self {
+            carbons: 18,
+            bounds: String::new(),
+            label: String::new(),
+        }
+    }
+
+    /// Positions and types of the unsaturated bounds
+    ///
+    /// `c` and `Z` are cis, `t` and `E` are trans double bounds, a bare
+    /// position is cis. `None` if a bound is malformed, repeated or out of
+    /// the chain.
+    pub(crate) fn bounds(&self) -> Option<Vec<(u8, i8)>> {
+        let mut bounds = Vec::new();
+        for token in self.bounds.split(',').map(str::trim) {
+            if token.is_empty() {
+                continue;
+            }
+            let position = token.trim_end_matches(char::is_alphabetic);
+            let bound = match &token[position.len()..] {
+                "" | "c" | "Z" => 2,
+                "t" | "E" => -2,
+                _ => return None,
+            };
+            let index = position
+                .parse()
+                .ok()
+                .filter(|&index| 0 < index && index < self.carbons)?;
+            bounds.push((index, bound));
+        }
+        bounds.sort_unstable();
+        if bounds.windows(2).any(|bounds| bounds[0].0 == bounds[1].0) {
+            return None;
+        }
+        Some(bounds)
+    }
+
+    pub(crate) fn is_valid(&self) -> bool {
+        self.carbons > 0 && self.bounds().is_some()
+    }
+
+    fn column(&self, data_type: &DataType) -> PolarsResult<Column> {
+        let bounds = self
+            .bounds()
+            .ok_or_else(|| polars_err!(ComputeError: "invalid bounds: {}", self.bounds))?;
+        let (indices, bounds): (Vec<u8>, Vec<i8>) = bounds.into_iter().unzip();
+        let fatty_acid = DataFrame::new(vec![
+            Column::new("Carbons".into(), [self.carbons]),
+            Column::new(
+                "Indices".into(),
+                [Series::from_vec(PlSmallStr::EMPTY, indices)],
+            ),
+            Column::new(
+                "Bounds".into(),
+                [Series::from_vec(PlSmallStr::EMPTY, bounds)],
+            ),
+            Column::new("Label".into(), [self.label.as_str()]),
+        ])?
+        .into_struct("FattyAcid".into());
+        Ok(fatty_acid.into_series().cast(data_type)?.into())
+    }
+}
+
+impl Default for Custom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// List columns of the row values: the retention times and their tags
+fn lists(data_frame: &DataFrame) -> Vec<&'static str> {
+    ["RetentionTime", "Batch", "Replicate"]
+        .into_iter()
+        .filter(|name| data_frame.column(name).is_ok())
+        .collect()
+}
+
+/// Batch of the replicate in the other rows
+fn batch(data_frame: &DataFrame, replicate: u32) -> PolarsResult<Option<String>> {
+    let replicates = data_frame["Replicate"].list()?;
+    let batches = data_frame["Batch"].list()?;
+    for (replicates, batches) in replicates.into_iter().zip(batches) {
+        let (Some(replicates), Some(batches)) = (replicates, batches) else {
+            continue;
+        };
+        let replicates = replicates.cast(&DataType::UInt32)?;
+        for (index, batch) in replicates.u32()?.into_iter().zip(batches.str()?) {
+            if index == Some(replicate) {
+                return Ok(batch.map(ToOwned::to_owned));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Retention times of the row, the null times keep their positions
+fn values(data_frame: &DataFrame, row: usize) -> PolarsResult<Vec<Option<f64>>> {
+    let values = list(data_frame, "RetentionTime", row)?.cast(&DataType::Float64)?;
+    Ok(values.f64()?.into_iter().collect())
+}
+
+fn list(data_frame: &DataFrame, name: &str, row: usize) -> PolarsResult<Series> {
+    let list = data_frame[name].list()?;
+    Ok(list
+        .get_as_series(row)
+        .unwrap_or_else(|| Series::new_empty(PlSmallStr::EMPTY, list.inner_dtype())))
+}
+
+fn push(data_frame: &mut DataFrame, name: &str, row: usize, value: Series) -> PolarsResult<()> {
+    let mut values = list(data_frame, name, row)?;
+    values.append(&value.cast(values.dtype())?)?;
+    replace_list(data_frame, name, row, values)
+}
+
+fn replace_list(
+    data_frame: &mut DataFrame,
+    name: &str,
+    row: usize,
+    values: Series,
+) -> PolarsResult<()> {
+    let value: ListChunked = [Some(values)].into_iter().collect();
+    let value = value.into_series().cast(data_frame[name].dtype())?;
+    replace(data_frame, name, row, &value.into())
+}
+
+fn replace(data_frame: &mut DataFrame, name: &str, row: usize, value: &Column) -> PolarsResult<()> {
+    let column = &data_frame[name];
+    let mut series = column.as_materialized_series().slice(0, row);
+    series.append(value.as_materialized_series())?;
+    series.append(
+        &column
+            .as_materialized_series()
+            .slice(row as i64 + 1, usize::MAX),
+    )?;
+    data_frame.replace(name, series)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The second replicate of the row is missing
+    fn data_frame() -> PolarsResult<DataFrame> {
+        DataFrame::new(vec![
+            Column::new(
+                "RetentionTime".into(),
+                [Series::new(PlSmallStr::EMPTY, [Some(1.0), None, Some(3.0)])],
+            ),
+            Column::new(
+                "Batch".into(),
+                [Series::new(PlSmallStr::EMPTY, ["A", "A", "B"])],
+            ),
+            Column::new(
+                "Replicate".into(),
+                [Series::new(PlSmallStr::EMPTY, [0u32, 1, 2])],
+            ),
+        ])
+    }
+
+    fn times(data_frame: &DataFrame) -> PolarsResult<Vec<Option<f64>>> {
+        values(data_frame, 0)
+    }
+
+    #[test]
+    fn value() -> PolarsResult<()> {
+        let mut data_frame = data_frame()?;
+        Edit::Value {
+            row: 0,
+            index: 2,
+            value: 4.0,
+        }
+        .apply(&mut data_frame)?;
+        assert_eq!(times(&data_frame)?, [Some(1.0), None, Some(4.0)]);
+        Edit::Value {
+            row: 0,
+            index: 1,
+            value: 2.0,
+        }
+        .apply(&mut data_frame)?;
+        assert_eq!(times(&data_frame)?, [Some(1.0), Some(2.0), Some(4.0)]);
+        Ok(())
+    }
+
+    #[test]
+    fn remove_value() -> PolarsResult<()> {
+        let mut data_frame = data_frame()?;
+        Edit::RemoveValue { row: 0, index: 2 }.apply(&mut data_frame)?;
+        assert_eq!(times(&data_frame)?, [Some(1.0), None]);
+        let batches = list(&data_frame, "Batch", 0)?;
+        assert_eq!(
+            batches.str()?.into_iter().collect::<Vec<_>>(),
+            [Some("A"); 2]
+        );
+        let replicates = list(&data_frame, "Replicate", 0)?;
+        assert_eq!(
+            replicates.u32()?.into_iter().collect::<Vec<_>>(),
+            [Some(0), Some(1)]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use super::Settings;
+use crate::{app::localize, special::isotope::ColumnExt as _};
+use egui::{Grid, Ui};
+use egui_plot::{Bar, BarChart, Plot};
+use polars::prelude::*;
+use tracing::error;
+
+/// Number of the isotope peaks (M, M+1…)
+const PEAKS: usize = 5;
+
+/// Isotope pattern view
+#[derive(Clone, Debug)]
+pub(crate) struct IsotopeView<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) row: usize,
+    pub(crate) settings: &'a Settings,
+}
+
+impl<'a> IsotopeView<'a> {
+    pub(crate) const fn new(data_frame: &'a DataFrame, row: usize, settings: &'a Settings) -> Self {
+        Self {
+            data_frame,
+            row,
+            settings,
+        }
+    }
+}
+
+impl IsotopeView<'_> {
+    pub(super) fn ui(&mut self, ui: &mut Ui) {
+        if let Err(error) = self.try_ui(ui) {
+            error!(%error);
+        }
+    }
+
+    fn try_ui(&mut self, ui: &mut Ui) -> PolarsResult<()> {
+        let formula = self.data_frame["FattyAcid"].formula(self.row)?;
+        let kinds = [
+            ("[RCO]+", formula.rco()),
+            ("[RCOO]-", formula.rcoo()),
+            ("RCOOH", formula),
+            (
+                self.settings.derivative.abbreviation(),
+                self.settings.derivative.formula(formula),
+            ),
+        ];
+        let mass_to_charge = self.settings.mass_to_charge;
+        Grid::new(ui.next_auto_id()).show(ui, |ui| {
+            ui.heading(localize!("kind"));
+            ui.heading(localize!("formula"));
+            for shift in 0..PEAKS {
+                ui.heading(if shift == 0 {
+                    "M".to_owned()
+                } else {
+                    format!("M+{shift}")
+                });
+            }
+            ui.end_row();
+            for (name, formula) in kinds {
+                ui.label(name);
+                ui.label(formula.to_string());
+                for peak in formula.pattern(PEAKS) {
+                    ui.label(format!("{:.1}", peak.abundance))
+                        .on_hover_text(mass_to_charge.format(peak.mass));
+                }
+                ui.end_row();
+            }
+        });
+        Plot::new(ui.next_auto_id())
+            .legend(Default::default())
+            .x_axis_label("m/z")
+            .y_axis_label(format!("{}, %", localize!("relative-abundance")))
+            .label_formatter(move |name, value| {
+                format!(
+                    "{name}\nm/z {}\n{:.1}%",
+                    mass_to_charge.format(value.x),
+                    value.y,
+                )
+            })
+            .show(ui, |ui| {
+                for (name, formula) in kinds {
+                    let bars = formula
+                        .pattern(PEAKS)
+                        .into_iter()
+                        .map(|peak| Bar::new(peak.mass, peak.abundance).width(0.05))
+                        .collect();
+                    ui.bar_chart(BarChart::new(bars).name(name));
+                }
+            });
+        Ok(())
+    }
+}
@@ -1,16 +1,31 @@
 use self::{
     control::Control,
+    edit::{Custom, Edit, Identity},
+    isotope::IsotopeView,
     plot::PlotView,
     settings::{Kind, Settings},
-    table::TableView,
+    table::{TableView, fatty_acids_choices},
 };
-use crate::app::{
-    computers::{SourceComputed, SourceKey},
-    data::{Format, save},
-    localize,
+use super::widgets::status::StatusWidget;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::app::data::{Format, save};
+use crate::{
+    app::{
+        computers::{SourceComputed, SourceKey, Status},
+        localize,
+    },
+    special::{column::mode::ModeSeries, substituent::ColumnExt as _},
+    utils::HashedDataFrame,
+};
+use egui::{Button, ComboBox, DragValue, Grid, Id, RichText, Ui, Window};
+use egui_phosphor::regular::{
+    ARROWS_HORIZONTAL, CHART_BAR, EXCLUDE, FLOPPY_DISK, FUNCTION, GEAR, PENCIL, PERCENT, PLUS,
+    TABLE,
+};
+use lipid::fatty_acid::{
+    display::{COMMON, DisplayWithOptions as _},
+    polars::ColumnExt as _,
 };
-use egui::{Id, RichText, Ui, Window};
-use egui_phosphor::regular::{ARROWS_HORIZONTAL, CHART_BAR, EXCLUDE, FLOPPY_DISK, GEAR, TABLE};
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
 use tracing::error;
@@ -18,23 +33,44 @@ use tracing::error;
 /// Source pane
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub(crate) struct Pane {
-    pub(crate) source: DataFrame,
-    pub(crate) target: DataFrame,
+    pub(crate) source: HashedDataFrame,
+    #[serde(skip)]
+    pub(crate) target: HashedDataFrame,
+    #[serde(skip)]
+    kind: Option<Kind>,
+    #[serde(skip)]
+    status: Option<Status>,
+    #[serde(skip)]
+    selected: Option<usize>,
     pub(crate) control: Control,
+    #[serde(default)]
+    pub(crate) origin: Origin,
+}
+
+/// Computed target and the settings window state are not part of the history
+impl PartialEq for Pane {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source && self.control.settings == other.control.settings
+    }
 }
 
 impl Pane {
-    pub(crate) const fn new(data_frame: DataFrame) -> Self {
+    pub(crate) fn new(data_frame: DataFrame, origin: Origin) -> Self {
         Self {
-            source: data_frame,
-            target: DataFrame::empty(),
+            source: HashedDataFrame::new(data_frame),
+            target: HashedDataFrame::default(),
+            kind: None,
+            status: None,
+            selected: None,
             control: Control::new(),
+            origin,
         }
     }
 
     pub(super) fn header(&mut self, ui: &mut Ui) {
         ui.separator();
         ui.visuals_mut().button_frame = false;
+        ui.add(StatusWidget::new(self.status.as_ref()));
         ui.toggle_value(
             &mut self.control.settings.resizable,
             RichText::new(ARROWS_HORIZONTAL).heading(),
@@ -42,6 +78,15 @@ impl Pane {
         .on_hover_text(localize!("resize"));
         ui.toggle_value(&mut self.control.open, RichText::new(GEAR).heading());
         ui.separator();
+        ui.toggle_value(
+            &mut self.control.settings.editable,
+            RichText::new(PENCIL).heading(),
+        )
+        .on_hover_text(localize!("edit"));
+        if self.control.settings.editable {
+            self.add_row(ui);
+        }
+        ui.separator();
         match self.control.settings.kind {
             Kind::Plot => {
                 if ui.button(RichText::new(TABLE).heading()).clicked() {
@@ -55,59 +100,208 @@ impl Pane {
             }
         };
         if ui.button(RichText::new(EXCLUDE).heading()).clicked() {
-            ui.data_mut(|data| data.insert_temp(Id::new("Distance"), self.target.clone()))
+            ui.data_mut(|data| {
+                data.insert_temp(
+                    Id::new("Distance"),
+                    (
+                        self.target.clone().into_inner(),
+                        self.control.settings.units.display,
+                    ),
+                )
+            })
         }
-        ui.separator();
-        ui.menu_button(RichText::new(FLOPPY_DISK).heading(), |ui| {
-            if ui.button("Parquet").clicked() {
-                if let Err(error) = save("df.parquet", Format::Parquet, self.target.clone()) {
-                    error!(%error);
+        if ui
+            .button(RichText::new(FUNCTION).heading())
+            .on_hover_text(localize!("increments"))
+            .clicked()
+        {
+            ui.data_mut(|data| {
+                data.insert_temp(Id::new("Increments"), self.source.clone().into_inner())
+            })
+        }
+        // Peak areas of the imported chromatograms
+        if self.source.column("Area").is_ok()
+            && ui
+                .button(RichText::new(PERCENT).heading())
+                .on_hover_text(localize!("composition"))
+                .clicked()
+        {
+            ui.data_mut(|data| {
+                data.insert_temp(Id::new("Composition"), self.source.clone().into_inner())
+            })
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            ui.separator();
+            ui.menu_button(RichText::new(FLOPPY_DISK).heading(), |ui| {
+                if ui
+                    .button(format!("{FLOPPY_DISK} {}…", localize!("save-target")))
+                    .clicked()
+                {
+                    save_as(self.target.clone().into_inner(), "target");
+                    ui.close_menu();
                 }
-                ui.close_menu();
-            }
-            if ui.button("BIN").clicked() {
-                // println!("self.target: {}", self.target);
-                // let lazy_frame = self.target.clone().lazy().select([
-                //     col("Mode").struct_().field_by_names(["*"]),
-                //     col("FA"),
-                //     col("Time").struct_().field_by_name("Values").alias("Time"),
-                // ]);
-                if let Err(error) = save("df.bin", Format::Bin, self.target.clone()) {
-                    error!(%error);
+                if ui
+                    .button(format!("{FLOPPY_DISK} {}…", localize!("save-source")))
+                    .on_hover_text(localize!("save-source-description"))
+                    .clicked()
+                {
+                    save_as(self.source.clone().into_inner(), "source");
+                    ui.close_menu();
                 }
-                ui.close_menu();
-            }
-            if ui.button("RON").clicked() {
-                if let Err(error) = save("df.ron", Format::Ron, self.target.clone()) {
-                    error!(%error);
+            });
+        }
+    }
+
+    fn add_row(&mut self, ui: &mut Ui) {
+        ui.menu_button(RichText::new(PLUS).heading(), |ui| {
+            let id = ui.auto_id_with("AddRow");
+            let (mut mode, mut fatty_acid) =
+                ui.data_mut(|data| data.get_temp::<(usize, Identity)>(id).unwrap_or_default());
+            ComboBox::from_id_salt("AddRowMode")
+                .selected_text(mode_text(&self.source, mode))
+                .show_ui(ui, |ui| {
+                    let mut texts = Vec::new();
+                    for row in 0..self.source.height() {
+                        let text = mode_text(&self.source, row);
+                        if !texts.contains(&text) {
+                            ui.selectable_value(&mut mode, row, text.clone());
+                            texts.push(text);
+                        }
+                    }
+                });
+            let selected_text = match &fatty_acid {
+                Identity::Row(row) => fatty_acid_text(&self.source, *row),
+                Identity::Custom(_) => localize!("custom-fatty-acid"),
+            };
+            ComboBox::from_id_salt("AddRowFattyAcid")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    for (row, _) in fatty_acids_choices(&self.source) {
+                        ui.selectable_value(
+                            &mut fatty_acid,
+                            Identity::Row(row),
+                            fatty_acid_text(&self.source, row),
+                        );
+                    }
+                    ui.separator();
+                    if ui
+                        .selectable_label(
+                            matches!(fatty_acid, Identity::Custom(_)),
+                            localize!("custom-fatty-acid"),
+                        )
+                        .clicked()
+                    {
+                        fatty_acid = Identity::Custom(Custom::new());
+                    }
+                });
+            let valid = match &mut fatty_acid {
+                Identity::Row(_) => true,
+                Identity::Custom(custom) => {
+                    custom_ui(ui, custom);
+                    custom.is_valid()
                 }
+            };
+            if ui
+                .add_enabled(
+                    valid,
+                    Button::new(format!("{PLUS} {}", localize!("add-row"))),
+                )
+                .clicked()
+            {
+                self.edit(
+                    ui,
+                    Edit::AddRow {
+                        mode,
+                        fatty_acid: fatty_acid.clone(),
+                    },
+                );
                 ui.close_menu();
             }
+            ui.data_mut(|data| data.insert_temp(id, (mode, fatty_acid)));
         });
     }
 
+    /// Applies the edit, the edited loaded data is written back to the data
+    fn edit(&mut self, ui: &Ui, edit: Edit) {
+        match self.source.update(|source| edit.apply(source)) {
+            Ok(()) => {
+                if self.origin == Origin::Data {
+                    ui.data_mut(|data| {
+                        data.insert_temp(Id::new("Source"), self.source.clone().into_inner())
+                    });
+                }
+            }
+            Err(error) => error!(%error),
+        }
+    }
+
     pub(super) fn content(&mut self, ui: &mut Ui) {
         self.window(ui);
-        self.target = ui.memory_mut(|memory| {
-            memory.caches.cache::<SourceComputed>().get(SourceKey {
-                data_frame: &self.source,
-                settings: &self.control.settings,
-            })
-        });
-        self.target = ui.memory_mut(|memory| {
-            memory.caches.cache::<SourceComputed>().get(SourceKey {
-                data_frame: &self.source,
-                settings: &self.control.settings,
-            })
-        });
+        self.compute(ui);
+        // The last result stays on screen until the new one is ready
+        if self.kind != Some(self.control.settings.kind) {
+            ui.centered_and_justified(|ui| ui.spinner());
+            return;
+        }
         match self.control.settings.kind {
             Kind::Plot => PlotView::new(&self.target, &self.control.settings).ui(ui),
-            Kind::Table => TableView::new(&self.target, &self.control.settings).ui(ui),
+            Kind::Table => {
+                let mut table = TableView::new(&self.source, &self.target, &self.control.settings);
+                table.selected = self.selected;
+                table.ui(ui);
+                self.selected = table.selected;
+                for edit in table.edits {
+                    self.edit(ui, edit);
+                }
+                self.isotopes(ui);
+            }
+        };
+    }
+
+    fn compute(&mut self, ui: &mut Ui) {
+        let ctx = ui.ctx().clone();
+        let status = ui.memory_mut(|memory| {
+            memory.caches.cache::<SourceComputed>().get(
+                &ctx,
+                SourceKey {
+                    data_frame: &self.source,
+                    settings: &self.control.settings,
+                },
+            )
+        });
+        self.status = match status {
+            Status::Ready(data_frame) => {
+                self.target = data_frame;
+                self.kind = Some(self.control.settings.kind);
+                None
+            }
+            status => Some(status),
         };
     }
 
+    fn isotopes(&mut self, ui: &mut Ui) {
+        let Some(row) = self.selected.filter(|&row| row < self.target.height()) else {
+            return;
+        };
+        let mut open = true;
+        Window::new(format!(
+            "{CHART_BAR} {} {}",
+            localize!("isotope-pattern"),
+            fatty_acid_text(&self.target, row),
+        ))
+        .id(ui.auto_id_with("Isotopes"))
+        .open(&mut open)
+        .show(ui.ctx(), |ui| {
+            IsotopeView::new(&self.target, row, &self.control.settings).ui(ui);
+        });
+        if !open {
+            self.selected = None;
+        }
+    }
+
     fn window(&mut self, ui: &mut Ui) {
-        Window::new(format!("{GEAR} Source settings"))
+        Window::new(format!("{GEAR} {}", localize!("source-settings")))
             .id(ui.next_auto_id())
             .open(&mut self.control.open)
             .show(ui.ctx(), |ui| {
@@ -121,5 +315,79 @@ impl Pane {
 pub(crate) mod settings;
 
 mod control;
+mod edit;
+mod isotope;
 mod plot;
 mod table;
+
+/// Origin of the source
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub(crate) enum Origin {
+    /// The loaded data, the edits are written back to it
+    Data,
+    /// A preset or an imported chromatogram, the edits stay in the pane
+    #[default]
+    Detached,
+}
+
+/// Saves the data frame to the file picked by the user, the format follows
+/// the extension
+#[cfg(not(target_arch = "wasm32"))]
+fn save_as(data_frame: DataFrame, name: &str) {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("RON", &["ron"])
+        .add_filter("BIN", &["bin"])
+        .add_filter("Parquet", &["parquet"])
+        .set_file_name(format!("{name}.ron"))
+        .save_file()
+    else {
+        return;
+    };
+    let format = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("bin") => Format::Bin,
+        Some("parquet") => Format::Parquet,
+        _ => Format::Ron,
+    };
+    if let Err(error) = save(&path, format, data_frame) {
+        error!(path = %path.display(), %error);
+    }
+}
+
+/// Fatty acid entered by the user
+pub(super) fn custom_ui(ui: &mut Ui, custom: &mut Custom) {
+    Grid::new(ui.auto_id_with("CustomFattyAcid")).show(ui, |ui| {
+        ui.label(localize!("carbons"));
+        ui.add(DragValue::new(&mut custom.carbons).range(1..=u8::MAX));
+        ui.end_row();
+
+        ui.label(localize!("unsaturated-bounds"))
+            .on_hover_text(localize!("unsaturated-bounds-description"));
+        ui.text_edit_singleline(&mut custom.bounds);
+        ui.end_row();
+
+        ui.label(localize!("label"))
+            .on_hover_text(localize!("label-description"));
+        ui.text_edit_singleline(&mut custom.label);
+        ui.end_row();
+    });
+}
+
+fn mode_text(data_frame: &DataFrame, row: usize) -> String {
+    match ModeSeries::new(&data_frame["Mode"]).and_then(|mode| mode.program(row)) {
+        Ok(program) => program.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+fn fatty_acid_text(data_frame: &DataFrame, row: usize) -> String {
+    match data_frame["FattyAcid"].fatty_acid().get(row) {
+        Ok(Some(fatty_acid)) => format!(
+            "{}{:#}",
+            data_frame["FattyAcid"]
+                .substituents(row)
+                .unwrap_or_default(),
+            (&fatty_acid).display(COMMON),
+        ),
+        _ => String::new(),
+    }
+}
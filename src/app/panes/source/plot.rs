@@ -1,4 +1,5 @@
-use super::Settings;
+use super::{Settings, settings::Axes};
+use crate::{app::localize, special::substituent::ColumnExt as _};
 use egui::Ui;
 use egui_ext::color;
 use egui_plot::{MarkerShape, Plot, Points};
@@ -39,8 +40,13 @@ impl PlotView<'_> {
         // let mode = &self.data_frame["Mode"];
         let index = self.data_frame["Index"].u32()?;
         let fatty_acid = self.data_frame.fatty_acid();
-        let retention_time = self.data_frame["RetentionTime"].list()?;
-        let ecl = self.data_frame["ECL"].list()?;
+        let (x, y) = match self.settings.axes {
+            Axes::RetentionTime => ("RetentionTime", "ECL"),
+            Axes::Temperature => ("Temperature", "ECL"),
+            Axes::HeatingRate => ("HeatingRate", "Temperature"),
+        };
+        let xs = self.data_frame[x].list()?;
+        let ys = self.data_frame[y].list()?;
         // let time = time.f64()?;
         // let ecl = ecl.f64()?;
         let mut plot = Plot::new("plot")
@@ -50,37 +56,61 @@ impl PlotView<'_> {
         if self.settings.legend {
             plot = plot.legend(Default::default());
         }
+        let (x_label, x_units, y_label, y_units) = match self.settings.axes {
+            Axes::RetentionTime => (
+                localize!("retention-time"),
+                self.settings.units.display.abbreviation(),
+                "ECL".to_owned(),
+                "",
+            ),
+            Axes::Temperature => (localize!("temperature"), "°C", "ECL".to_owned(), ""),
+            Axes::HeatingRate => (
+                localize!("heating-rate"),
+                "°C/min",
+                localize!("temperature"),
+                "°C",
+            ),
+        };
+        plot = plot
+            .x_axis_label(format!("{x_label}, {x_units}"))
+            .y_axis_label(if y_units.is_empty() {
+                y_label
+            } else {
+                format!("{y_label}, {y_units}")
+            });
         // let scale = plot.transform.dvalue_dpos();
         // let x_decimals = ((-scale[0].abs().log10()).ceil().at_least(0.0) as usize).clamp(1, 6);
         // let y_decimals = ((-scale[1].abs().log10()).ceil().at_least(0.0) as usize).clamp(1, 6);
-        plot = plot.label_formatter(|name, value| {
+        plot = plot.label_formatter(move |name, value| {
             let name = if !name.is_empty() {
                 format!("{name}\n")
             } else {
                 String::new()
             };
-            format!("{name}x = {}\ny = {}", value.x, value.y)
+            format!("{name}x = {} {x_units}\ny = {} {y_units}", value.x, value.y)
             // format!(
             //     "{}x = {:.*}\ny = {:.*}",
             //     name, x_decimals, value.x, y_decimals, value.y
             // )
         });
         plot.show(ui, |ui| -> PolarsResult<()> {
-            for (index, fatty_acid, retention_time, ecl) in
-                izip!(index, fatty_acid, retention_time, ecl)
-            {
-                if let Some((retention_time, ecl)) = retention_time.zip(ecl) {
+            for (row, (index, fatty_acid, xs, ys)) in izip!(index, fatty_acid, xs, ys).enumerate() {
+                if let Some((xs, ys)) = xs.zip(ys) {
                     let mut points = Vec::new();
-                    for (time, ecl) in zip(retention_time.f64()?, ecl.f64()?) {
-                        if let Some((time, ecl)) = time.zip(ecl) {
-                            points.push([time, ecl]);
+                    for (x, y) in zip(xs.f64()?, ys.f64()?) {
+                        if let Some((x, y)) = x.zip(y) {
+                            points.push([x, y]);
                         }
                     }
                     let mut points = Points::new(points)
                         .color(color(index.unwrap() as _))
-                        .radius(3.0);
+                        .radius(3.0_f32);
                     if let Some(fatty_acid) = fatty_acid {
-                        points = points.name(format!("{:#}", (&fatty_acid).display(COMMON)));
+                        let substituents = self.data_frame["FattyAcid"]
+                            .substituents(row)
+                            .unwrap_or_default();
+                        points = points
+                            .name(format!("{substituents}{:#}", (&fatty_acid).display(COMMON)));
                         if fatty_acid.unsaturation() == 0 {
                             points = points.shape(MarkerShape::Square).filled(false);
                         }
@@ -1,14 +1,21 @@
 use crate::{
-    app::{MAX_PRECISION, localize, text::Text},
-    special::{column::mode::ColumnExt as _, data_frame::DataFrameExt as _},
+    app::{
+        MAX_PRECISION, localize,
+        text::Text,
+        units::{MassToCharge, Units},
+    },
+    special::{
+        alignment::Correction, column::mode::ColumnExt as _, data_frame::DataFrameExt as _,
+        derivative::Derivative,
+    },
 };
-use egui::{ComboBox, Grid, RichText, Slider, Ui, emath::Float};
+use egui::{ComboBox, DragValue, Grid, RichText, Slider, Ui, emath::Float};
 use egui_ext::LabeledSeparator;
 use egui_phosphor::regular::TRASH;
 use lipid::fatty_acid::{
     FattyAcid,
     display::{COMMON, DisplayWithOptions},
-    polars::{DataFrameExt as _, column::ColumnExt as _},
+    polars::column::ColumnExt as _,
 };
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -19,18 +26,26 @@ use std::hash::{Hash, Hasher};
 pub(crate) struct Settings {
     pub(crate) precision: usize,
     pub(crate) resizable: bool,
+    pub(crate) editable: bool,
     pub(crate) sticky: usize,
     pub(crate) truncate: bool,
+    pub(crate) units: Units,
+    pub(crate) mass_to_charge: MassToCharge,
 
     pub(crate) kind: Kind,
+    pub(crate) derivative: Derivative,
     pub(crate) ddof: u8,
     pub(crate) logarithmic: bool,
-    pub(crate) relative: Option<FattyAcid>,
+    #[serde(default)]
+    pub(crate) reference: Reference,
+    #[serde(default)]
+    pub(crate) alignment: Alignment,
     pub(crate) filter: Filter,
     pub(crate) sort: Sort,
     pub(crate) order: Order,
 
     pub(crate) group: Group,
+    pub(crate) axes: Axes,
     pub(crate) legend: bool,
 }
 
@@ -39,18 +54,24 @@ impl Settings {
         Self {
             precision: 2,
             resizable: false,
+            editable: false,
             sticky: 1,
             truncate: false,
+            units: Units::new(),
+            mass_to_charge: MassToCharge::new(),
 
             kind: Kind::Table,
+            derivative: Derivative::Methyl,
             ddof: 1,
             logarithmic: false,
-            relative: None,
+            reference: Reference::new(),
+            alignment: Alignment::new(),
             filter: Filter::new(),
             sort: Sort::Time,
             order: Order::Ascending,
 
             group: Group::FattyAcid,
+            axes: Axes::RetentionTime,
             legend: true,
         }
     }
@@ -73,42 +94,176 @@ impl Settings {
                 ui.checkbox(&mut self.truncate, "");
                 ui.end_row();
 
+                // Precision m/z
+                ui.label(localize!("mass-to-charge-precision"))
+                    .on_hover_text(localize!("mass-to-charge-precision-description"));
+                ui.add(Slider::new(
+                    &mut self.mass_to_charge.precision,
+                    0..=MAX_PRECISION,
+                ));
+                ui.end_row();
+
+                // Units
+                ui.separator();
+                ui.labeled_separator(RichText::new(localize!("units")).heading());
+                ui.end_row();
+                self.units.ui(ui);
+
                 // Calculate
                 ui.separator();
-                ui.labeled_separator(RichText::new("Calculate").heading());
+                ui.labeled_separator(RichText::new(localize!("calculate")).heading());
                 ui.end_row();
 
                 // Relative
-                ui.label("Relative").on_hover_text("Relative fatty acid");
-                ui.horizontal(|ui| {
-                    let selected_text = self
-                        .relative
-                        .as_ref()
-                        .map(|relative| relative.display(COMMON).to_string())
-                        .unwrap_or_default();
+                ui.label(localize!("relative-references"))
+                    .on_hover_text(localize!("relative-references-description"));
+                ui.horizontal(|ui| -> PolarsResult<()> {
                     ComboBox::from_id_salt(ui.auto_id_with("Relative"))
-                        .selected_text(selected_text)
+                        .selected_text(
+                            self.reference
+                                .fatty_acids
+                                .iter()
+                                .map(|fatty_acid| fatty_acid.display(COMMON).to_string())
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        )
+                        .show_ui(ui, |ui| -> PolarsResult<()> {
+                            let fatty_acid = data_frame["FattyAcid"]
+                                .unique()?
+                                .sort(Default::default())?
+                                .fatty_acid();
+                            for index in 0..fatty_acid.len() {
+                                if let Some(fatty_acid) = fatty_acid.get(index)? {
+                                    // The order of the selection is the order of the fallback
+                                    let position = self
+                                        .reference
+                                        .fatty_acids
+                                        .iter()
+                                        .position(|reference| *reference == fatty_acid);
+                                    let text = match position {
+                                        Some(position) => format!(
+                                            "{} ({})",
+                                            (&fatty_acid).display(COMMON),
+                                            position + 1,
+                                        ),
+                                        None => (&fatty_acid).display(COMMON).to_string(),
+                                    };
+                                    if ui.selectable_label(position.is_some(), text).clicked() {
+                                        match position {
+                                            Some(position) => {
+                                                self.reference.fatty_acids.remove(position);
+                                            }
+                                            None => self.reference.fatty_acids.push(fatty_acid),
+                                        }
+                                    }
+                                }
+                            }
+                            Ok(())
+                        })
+                        .inner
+                        .transpose()?;
+                    if ui.button(TRASH).clicked() {
+                        self.reference.fatty_acids = Vec::new();
+                    }
+                    Ok(())
+                })
+                .inner?;
+                ui.end_row();
+
+                // Dead time
+                ui.label(localize!("dead-time"))
+                    .on_hover_text(localize!("dead-time-description"));
+                let factor = self.units.factor();
+                let mut dead_time = self.reference.dead_time * factor;
+                if ui
+                    .add(
+                        DragValue::new(&mut dead_time)
+                            .range(0.0..=f64::MAX)
+                            .speed(0.01)
+                            .suffix(format!(" {}", self.units.display.abbreviation())),
+                    )
+                    .changed()
+                {
+                    self.reference.dead_time = dead_time / factor;
+                }
+                ui.end_row();
+
+                // Alignment
+                ui.label(localize!("alignment"))
+                    .on_hover_text(localize!("alignment-description"));
+                ui.horizontal(|ui| -> PolarsResult<()> {
+                    ComboBox::from_id_salt(ui.auto_id_with("Alignment"))
+                        .selected_text(self.alignment.fatty_acids.len().to_string())
                         .show_ui(ui, |ui| -> PolarsResult<()> {
-                            let current_value = &mut self.relative;
-                            let fatty_acid = data_frame["FattyAcid"].unique()?;
-                            let fatty_acid = fatty_acid
-                                .filter(&fatty_acid.fatty_acid().saturated()?)?
+                            let fatty_acid = data_frame["FattyAcid"]
+                                .unique()?
                                 .sort(Default::default())?
                                 .fatty_acid();
                             for index in 0..fatty_acid.len() {
-                                if let Some(selected_value) = fatty_acid.get(index)? {
-                                    let text = (&selected_value).display(COMMON).to_string();
-                                    ui.selectable_value(current_value, Some(selected_value), text);
+                                if let Some(fatty_acid) = fatty_acid.get(index)? {
+                                    let contains = self.alignment.fatty_acids.contains(&fatty_acid);
+                                    let mut selected = contains;
+                                    ui.toggle_value(
+                                        &mut selected,
+                                        (&fatty_acid).display(COMMON).to_string(),
+                                    );
+                                    if selected && !contains {
+                                        self.alignment.fatty_acids.push(fatty_acid);
+                                    } else if !selected && contains {
+                                        self.alignment
+                                            .fatty_acids
+                                            .retain(|anchor| *anchor != fatty_acid);
+                                    }
                                 }
                             }
                             Ok(())
                         })
                         .inner
-                        .transpose()
+                        .transpose()?;
+                    if ui.button(TRASH).clicked() {
+                        self.alignment.fatty_acids = Vec::new();
+                    }
+                    Ok(())
                 })
                 .inner?;
                 ui.end_row();
 
+                // Correction
+                ui.label(localize!("correction"));
+                ComboBox::from_id_salt(ui.auto_id_with("Correction"))
+                    .selected_text(self.alignment.correction.text())
+                    .show_ui(ui, |ui| {
+                        for correction in Correction::ALL {
+                            ui.selectable_value(
+                                &mut self.alignment.correction,
+                                correction,
+                                correction.text(),
+                            )
+                            .on_hover_text(correction.hover_text());
+                        }
+                    })
+                    .response
+                    .on_hover_text(self.alignment.correction.hover_text());
+                ui.end_row();
+
+                // Derivative
+                ui.label(localize!("derivative"));
+                ComboBox::from_id_salt(ui.auto_id_with("Derivative"))
+                    .selected_text(self.derivative.text())
+                    .show_ui(ui, |ui| {
+                        for derivative in Derivative::ALL {
+                            ui.selectable_value(
+                                &mut self.derivative,
+                                derivative,
+                                derivative.text(),
+                            )
+                            .on_hover_text(derivative.hover_text());
+                        }
+                    })
+                    .response
+                    .on_hover_text(self.derivative.hover_text());
+                ui.end_row();
+
                 // DDOF
                 // https://numpy.org/devdocs/reference/generated/numpy.std.html
                 ui.label("DDOF");
@@ -122,11 +277,12 @@ impl Settings {
 
                 // Filter
                 ui.separator();
-                ui.labeled_separator(RichText::new("Filter").heading());
+                ui.labeled_separator(RichText::new(localize!("filter")).heading());
                 ui.end_row();
 
                 // Onset temperature filter
-                ui.label("Onset").on_hover_text("Onset temperature");
+                ui.label(localize!("onset"))
+                    .on_hover_text(localize!("onset-temperature"));
                 ui.horizontal(|ui| -> PolarsResult<()> {
                     ComboBox::from_id_salt("OnsetTemperatureFilter")
                         .selected_text(format!("{:?}", self.filter.mode.onset_temperature))
@@ -154,7 +310,8 @@ impl Settings {
                 ui.end_row();
 
                 // Temperature step filter
-                ui.label("Step").on_hover_text("Temperature step");
+                ui.label(localize!("step"))
+                    .on_hover_text(localize!("temperature-step"));
                 ui.horizontal(|ui| -> PolarsResult<()> {
                     ComboBox::from_id_salt("TemperatureStepFilter")
                         .selected_text(format!("{:?}", self.filter.mode.temperature_step))
@@ -180,7 +337,7 @@ impl Settings {
                 ui.end_row();
 
                 // Fatty acids filter
-                ui.label("Fatty acids");
+                ui.label(localize!("fatty-acids"));
                 // let text = AnyValue::List(Series::from_iter(
                 //     self.filter
                 //         .fatty_acids
@@ -225,12 +382,12 @@ impl Settings {
 
                 // Sort
                 ui.separator();
-                ui.labeled_separator(RichText::new("Sort").heading());
+                ui.labeled_separator(RichText::new(localize!("sort")).heading());
                 ui.end_row();
 
-                ui.label("Sort");
+                ui.label(localize!("sort"));
                 ComboBox::from_id_salt(ui.next_auto_id())
-                    .selected_text(format!("{:?}", self.sort))
+                    .selected_text(self.sort.text())
                     .show_ui(ui, |ui| {
                         ui.selectable_value(
                             &mut self.sort,
@@ -244,7 +401,7 @@ impl Settings {
                 ui.end_row();
 
                 // Order
-                ui.label("Order");
+                ui.label(localize!("order"));
                 ComboBox::from_id_salt(ui.next_auto_id())
                     .selected_text(self.order.text())
                     .show_ui(ui, |ui| {
@@ -268,11 +425,11 @@ impl Settings {
                 if let Kind::Plot = self.kind {
                     // Plot
                     ui.separator();
-                    ui.labeled_separator(RichText::new("Plot").heading());
+                    ui.labeled_separator(RichText::new(localize!("plot")).heading());
                     ui.end_row();
 
                     // Group
-                    ui.label("Group");
+                    ui.label(localize!("group"));
                     ComboBox::from_id_salt(ui.next_auto_id())
                         .selected_text(self.group.text())
                         .show_ui(ui, |ui| {
@@ -299,6 +456,21 @@ impl Settings {
                         .on_hover_text(self.group.hover_text());
                     ui.end_row();
 
+                    // Axes
+                    ui.label(localize!("axes"));
+                    ComboBox::from_id_salt(ui.next_auto_id())
+                        .selected_text(self.axes.text())
+                        .show_ui(ui, |ui| {
+                            for axes in [Axes::RetentionTime, Axes::Temperature, Axes::HeatingRate]
+                            {
+                                ui.selectable_value(&mut self.axes, axes, axes.text())
+                                    .on_hover_text(axes.hover_text());
+                            }
+                        })
+                        .response
+                        .on_hover_text(self.axes.hover_text());
+                    ui.end_row();
+
                     // Legend
                     ui.label(localize!("legend"));
                     ui.checkbox(&mut self.legend, "");
@@ -316,6 +488,44 @@ impl Default for Settings {
     }
 }
 
+impl Text for Derivative {
+    fn text(&self) -> String {
+        match self {
+            Self::Methyl => localize!("derivative-methyl"),
+            Self::Ethyl => localize!("derivative-ethyl"),
+            Self::Dmox => localize!("derivative-dmox"),
+            Self::Picolinyl => localize!("derivative-picolinyl"),
+            Self::Trimethylsilyl => localize!("derivative-trimethylsilyl"),
+        }
+    }
+
+    fn hover_text(&self) -> String {
+        match self {
+            Self::Methyl => localize!("derivative-methyl-description"),
+            Self::Ethyl => localize!("derivative-ethyl-description"),
+            Self::Dmox => localize!("derivative-dmox-description"),
+            Self::Picolinyl => localize!("derivative-picolinyl-description"),
+            Self::Trimethylsilyl => localize!("derivative-trimethylsilyl-description"),
+        }
+    }
+}
+
+impl Text for Correction {
+    fn text(&self) -> String {
+        match self {
+            Self::Linear => localize!("correction-linear"),
+            Self::Piecewise => localize!("correction-piecewise"),
+        }
+    }
+
+    fn hover_text(&self) -> String {
+        match self {
+            Self::Linear => localize!("correction-linear-description"),
+            Self::Piecewise => localize!("correction-piecewise-description"),
+        }
+    }
+}
+
 /// Group
 #[derive(Clone, Copy, Debug, Default, Deserialize, Hash, PartialEq, Serialize)]
 pub(crate) enum Group {
@@ -326,19 +536,49 @@ pub(crate) enum Group {
 }
 
 impl Text for Group {
-    fn text(&self) -> &'static str {
+    fn text(&self) -> String {
         match self {
-            Self::FattyAcid => "Fatty acid",
-            Self::OnsetTemperature => "Onset temperature",
-            Self::TemperatureStep => "Temperature step",
+            Self::FattyAcid => localize!("group-fatty-acid"),
+            Self::OnsetTemperature => localize!("group-onset-temperature"),
+            Self::TemperatureStep => localize!("group-temperature-step"),
         }
     }
 
-    fn hover_text(&self) -> &'static str {
+    fn hover_text(&self) -> String {
         match self {
-            Self::FattyAcid => "Group by fatty acid",
-            Self::OnsetTemperature => "Group by onset temperature",
-            Self::TemperatureStep => "Group by temperature step",
+            Self::FattyAcid => localize!("group-fatty-acid-description"),
+            Self::OnsetTemperature => localize!("group-onset-temperature-description"),
+            Self::TemperatureStep => localize!("group-temperature-step-description"),
+        }
+    }
+}
+
+/// Plot axes
+#[derive(Clone, Copy, Debug, Default, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) enum Axes {
+    /// ECL vs retention time
+    #[default]
+    RetentionTime,
+    /// ECL vs elution temperature
+    Temperature,
+    /// Elution temperature vs heating rate
+    HeatingRate,
+}
+
+impl Text for Axes {
+    fn text(&self) -> String {
+        match self {
+            Self::RetentionTime => localize!("axes-retention-time"),
+            Self::Temperature => localize!("axes-temperature"),
+            Self::HeatingRate => localize!("axes-heating-rate"),
+        }
+    }
+
+    fn hover_text(&self) -> String {
+        match self {
+            Self::RetentionTime => localize!("axes-retention-time-description"),
+            Self::Temperature => localize!("axes-temperature-description"),
+            Self::HeatingRate => localize!("axes-heating-rate-description"),
         }
     }
 }
@@ -351,6 +591,50 @@ pub(crate) enum Kind {
     Table,
 }
 
+/// References of the relative retention time
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Reference {
+    /// The first reference present in the mode is used, the next ones are
+    /// the fallback
+    pub(crate) fatty_acids: Vec<FattyAcid>,
+    /// Dead (hold-up) time of the adjusted retention times in the source
+    /// units
+    pub(crate) dead_time: f64,
+}
+
+impl Reference {
+    pub const fn new() -> Self {
+        Self {
+            fatty_acids: Vec::new(),
+            dead_time: 0.0,
+        }
+    }
+}
+
+impl Hash for Reference {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.fatty_acids.hash(state);
+        self.dead_time.ord().hash(state);
+    }
+}
+
+/// Retention time alignment between the runs
+#[derive(Clone, Debug, Default, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct Alignment {
+    /// Anchors of the alignment, no anchors leave the runs as is
+    pub(crate) fatty_acids: Vec<FattyAcid>,
+    pub(crate) correction: Correction,
+}
+
+impl Alignment {
+    pub const fn new() -> Self {
+        Self {
+            fatty_acids: Vec::new(),
+            correction: Correction::Linear,
+        }
+    }
+}
+
 /// Filter
 #[derive(Clone, Debug, Default, Deserialize, Hash, PartialEq, Serialize)]
 pub(crate) struct Filter {
@@ -408,17 +692,17 @@ pub(crate) enum Sort {
 }
 
 impl Text for Sort {
-    fn text(&self) -> &'static str {
+    fn text(&self) -> String {
         match self {
-            Self::FattyAcid => "Fatty acid",
-            Self::Time => "Time",
+            Self::FattyAcid => localize!("sort-fatty-acid"),
+            Self::Time => localize!("sort-time"),
         }
     }
 
-    fn hover_text(&self) -> &'static str {
+    fn hover_text(&self) -> String {
         match self {
-            Self::FattyAcid => "Sort by atty acid",
-            Self::Time => "Sort by Equivalent carbon number and retention time",
+            Self::FattyAcid => localize!("sort-fatty-acid-description"),
+            Self::Time => localize!("sort-time-description"),
         }
     }
 }
@@ -431,17 +715,17 @@ pub(in crate::app) enum Order {
 }
 
 impl Text for Order {
-    fn text(&self) -> &'static str {
+    fn text(&self) -> String {
         match self {
-            Self::Ascending => "Ascending",
-            Self::Descending => "Descending",
+            Self::Ascending => localize!("ascending"),
+            Self::Descending => localize!("descending"),
         }
     }
 
-    fn hover_text(&self) -> &'static str {
+    fn hover_text(&self) -> String {
         match self {
-            Self::Ascending => "Dscending",
-            Self::Descending => "Descending",
+            Self::Ascending => localize!("ascending-description"),
+            Self::Descending => localize!("descending-description"),
         }
     }
 }
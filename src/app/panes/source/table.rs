@@ -1,8 +1,19 @@
-use super::Settings;
-use crate::app::panes::widgets::float::FloatValue;
-use egui::{Frame, Grid, Id, Margin, TextStyle, TextWrapMode, Ui, Vec2, vec2};
+use super::{
+    Settings, custom_ui,
+    edit::{Custom, Edit, Identity},
+};
+use crate::{
+    app::{localize, panes::widgets::float::FloatValue, units::RetentionTimeFormat},
+    special::{column::mode::ColumnExt as _, substituent::ColumnExt as _},
+};
+use egui::{
+    Button, ComboBox, DragValue, Frame, Grid, Id, Margin, RichText, TextStyle, TextWrapMode, Ui,
+    Vec2, vec2,
+};
+use egui_phosphor::regular::{MINUS, PENCIL, PLUS, TRASH};
 use egui_table::{AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate};
 use lipid::fatty_acid::{
+    FattyAcid,
     display::{COMMON, DisplayWithOptions as _},
     polars::ColumnExt,
 };
@@ -14,9 +25,10 @@ const MARGIN: Vec2 = vec2(4.0, 0.0);
 const ID: Range<usize> = 0..3;
 const RETENTION_TIME: Range<usize> = ID.end..ID.end + 3;
 const TEMPERATURE: Range<usize> = RETENTION_TIME.end..RETENTION_TIME.end + 1;
-const CHAIN_LENGTH: Range<usize> = TEMPERATURE.end..TEMPERATURE.end + 3;
+const CHAIN_LENGTH: Range<usize> = TEMPERATURE.end..TEMPERATURE.end + 4;
 const MASS: Range<usize> = CHAIN_LENGTH.end..CHAIN_LENGTH.end + 1;
-const DERIVATIVE: Range<usize> = MASS.end..MASS.end + 2;
+const FRAGMENTS: Range<usize> = MASS.end..MASS.end + 4;
+const DERIVATIVE: Range<usize> = FRAGMENTS.end..FRAGMENTS.end + 2;
 const LEN: usize = DERIVATIVE.end;
 
 const TOP: &[Range<usize>] = &[
@@ -25,21 +37,32 @@ const TOP: &[Range<usize>] = &[
     TEMPERATURE,
     CHAIN_LENGTH,
     MASS,
+    FRAGMENTS,
     DERIVATIVE,
 ];
 
 /// Table view
 #[derive(Clone, Debug)]
 pub(crate) struct TableView<'a> {
+    pub(crate) source: &'a DataFrame,
     pub(crate) data_frame: &'a DataFrame,
     pub(crate) settings: &'a Settings,
+    pub(crate) edits: Vec<Edit>,
+    pub(crate) selected: Option<usize>,
 }
 
 impl<'a> TableView<'a> {
-    pub(crate) const fn new(data_frame: &'a DataFrame, settings: &'a Settings) -> Self {
+    pub(crate) const fn new(
+        source: &'a DataFrame,
+        data_frame: &'a DataFrame,
+        settings: &'a Settings,
+    ) -> Self {
         Self {
+            source,
             data_frame,
             settings,
+            edits: Vec::new(),
+            selected: None,
         }
     }
 }
@@ -80,38 +103,47 @@ impl TableView<'_> {
                 ui.heading("ID");
             }
             (0, RETENTION_TIME) => {
-                ui.heading("Retention time");
+                ui.heading(format!(
+                    "{}, {}",
+                    localize!("retention-time"),
+                    self.settings.units.display.abbreviation()
+                ));
             }
             (0, TEMPERATURE) => {
-                ui.heading("Temperature");
+                ui.heading(localize!("temperature"));
             }
             (0, CHAIN_LENGTH) => {
-                ui.heading("Chain length");
+                ui.heading(localize!("chain-length"));
             }
             (0, MASS) => {
-                ui.heading("Mass");
+                ui.heading(localize!("mass"));
+            }
+            (0, FRAGMENTS) => {
+                ui.heading(format!("{}, m/z", localize!("fragments")))
+                    .on_hover_text(localize!("fragments-description"));
             }
             (0, DERIVATIVE) => {
-                ui.heading("Derivative");
+                ui.heading(localize!("derivative"));
             }
             // Bottom
             (1, id::INDEX) => {
-                ui.heading("Index");
+                ui.heading(localize!("index"));
             }
             (1, id::MODE) => {
-                ui.heading("Mode");
+                ui.heading(localize!("mode"));
             }
             (1, id::FA) => {
-                ui.heading("Fatty acid");
+                ui.heading(localize!("fatty-acid"));
             }
             (1, retention_time::ABSOLUTE) => {
-                ui.heading("Absolute");
+                ui.heading(localize!("absolute"));
             }
             (1, retention_time::RELATIVE) => {
-                ui.heading("Relative");
+                ui.heading(localize!("relative"))
+                    .on_hover_text(localize!("relative-references-description"));
             }
             (1, retention_time::DELTA) => {
-                ui.heading("Delta");
+                ui.heading(localize!("delta"));
             }
             (1, chain_length::ECL) => {
                 ui.heading("ECL");
@@ -122,11 +154,34 @@ impl TableView<'_> {
             (1, chain_length::ECN) => {
                 ui.heading("ECN");
             }
+            (1, chain_length::TECL) => {
+                ui.heading("TECL")
+                    .on_hover_text(localize!("tecl-description"));
+            }
+            (1, MASS) => {
+                ui.heading(self.settings.derivative.abbreviation());
+            }
+            (1, fragments::M) => {
+                ui.heading("M+")
+                    .on_hover_text(localize!("molecular-ion-description"));
+            }
+            (1, fragments::LOSSES) => {
+                ui.heading(localize!("losses"))
+                    .on_hover_text(localize!("losses-description"));
+            }
+            (1, fragments::IONS) => {
+                ui.heading(localize!("ions"))
+                    .on_hover_text(localize!("ions-description"));
+            }
+            (1, fragments::DIAGNOSTIC) => {
+                ui.heading(localize!("diagnostic"))
+                    .on_hover_text(localize!("diagnostic-description"));
+            }
             (1, derivative::SLOPE) => {
-                ui.heading("Slope");
+                ui.heading(localize!("slope"));
             }
             (1, derivative::ANGLE) => {
-                ui.heading("Angle");
+                ui.heading(localize!("angle"));
             }
             _ => {}
         }
@@ -137,22 +192,87 @@ impl TableView<'_> {
             (row, id::INDEX) => {
                 let index = self.data_frame["Index"].u32().unwrap();
                 let value = index.get(row).unwrap();
-                ui.label(value.to_string());
+                ui.horizontal(|ui| {
+                    if self.settings.editable
+                        && ui
+                            .button(TRASH)
+                            .on_hover_text(localize!("delete-row"))
+                            .clicked()
+                    {
+                        self.edits.push(Edit::DeleteRow {
+                            row: self.source_row(row),
+                        });
+                    }
+                    let selected = self.selected == Some(row);
+                    if ui
+                        .selectable_label(selected, value.to_string())
+                        .on_hover_text(localize!("isotope-pattern"))
+                        .clicked()
+                    {
+                        self.selected = if selected { None } else { Some(row) };
+                    }
+                });
             }
             (row, id::MODE) => {
-                let mode = self.data_frame["Mode"].struct_().unwrap();
-                let onset_temperature = mode.field_by_name("OnsetTemperature").unwrap();
-                let temperature_step = mode.field_by_name("TemperatureStep").unwrap();
-                ui.label(format!(
-                    "{}/{}",
-                    onset_temperature.str_value(row).unwrap(),
-                    temperature_step.str_value(row).unwrap()
-                ));
+                let program = self.data_frame["Mode"].mode().program(row).unwrap();
+                ui.label(program.to_string());
             }
             (row, id::FA) => {
                 let fatty_acids = self.data_frame["FattyAcid"].fatty_acid();
                 let fatty_acid = fatty_acids.get(row).unwrap().unwrap();
-                ui.label(format!("{:#}", fatty_acid.display(COMMON)));
+                let substituents = self.data_frame["FattyAcid"].substituents(row).unwrap();
+                let text = format!("{substituents}{:#}", fatty_acid.display(COMMON));
+                if self.settings.editable {
+                    let row = self.source_row(row);
+                    ui.horizontal(|ui| {
+                        ComboBox::from_id_salt(ui.auto_id_with(row))
+                            .selected_text(text)
+                            .show_ui(ui, |ui| {
+                                for (from, selected_value) in fatty_acids_choices(self.source) {
+                                    let substituents =
+                                        self.source["FattyAcid"].substituents(from).unwrap();
+                                    let text = format!(
+                                        "{substituents}{:#}",
+                                        (&selected_value).display(COMMON)
+                                    );
+                                    if ui
+                                        .selectable_label(selected_value == fatty_acid, text)
+                                        .clicked()
+                                        && selected_value != fatty_acid
+                                    {
+                                        self.edits.push(Edit::FattyAcid {
+                                            row,
+                                            fatty_acid: Identity::Row(from),
+                                        });
+                                    }
+                                }
+                            });
+                        ui.menu_button(PENCIL, |ui| {
+                            let id = ui.auto_id_with(("CustomFattyAcid", row));
+                            let mut custom =
+                                ui.data_mut(|data| data.get_temp::<Custom>(id).unwrap_or_default());
+                            custom_ui(ui, &mut custom);
+                            if ui
+                                .add_enabled(
+                                    custom.is_valid(),
+                                    Button::new(localize!("custom-fatty-acid-apply")),
+                                )
+                                .clicked()
+                            {
+                                self.edits.push(Edit::FattyAcid {
+                                    row,
+                                    fatty_acid: Identity::Custom(custom.clone()),
+                                });
+                                ui.close_menu();
+                            }
+                            ui.data_mut(|data| data.insert_temp(id, custom));
+                        })
+                        .response
+                        .on_hover_text(localize!("custom-fatty-acid"));
+                    });
+                } else {
+                    ui.label(text);
+                }
                 // .on_hover_text(fatty_acid.label());
             }
             (row, retention_time::ABSOLUTE) => {
@@ -171,11 +291,23 @@ impl TableView<'_> {
                         ui.label(mean.str_value(row).unwrap());
                         ui.label("±");
                         ui.label(standard_deviation.str_value(row).unwrap());
+                        ui.label(format!(" {}", self.settings.units.display.abbreviation()));
                     });
+                    if !self.settings.alignment.fatty_acids.is_empty() {
+                        let unaligned = absolute
+                            .field_by_name("UnalignedStandardDeviation")
+                            .unwrap();
+                        ui.horizontal(|ui| {
+                            ui.label(localize!("unaligned"));
+                            ui.label(" ±");
+                            ui.label(unaligned.str_value(row).unwrap());
+                            ui.label(format!(" {}", self.settings.units.display.abbreviation()));
+                        });
+                    }
                 })
                 .on_hover_ui(|ui| {
                     ui.spacing_mut().item_spacing.x = 0.0;
-                    ui.heading("Repeats");
+                    ui.heading(localize!("repeats"));
                     let values = absolute
                         .field_by_name("Values")
                         .unwrap()
@@ -184,20 +316,39 @@ impl TableView<'_> {
                         .get_as_series(row)
                         .unwrap();
                     ui.vertical(|ui| {
-                        for value in values.iter() {
-                            ui.label(value.to_string());
+                        for value in values.f64().unwrap() {
+                            match value {
+                                Some(value) => ui.label(
+                                    RetentionTimeFormat::new(value)
+                                        .units(self.settings.units.display),
+                                ),
+                                None => ui.label(AnyValue::Null.to_string()),
+                            };
                         }
                     });
                 });
+                if self.settings.editable {
+                    self.values_ui(ui, row);
+                }
             }
             (row, retention_time::RELATIVE) => {
                 let retention_time = self.data_frame["RetentionTime"].struct_().unwrap();
                 let relative = retention_time.field_by_name("Relative").unwrap();
+                let relative = relative.struct_().unwrap();
+                let mean = relative.field_by_name("Mean").unwrap();
                 ui.add(
-                    FloatValue::new(relative.f64().unwrap().get(row))
-                        .precision(Some(self.settings.precision))
-                        .hover(),
-                );
+                    FloatValue::new(mean.f64().unwrap().get(row))
+                        .precision(Some(self.settings.precision)),
+                )
+                .on_hover_ui(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    let standard_deviation = relative.field_by_name("StandardDeviation").unwrap();
+                    ui.horizontal(|ui| {
+                        ui.label(mean.str_value(row).unwrap());
+                        ui.label("±");
+                        ui.label(standard_deviation.str_value(row).unwrap());
+                    });
+                });
             }
             (row, retention_time::DELTA) => {
                 let retention_time = self.data_frame["RetentionTime"].struct_().unwrap();
@@ -239,11 +390,24 @@ impl TableView<'_> {
                 let ecn = chain_length.field_by_name("ECN").unwrap();
                 ui.label(ecn.str_value(row).unwrap());
             }
+            (row, chain_length::TECL) => {
+                let chain_length = self.data_frame["ChainLength"].struct_().unwrap();
+                let tecl = chain_length.field_by_name("TECL").unwrap();
+                let tecl = tecl.f64().unwrap().get(row);
+                ui.add(FloatValue::new(tecl).precision(Some(self.settings.precision)))
+                    .on_hover_ui(|ui| {
+                        ui.label(localize!("retention-index"));
+                        ui.add(
+                            FloatValue::new(tecl.map(|tecl| tecl * 100.0))
+                                .precision(Some(self.settings.precision)),
+                        );
+                    });
+            }
             (row, MASS) => {
                 let mass = self.data_frame["Mass"].struct_().unwrap();
-                let rcooch3 = mass.field_by_name("RCOOCH3").unwrap();
+                let derivative = mass.field_by_name("Derivative").unwrap();
                 ui.add(
-                    FloatValue::new(rcooch3.f64().unwrap().get(row))
+                    FloatValue::new(derivative.f64().unwrap().get(row))
                         .precision(Some(self.settings.precision)),
                 )
                 .on_hover_ui(|ui| {
@@ -263,11 +427,39 @@ impl TableView<'_> {
                         ui.label(rcooh.str_value(row).unwrap());
                         ui.end_row();
 
-                        ui.label("RCOOCH3");
-                        ui.label(rcooch3.str_value(row).unwrap());
+                        ui.label(self.settings.derivative.abbreviation());
+                        ui.label(derivative.str_value(row).unwrap());
                     });
                 });
             }
+            (row, fragments::M) => {
+                self.mass_to_charge_ui(ui, row, &[("M+", "M")]);
+            }
+            (row, fragments::LOSSES) => {
+                let losses = self.settings.derivative.losses();
+                let names: Vec<_> = losses.iter().map(|&(name, _)| (name, name)).collect();
+                self.mass_to_charge_ui(ui, row, &names);
+            }
+            (row, fragments::IONS) => {
+                let ions = self.settings.derivative.ions();
+                let names: Vec<_> = ions.iter().map(|&(name, _)| (name, name)).collect();
+                self.mass_to_charge_ui(ui, row, &names);
+            }
+            (row, fragments::DIAGNOSTIC) => {
+                let fragments = self.data_frame["Fragments"].struct_().unwrap();
+                let diagnostic = fragments.field_by_name("Diagnostic").unwrap();
+                if let Some(ions) = diagnostic.list().unwrap().get_as_series(row) {
+                    let text = ions
+                        .f64()
+                        .unwrap()
+                        .into_iter()
+                        .flatten()
+                        .map(|value| self.settings.mass_to_charge.format(value).to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    ui.label(text);
+                }
+            }
             (row, derivative::SLOPE) => {
                 let derivative = self.data_frame["Derivative"].struct_().unwrap();
                 let slope = derivative.field_by_name("Slope").unwrap();
@@ -292,6 +484,98 @@ impl TableView<'_> {
     }
 }
 
+impl TableView<'_> {
+    /// Fragments by the struct field names, hover text with the labels
+    fn mass_to_charge_ui(&self, ui: &mut Ui, row: usize, names: &[(&str, &str)]) {
+        let fragments = self.data_frame["Fragments"].struct_().unwrap();
+        let mut values = Vec::with_capacity(names.len());
+        for &(label, name) in names {
+            let fragment = fragments.field_by_name(name).unwrap();
+            if let Some(value) = fragment.f64().unwrap().get(row) {
+                values.push((label, value));
+            }
+        }
+        let text = values
+            .iter()
+            .map(|&(_, value)| self.settings.mass_to_charge.format(value).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        ui.label(text).on_hover_ui(|ui| {
+            Grid::new(ui.next_auto_id()).show(ui, |ui| {
+                for (label, value) in values {
+                    ui.label(label);
+                    ui.label(value.to_string());
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn source_row(&self, row: usize) -> usize {
+        self.data_frame["Row"].u32().unwrap().get(row).unwrap() as _
+    }
+
+    fn values_ui(&mut self, ui: &mut Ui, row: usize) {
+        let row = self.source_row(row);
+        ui.menu_button(RichText::new(PENCIL), |ui| {
+            ui.heading(localize!("repeats"));
+            let Some(values) = self.source["RetentionTime"]
+                .list()
+                .ok()
+                .and_then(|list| list.get_as_series(row))
+            else {
+                return;
+            };
+            Grid::new(ui.next_auto_id()).show(ui, |ui| {
+                for (index, value) in values.f64().unwrap().into_iter().flatten().enumerate() {
+                    let mut edited = value;
+                    if ui.add(DragValue::new(&mut edited).speed(0.01)).changed() {
+                        self.edits.push(Edit::Value {
+                            row,
+                            index,
+                            value: edited,
+                        });
+                    }
+                    if ui
+                        .button(MINUS)
+                        .on_hover_text(localize!("remove-value"))
+                        .clicked()
+                    {
+                        self.edits.push(Edit::RemoveValue { row, index });
+                    }
+                    ui.end_row();
+                }
+            });
+            if ui
+                .button(PLUS)
+                .on_hover_text(localize!("add-value"))
+                .clicked()
+            {
+                let value = values
+                    .f64()
+                    .ok()
+                    .and_then(|values| values.mean())
+                    .unwrap_or_default();
+                self.edits.push(Edit::PushValue { row, value });
+            }
+        });
+    }
+}
+
+/// Unique fatty acids of the source with the first row they occur in
+pub(crate) fn fatty_acids_choices(source: &DataFrame) -> Vec<(usize, FattyAcid)> {
+    let mut choices: Vec<(usize, FattyAcid)> = Vec::new();
+    let fatty_acids = source["FattyAcid"].fatty_acid();
+    for row in 0..fatty_acids.len() {
+        if let Ok(Some(fatty_acid)) = fatty_acids.get(row) {
+            if !choices.iter().any(|(_, choice)| choice == &fatty_acid) {
+                choices.push((row, fatty_acid));
+            }
+        }
+    }
+    choices
+}
+
 impl TableDelegate for TableView<'_> {
     fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
         Frame::none()
@@ -336,6 +620,16 @@ mod chain_length {
     pub(super) const ECL: Range<usize> = CHAIN_LENGTH.start..CHAIN_LENGTH.start + 1;
     pub(super) const FCL: Range<usize> = ECL.end..ECL.end + 1;
     pub(super) const ECN: Range<usize> = FCL.end..FCL.end + 1;
+    pub(super) const TECL: Range<usize> = ECN.end..ECN.end + 1;
+}
+
+mod fragments {
+    use super::*;
+
+    pub(super) const M: Range<usize> = FRAGMENTS.start..FRAGMENTS.start + 1;
+    pub(super) const LOSSES: Range<usize> = M.end..M.end + 1;
+    pub(super) const IONS: Range<usize> = LOSSES.end..LOSSES.end + 1;
+    pub(super) const DIAGNOSTIC: Range<usize> = IONS.end..IONS.end + 1;
 }
 
 mod derivative {
@@ -0,0 +1,153 @@
+use self::{control::Control, plot::PlotView, settings::Settings, table::TableView};
+use super::widgets::status::StatusWidget;
+use crate::{
+    app::{
+        computers::{SpectrumComputed, SpectrumKey, Status},
+        data::{Format, save},
+        localize,
+    },
+    utils::HashedDataFrame,
+};
+use egui::{RichText, Ui, Window};
+use egui_phosphor::regular::{ARROWS_HORIZONTAL, FLOPPY_DISK, GEAR, WAVEFORM};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+/// Spectrum pane
+///
+/// Measured EI spectra against the predicted ions of the fatty acids.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Pane {
+    pub(crate) source: HashedDataFrame,
+    pub(crate) spectra: HashedDataFrame,
+    #[serde(skip)]
+    pub(crate) target: HashedDataFrame,
+    #[serde(skip)]
+    status: Option<Status>,
+    #[serde(skip)]
+    selected: Option<usize>,
+    pub(crate) control: Control,
+}
+
+/// Computed target and the settings window state are not part of the history
+impl PartialEq for Pane {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+            && self.spectra == other.spectra
+            && self.control.settings == other.control.settings
+    }
+}
+
+impl Pane {
+    pub(crate) fn new(source: DataFrame, spectra: DataFrame) -> Self {
+        Self {
+            source: HashedDataFrame::new(source),
+            spectra: HashedDataFrame::new(spectra),
+            target: HashedDataFrame::default(),
+            status: None,
+            selected: None,
+            control: Control::new(),
+        }
+    }
+
+    pub(super) fn header(&mut self, ui: &mut Ui) {
+        ui.visuals_mut().button_frame = false;
+        ui.separator();
+        ui.add(StatusWidget::new(self.status.as_ref()));
+        ui.toggle_value(
+            &mut self.control.settings.resizable,
+            RichText::new(ARROWS_HORIZONTAL).heading(),
+        )
+        .on_hover_text(localize!("resize"));
+        ui.toggle_value(&mut self.control.open, RichText::new(GEAR).heading());
+        ui.separator();
+        ui.menu_button(RichText::new(FLOPPY_DISK).heading(), |ui| {
+            if ui.button("BIN").clicked() {
+                if let Err(error) = save(
+                    "spectrum.bin",
+                    Format::Bin,
+                    self.target.clone().into_inner(),
+                ) {
+                    error!(%error);
+                }
+            }
+            if ui.button("RON").clicked() {
+                if let Err(error) = save(
+                    "spectrum.ron",
+                    Format::Ron,
+                    self.target.clone().into_inner(),
+                ) {
+                    error!(%error);
+                }
+            }
+        });
+    }
+
+    pub(super) fn content(&mut self, ui: &mut Ui) {
+        self.window(ui);
+        self.compute(ui);
+        // The last result stays on screen until the new one is ready
+        if self.target.width() == 0 {
+            ui.centered_and_justified(|ui| ui.spinner());
+            return;
+        }
+        let mut table = TableView::new(&self.target, &self.control.settings);
+        table.selected = self.selected;
+        table.ui(ui);
+        self.selected = table.selected;
+        self.spectrum(ui);
+    }
+
+    fn compute(&mut self, ui: &mut Ui) {
+        let ctx = ui.ctx().clone();
+        let status = ui.memory_mut(|memory| {
+            memory.caches.cache::<SpectrumComputed>().get(
+                &ctx,
+                SpectrumKey {
+                    data_frame: &self.source,
+                    spectra: &self.spectra,
+                    settings: &self.control.settings,
+                },
+            )
+        });
+        self.status = match status {
+            Status::Ready(data_frame) => {
+                self.target = data_frame;
+                None
+            }
+            status => Some(status),
+        };
+    }
+
+    fn spectrum(&mut self, ui: &mut Ui) {
+        let Some(row) = self.selected.filter(|&row| row < self.target.height()) else {
+            return;
+        };
+        let mut open = true;
+        Window::new(format!("{WAVEFORM} {}", localize!("spectrum")))
+            .id(ui.auto_id_with("Spectrum"))
+            .open(&mut open)
+            .show(ui.ctx(), |ui| {
+                PlotView::new(&self.target, row, &self.control.settings).ui(ui);
+            });
+        if !open {
+            self.selected = None;
+        }
+    }
+
+    fn window(&mut self, ui: &mut Ui) {
+        Window::new(format!("{GEAR} {}", localize!("spectrum-settings")))
+            .id(ui.next_auto_id())
+            .open(&mut self.control.open)
+            .show(ui.ctx(), |ui| {
+                self.control.settings.ui(ui, &self.target);
+            });
+    }
+}
+
+pub(crate) mod settings;
+
+mod control;
+mod plot;
+mod table;
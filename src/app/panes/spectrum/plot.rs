@@ -0,0 +1,86 @@
+use super::Settings;
+use crate::app::localize;
+use egui::{Color32, Ui};
+use egui_plot::{Bar, BarChart, Plot};
+use polars::prelude::*;
+use tracing::error;
+
+/// Plot view
+///
+/// The measured spectrum normalized to the base peak up, the predicted ions
+/// down (head to tail).
+#[derive(Clone, Debug)]
+pub(crate) struct PlotView<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) row: usize,
+    pub(crate) settings: &'a Settings,
+}
+
+impl<'a> PlotView<'a> {
+    pub(crate) const fn new(data_frame: &'a DataFrame, row: usize, settings: &'a Settings) -> Self {
+        Self {
+            data_frame,
+            row,
+            settings,
+        }
+    }
+}
+
+impl PlotView<'_> {
+    pub(super) fn ui(&mut self, ui: &mut Ui) {
+        if let Err(error) = self.try_ui(ui) {
+            error!(%error);
+        }
+    }
+
+    fn try_ui(&mut self, ui: &mut Ui) -> PolarsResult<()> {
+        let list = |name: &str| -> PolarsResult<Vec<f64>> {
+            Ok(self.data_frame[name]
+                .list()?
+                .get_as_series(self.row)
+                .map(|series| {
+                    series
+                        .f64()
+                        .map(|values| values.into_iter().flatten().collect())
+                })
+                .transpose()?
+                .unwrap_or_default())
+        };
+        let mass_to_charge = list("MassToCharge")?;
+        let intensities = list("Intensity")?;
+        let predicted = list("Predicted")?;
+        let max = intensities.iter().copied().fold(0.0, f64::max);
+        let measured = mass_to_charge
+            .iter()
+            .zip(&intensities)
+            .map(|(&mass_to_charge, &intensity)| {
+                Bar::new(mass_to_charge, intensity / max * 100.0).width(0.5)
+            })
+            .collect();
+        let predicted = predicted
+            .iter()
+            .map(|&mass_to_charge| Bar::new(mass_to_charge, -100.0).width(0.5))
+            .collect();
+        let format = self.settings.mass_to_charge;
+        Plot::new(ui.next_auto_id())
+            .legend(Default::default())
+            .x_axis_label("m/z")
+            .y_axis_label(format!("{}, %", localize!("relative-intensity")))
+            .label_formatter(move |name, value| {
+                format!(
+                    "{name}\nm/z {}\n{:.1}%",
+                    format.format(value.x),
+                    value.y.abs(),
+                )
+            })
+            .show(ui, |ui| {
+                ui.bar_chart(BarChart::new(measured).name(localize!("measured")));
+                ui.bar_chart(
+                    BarChart::new(predicted)
+                        .name(localize!("predicted"))
+                        .color(Color32::from_rgb(200, 60, 60)),
+                );
+            });
+        Ok(())
+    }
+}
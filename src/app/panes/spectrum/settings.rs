@@ -0,0 +1,86 @@
+use crate::{
+    app::{MAX_PRECISION, localize, text::Text, units::MassToCharge},
+    special::derivative::Derivative,
+};
+use egui::{ComboBox, Grid, RichText, Slider, Ui};
+use egui_ext::LabeledSeparator;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Settings
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Settings {
+    pub(crate) precision: usize,
+    pub(crate) resizable: bool,
+    pub(crate) sticky: usize,
+    pub(crate) truncate: bool,
+    pub(crate) mass_to_charge: MassToCharge,
+
+    pub(crate) derivative: Derivative,
+}
+
+impl Settings {
+    pub(crate) const fn new() -> Self {
+        Self {
+            precision: 0,
+            resizable: false,
+            sticky: 1,
+            truncate: false,
+            mass_to_charge: MassToCharge::new(),
+
+            derivative: Derivative::Methyl,
+        }
+    }
+
+    pub(crate) fn ui(&mut self, ui: &mut Ui, data_frame: &DataFrame) {
+        Grid::new("spectrum").show(ui, |ui| {
+            // Precision floats
+            ui.label(localize!("precision"));
+            ui.add(Slider::new(&mut self.precision, 0..=MAX_PRECISION));
+            ui.end_row();
+
+            // Precision m/z
+            ui.label("m/z precision");
+            ui.add(Slider::new(
+                &mut self.mass_to_charge.precision,
+                0..=MAX_PRECISION,
+            ));
+            ui.end_row();
+
+            // Sticky columns
+            ui.label(localize!("sticky"));
+            ui.add(Slider::new(&mut self.sticky, 0..=data_frame.width()));
+            ui.end_row();
+
+            // Truncate titles
+            ui.label(localize!("truncate"));
+            ui.checkbox(&mut self.truncate, "");
+            ui.end_row();
+
+            // Calculate
+            ui.separator();
+            ui.labeled_separator(RichText::new(localize!("calculate")).heading());
+            ui.end_row();
+
+            // Derivative
+            ui.label(localize!("derivative"));
+            ComboBox::from_id_salt(ui.auto_id_with("Derivative"))
+                .selected_text(self.derivative.text())
+                .show_ui(ui, |ui| {
+                    for derivative in Derivative::ALL {
+                        ui.selectable_value(&mut self.derivative, derivative, derivative.text())
+                            .on_hover_text(derivative.hover_text());
+                    }
+                })
+                .response
+                .on_hover_text(self.derivative.hover_text());
+            ui.end_row();
+        });
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
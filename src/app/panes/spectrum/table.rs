@@ -0,0 +1,156 @@
+use super::Settings;
+use crate::{
+    app::{localize, panes::widgets::float::FloatValue},
+    special::substituent::ColumnExt as _,
+};
+use egui::{Frame, Id, Margin, TextStyle, TextWrapMode, Ui, Vec2, vec2};
+use egui_table::{AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate};
+use lipid::fatty_acid::{
+    display::{COMMON, DisplayWithOptions as _},
+    polars::ColumnExt as _,
+};
+use polars::prelude::*;
+
+const INDEX: usize = 0;
+const FA: usize = 1;
+const NAME: usize = 2;
+const PEAKS: usize = 3;
+const MATCH: usize = 4;
+const REVERSE: usize = 5;
+const LEN: usize = 6;
+
+const MARGIN: Vec2 = vec2(4.0, 0.0);
+
+/// Table view
+#[derive(Clone, Debug)]
+pub(crate) struct TableView<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) settings: &'a Settings,
+    pub(crate) selected: Option<usize>,
+}
+
+impl<'a> TableView<'a> {
+    pub(crate) const fn new(data_frame: &'a DataFrame, settings: &'a Settings) -> Self {
+        Self {
+            data_frame,
+            settings,
+            selected: None,
+        }
+    }
+}
+
+impl TableView<'_> {
+    pub(super) fn ui(&mut self, ui: &mut Ui) {
+        ui.visuals_mut().collapsing_header_frame = true;
+        let id_salt = Id::new("SpectrumTable");
+        let height = ui.text_style_height(&TextStyle::Heading);
+        let num_rows = self.data_frame.height() as _;
+        Table::new()
+            .id_salt(id_salt)
+            .num_rows(num_rows)
+            .columns(vec![
+                Column::default().resizable(self.settings.resizable);
+                LEN
+            ])
+            .num_sticky_cols(self.settings.sticky)
+            .headers([HeaderRow::new(height)])
+            .auto_size_mode(AutoSizeMode::OnParentResize)
+            .show(ui, self);
+    }
+
+    fn header_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) {
+        if self.settings.truncate {
+            ui.style_mut().wrap_mode = Some(TextWrapMode::Truncate);
+        }
+        match (row, column) {
+            (0, INDEX) => {
+                ui.heading(localize!("index"));
+            }
+            (0, FA) => {
+                ui.heading(localize!("fatty-acid"));
+            }
+            (0, NAME) => {
+                ui.heading(localize!("spectrum"))
+                    .on_hover_text(localize!("spectrum-name-description"));
+            }
+            (0, PEAKS) => {
+                ui.heading(localize!("spectrum-peaks"));
+            }
+            (0, MATCH) => {
+                ui.heading(localize!("match"))
+                    .on_hover_text(localize!("match-description"));
+            }
+            (0, REVERSE) => {
+                ui.heading(localize!("reverse"))
+                    .on_hover_text(localize!("reverse-description"));
+            }
+            _ => {}
+        }
+    }
+
+    fn body_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) -> PolarsResult<()> {
+        match (row, column) {
+            (row, INDEX) => {
+                let indices = self.data_frame["Index"].u32()?;
+                let value = indices.get(row).unwrap();
+                let selected = self.selected == Some(row);
+                if ui
+                    .selectable_label(selected, value.to_string())
+                    .on_hover_text(localize!("spectrum"))
+                    .clicked()
+                {
+                    self.selected = if selected { None } else { Some(row) };
+                }
+            }
+            (row, FA) => {
+                if let Some(fatty_acid) = self.data_frame["FattyAcid"].fatty_acid().get(row)? {
+                    let substituents = self.data_frame["FattyAcid"].substituents(row)?;
+                    ui.label(format!("{substituents}{:#}", fatty_acid.display(COMMON)));
+                }
+            }
+            (row, NAME) => {
+                if let Some(name) = self.data_frame["Name"].str()?.get(row) {
+                    ui.label(name);
+                }
+            }
+            (row, PEAKS) => {
+                if let Some(peaks) = self.data_frame["MassToCharge"].list()?.get_as_series(row) {
+                    ui.label(peaks.len().to_string());
+                }
+            }
+            (row, MATCH) => {
+                let value = self.data_frame["Match"].f64()?.get(row);
+                ui.add(FloatValue::new(value).precision(Some(self.settings.precision)));
+            }
+            (row, REVERSE) => {
+                let value = self.data_frame["Reverse"].f64()?.get(row);
+                ui.add(FloatValue::new(value).precision(Some(self.settings.precision)));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl TableDelegate for TableView<'_> {
+    fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.header_cell_content_ui(ui, cell.row_nr, cell.group_index)
+            });
+    }
+
+    fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        if cell.row_nr % 2 == 0 {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
+        }
+        Frame::none()
+            .inner_margin(Margin::symmetric(MARGIN.x, MARGIN.y))
+            .show(ui, |ui| {
+                self.body_cell_content_ui(ui, cell.row_nr as _, cell.col_nr)
+                    .unwrap()
+            });
+    }
+}
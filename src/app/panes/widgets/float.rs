@@ -19,10 +19,6 @@ impl FloatValue {
         }
     }
 
-    pub fn disable(self, disable: bool) -> Self {
-        Self { disable, ..self }
-    }
-
     pub fn hover(self) -> Self {
         Self {
             hover: true,
@@ -30,10 +26,6 @@ impl FloatValue {
         }
     }
 
-    pub fn percent(self, percent: bool) -> Self {
-        Self { percent, ..self }
-    }
-
     pub fn precision(self, precision: Option<usize>) -> Self {
         Self { precision, ..self }
     }
@@ -1 +1,2 @@
 pub(crate) mod float;
+pub(crate) mod status;
@@ -0,0 +1,49 @@
+use crate::app::{computers::Status, localize};
+use egui::{Response, RichText, Ui, Widget};
+use egui_phosphor::regular::{PROHIBIT, STOP, WARNING};
+
+/// Computation status widget
+///
+/// A spinner with a stop button while the task is pending, a warning if it
+/// failed and nothing once it is done.
+#[derive(Clone, Copy, Debug)]
+pub struct StatusWidget<'a> {
+    pub status: Option<&'a Status>,
+}
+
+impl<'a> StatusWidget<'a> {
+    pub fn new(status: Option<&'a Status>) -> Self {
+        Self { status }
+    }
+}
+
+impl Widget for StatusWidget<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.horizontal(|ui| match self.status {
+            Some(Status::Pending(interrupt)) => {
+                ui.spinner().on_hover_text(localize!("computing"));
+                if ui
+                    .button(RichText::new(STOP).heading())
+                    .on_hover_text(localize!("interrupt"))
+                    .clicked()
+                {
+                    interrupt.interrupt();
+                }
+            }
+            Some(Status::Failed(error)) => {
+                ui.label(
+                    RichText::new(WARNING)
+                        .heading()
+                        .color(ui.visuals().error_fg_color),
+                )
+                .on_hover_text(error);
+            }
+            Some(Status::Interrupted) => {
+                ui.label(RichText::new(PROHIBIT).heading())
+                    .on_hover_text(localize!("interrupted"));
+            }
+            Some(Status::Ready(_)) | None => {}
+        })
+        .response
+    }
+}
@@ -0,0 +1,63 @@
+use super::{data::Data, panes::Pane};
+use anyhow::Result;
+use egui_tiles::Tree;
+use ron::{extensions::Extensions, ser::PrettyConfig};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Project file extension
+pub(crate) const EXTENSION: &str = "amal";
+
+const RECENT: usize = 8;
+
+/// Project
+///
+/// Data, panes layout and every pane's control saved together.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct Project {
+    pub(crate) data: Data,
+    pub(crate) tree: Tree<Pane>,
+}
+
+impl Project {
+    pub(crate) fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(ron::de::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub(crate) fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let contents = ron::ser::to_string_pretty(
+            self,
+            PrettyConfig::default().extensions(Extensions::IMPLICIT_SOME),
+        )?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// Recent projects
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Recent(VecDeque<PathBuf>);
+
+impl Recent {
+    pub(crate) fn push(&mut self, path: PathBuf) {
+        self.0.retain(|recent| recent != &path);
+        self.0.push_front(path);
+        self.0.truncate(RECENT);
+    }
+
+    pub(crate) fn remove(&mut self, path: &Path) {
+        self.0.retain(|recent| recent != path);
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &PathBuf> {
+        self.0.iter()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
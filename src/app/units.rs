@@ -0,0 +1,223 @@
+use super::{localize, text::Text};
+use egui::{ComboBox, Ui, WidgetText};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use uom::si::{
+    f64::Time,
+    time::{self, millisecond, minute, second},
+};
+
+/// Retention time units
+///
+/// The source units are the units the data is stored in, the display units
+/// are the units of tables, plots and exports.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) struct Units {
+    pub(crate) source: TimeUnits,
+    pub(crate) display: TimeUnits,
+}
+
+impl Units {
+    pub(crate) const fn new() -> Self {
+        Self {
+            source: TimeUnits::Minute,
+            display: TimeUnits::Minute,
+        }
+    }
+
+    /// Display units per source unit
+    pub(crate) fn factor(self) -> f64 {
+        self.source.convert(1.0, self.display)
+    }
+
+    /// Grid rows with the source and display units selectors
+    pub(crate) fn ui(&mut self, ui: &mut Ui) {
+        ui.label(localize!("source-units"))
+            .on_hover_text(localize!("source-units-description"));
+        time_units(ui, "SourceUnits", &mut self.source);
+        ui.end_row();
+
+        ui.label(localize!("display-units"))
+            .on_hover_text(localize!("display-units-description"));
+        time_units(ui, "DisplayUnits", &mut self.display);
+        ui.end_row();
+    }
+}
+
+impl Default for Units {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn time_units(ui: &mut Ui, id_salt: &str, current_value: &mut TimeUnits) {
+    ComboBox::from_id_salt(ui.auto_id_with(id_salt))
+        .selected_text(current_value.text())
+        .show_ui(ui, |ui| {
+            for selected_value in [TimeUnits::Millisecond, TimeUnits::Second, TimeUnits::Minute] {
+                ui.selectable_value(current_value, selected_value, selected_value.text())
+                    .on_hover_text(selected_value.hover_text());
+            }
+        })
+        .response
+        .on_hover_text(current_value.hover_text());
+}
+
+/// Time units
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum TimeUnits {
+    Millisecond,
+    Second,
+    #[default]
+    Minute,
+}
+
+impl TimeUnits {
+    pub fn abbreviation(&self) -> &'static str {
+        time::Units::from(*self).abbreviation()
+    }
+
+    pub fn singular(&self) -> &'static str {
+        time::Units::from(*self).singular()
+    }
+
+    pub fn plural(&self) -> &'static str {
+        time::Units::from(*self).plural()
+    }
+
+    /// Converts the value from these units to the target units
+    pub fn convert(self, value: f64, target: TimeUnits) -> f64 {
+        let time = match self {
+            Self::Millisecond => Time::new::<millisecond>(value),
+            Self::Second => Time::new::<second>(value),
+            Self::Minute => Time::new::<minute>(value),
+        };
+        match target {
+            Self::Millisecond => time.get::<millisecond>(),
+            Self::Second => time.get::<second>(),
+            Self::Minute => time.get::<minute>(),
+        }
+    }
+}
+
+impl Text for TimeUnits {
+    fn text(&self) -> String {
+        match self {
+            Self::Millisecond => localize!("millisecond"),
+            Self::Second => localize!("second"),
+            Self::Minute => localize!("minute"),
+        }
+    }
+
+    fn hover_text(&self) -> String {
+        match self {
+            Self::Millisecond => localize!("millisecond-description"),
+            Self::Second => localize!("second-description"),
+            Self::Minute => localize!("minute-description"),
+        }
+    }
+}
+
+impl From<TimeUnits> for time::Units {
+    fn from(value: TimeUnits) -> Self {
+        match value {
+            TimeUnits::Millisecond => time::Units::millisecond(millisecond),
+            TimeUnits::Second => time::Units::second(second),
+            TimeUnits::Minute => time::Units::minute(minute),
+        }
+    }
+}
+
+/// Retention time format
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct RetentionTimeFormat {
+    value: f64,
+    precision: Option<usize>,
+    units: Option<TimeUnits>,
+}
+
+impl RetentionTimeFormat {
+    pub(crate) fn new(value: f64) -> Self {
+        Self {
+            value,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn units(self, units: TimeUnits) -> Self {
+        Self {
+            units: Some(units),
+            ..self
+        }
+    }
+}
+
+impl Display for RetentionTimeFormat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let value = self.value;
+        if let Some(precision) = self.precision {
+            write!(f, "{value:.precision$}")?;
+        } else {
+            write!(f, "{value}")?;
+        }
+        if let Some(units) = self.units {
+            write!(f, " {}", units.text())?;
+        }
+        Ok(())
+    }
+}
+
+impl From<RetentionTimeFormat> for WidgetText {
+    fn from(value: RetentionTimeFormat) -> Self {
+        value.to_string().into()
+    }
+}
+
+/// Mass to charge settings
+#[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct MassToCharge {
+    pub(crate) precision: usize,
+}
+
+impl MassToCharge {
+    pub(crate) const fn new() -> Self {
+        Self { precision: 1 }
+    }
+
+    pub(crate) fn format(self, value: f64) -> MassToChargeFormat {
+        MassToChargeFormat {
+            value,
+            precision: Some(self.precision),
+        }
+    }
+}
+
+impl Default for MassToCharge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Mass to charge format
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct MassToChargeFormat {
+    value: f64,
+    precision: Option<usize>,
+}
+
+impl Display for MassToChargeFormat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let value = self.value;
+        if let Some(precision) = self.precision {
+            write!(f, "{value:.precision$}")
+        } else {
+            write!(f, "{value}")
+        }
+    }
+}
+
+impl From<MassToChargeFormat> for WidgetText {
+    fn from(value: MassToChargeFormat) -> Self {
+        value.to_string().into()
+    }
+}
@@ -1,10 +1,11 @@
 use anyhow::Result;
 use polars::prelude::*;
 use ron::{extensions::Extensions, ser::PrettyConfig};
-use std::{fs::write, iter::empty, path::Path};
-use walkdir::WalkDir;
+use std::{fs::write, iter::empty};
 // use special::expressions::fatty_acid::{ExprExt, FattyAcid as _};
 
+/// Reference ranges of the fatty acids, drop the `df.amal.ron` on a
+/// composition pane to compare the samples with them
 fn main() -> Result<()> {
     let data_frame = df! {
         "FattyAcid" => df! {
@@ -296,28 +297,35 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn format(path: &Path) -> Result<()> {
-    let source = std::fs::read_to_string(path)?;
-    let data_frame: DataFrame = ron::de::from_str(&source)?;
-    let formated = ron::ser::to_string_pretty(
-        &data_frame,
-        PrettyConfig::default().extensions(Extensions::IMPLICIT_SOME),
-    )?;
-    if source != formated {
-        // std::fs::copy(path, format!("{path}.bk"))?;
-        std::fs::write(path, formated)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use walkdir::WalkDir;
+
+    fn format(path: &Path) -> Result<()> {
+        let source = std::fs::read_to_string(path)?;
+        let data_frame: DataFrame = ron::de::from_str(&source)?;
+        let formated = ron::ser::to_string_pretty(
+            &data_frame,
+            PrettyConfig::default().extensions(Extensions::IMPLICIT_SOME),
+        )?;
+        if source != formated {
+            // std::fs::copy(path, format!("{path}.bk"))?;
+            std::fs::write(path, formated)?;
+        }
+        Ok(())
     }
-    Ok(())
-}
 
-#[test]
-fn test_format() -> Result<()> {
-    for entry in WalkDir::new("input/data/") {
-        let entry = entry?;
-        if entry.metadata()?.is_file() {
-            println!("{}", entry.path().display());
-            format(entry.path())?;
+    #[test]
+    fn test_format() -> Result<()> {
+        for entry in WalkDir::new("input/data/") {
+            let entry = entry?;
+            if entry.metadata()?.is_file() {
+                println!("{}", entry.path().display());
+                format(entry.path())?;
+            }
         }
+        Ok(())
     }
-    Ok(())
 }
@@ -1,8 +1,7 @@
 use anyhow::Result;
 use polars::prelude::*;
 use ron::{extensions::Extensions, ser::PrettyConfig};
-use std::{fs::write, iter::empty, path::Path};
-use walkdir::WalkDir;
+use std::fs::{read_to_string, write};
 
 // lazy_frame = lazy_frame
 // .with_row_index("IDX", None)
@@ -79,8 +78,8 @@ use walkdir::WalkDir;
 // data::save("df.ron", data::Format::Ron, data_frame).unwrap();
 // std::process::exit(0);
 fn main() -> Result<()> {
-    // let content = include_str!("../../df.ron");
-    let data_frame: DataFrame = ron::de::from_str(content).unwrap();
+    let content = read_to_string("df.ron")?;
+    let data_frame: DataFrame = ron::de::from_str(&content)?;
     println!("data_frame: {data_frame}");
     // println!(
     //     "data_frame: {}",
@@ -1,15 +1,42 @@
-pub(crate) mod atoms {
-    use molecule::atom::isotopes::*;
-
-    pub(crate) const C: C = C::Twelve;
-    pub(crate) const H: H = H::One;
-    pub(crate) const O: O = O::Sixteen;
-}
-
 pub(crate) mod relative_atomic_mass {
     use molecule::atom::isotopes::*;
 
     pub(crate) const C: f64 = C::Twelve.relative_atomic_mass().value;
     pub(crate) const H: f64 = H::One.relative_atomic_mass().value;
     pub(crate) const O: f64 = O::Sixteen.relative_atomic_mass().value;
+    pub(crate) const N: f64 = N::Fourteen.relative_atomic_mass().value;
+    pub(crate) const SI: f64 = Si::TwentyEight.relative_atomic_mass().value;
+}
+
+/// Natural isotopes: relative atomic mass and abundance, ordered by the
+/// nominal mass shift from the lightest isotope
+///
+/// The masses come from `molecule`, the abundances are the IUPAC
+/// representative isotopic compositions: the app relies only on the isotope
+/// masses of `molecule`, the same as [`relative_atomic_mass`].
+pub(crate) mod isotopes {
+    use molecule::atom::isotopes::*;
+
+    pub(crate) const C: [(f64, f64); 2] = [
+        (C::Twelve.relative_atomic_mass().value, 0.9893),
+        (C::Thirteen.relative_atomic_mass().value, 0.0107),
+    ];
+    pub(crate) const H: [(f64, f64); 2] = [
+        (H::One.relative_atomic_mass().value, 0.999_885),
+        (H::Two.relative_atomic_mass().value, 0.000_115),
+    ];
+    pub(crate) const O: [(f64, f64); 3] = [
+        (O::Sixteen.relative_atomic_mass().value, 0.997_57),
+        (O::Seventeen.relative_atomic_mass().value, 0.000_38),
+        (O::Eighteen.relative_atomic_mass().value, 0.002_05),
+    ];
+    pub(crate) const N: [(f64, f64); 2] = [
+        (N::Fourteen.relative_atomic_mass().value, 0.996_36),
+        (N::Fifteen.relative_atomic_mass().value, 0.003_64),
+    ];
+    pub(crate) const SI: [(f64, f64); 3] = [
+        (Si::TwentyEight.relative_atomic_mass().value, 0.922_23),
+        (Si::TwentyNine.relative_atomic_mass().value, 0.046_85),
+        (Si::Thirty.relative_atomic_mass().value, 0.030_92),
+    ];
 }
@@ -1,5 +1,4 @@
 #![feature(decl_macro)]
-#![feature(try_trait_v2)]
 
 pub use app::App;
 
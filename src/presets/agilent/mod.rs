@@ -1,6 +1,19 @@
+use super::{Metadata, Preset};
 use polars::frame::DataFrame;
 use std::sync::LazyLock;
 
 pub(crate) static AGILENT: LazyLock<DataFrame> = LazyLock::new(|| {
-    ron::de::from_str(include_str!("Agilent.ron")).expect("deserialize Agilent.ron")
+    bincode::deserialize(include_bytes!("Agilent.bin")).expect("deserialize Agilent.bin")
 });
+
+pub(crate) fn preset() -> Preset {
+    Preset {
+        name: "IPPRAS/Agilent".to_owned(),
+        // The column and the carrier gas of the measurements are not known
+        metadata: Metadata {
+            program: "60–150 °C onset, 1–10 °C/min".to_owned(),
+            ..Metadata::default()
+        },
+        data_frame: AGILENT.clone(),
+    }
+}
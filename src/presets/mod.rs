@@ -1,3 +1,36 @@
-pub(crate) use self::agilent::AGILENT;
+use polars::frame::DataFrame;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// Built-in presets
+pub(crate) static PRESETS: LazyLock<Vec<Preset>> = LazyLock::new(|| vec![agilent::preset()]);
+
+/// Preset
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct Preset {
+    pub(crate) name: String,
+    pub(crate) metadata: Metadata,
+    pub(crate) data_frame: DataFrame,
+}
+
+/// Preset metadata
+///
+/// The column fields are unset when they are not known.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub(crate) struct Metadata {
+    /// Column name
+    pub(crate) column: Option<String>,
+    /// Stationary phase
+    pub(crate) phase: Option<String>,
+    /// Column length, m
+    pub(crate) length: Option<f64>,
+    /// Column inner diameter, mm
+    pub(crate) diameter: Option<f64>,
+    /// Film thickness, µm
+    pub(crate) film: Option<f64>,
+    pub(crate) carrier: String,
+    pub(crate) program: String,
+}
 
 mod agilent;
@@ -0,0 +1,301 @@
+use super::{column::mode::ColumnExt as _, program::Program};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Correction of the retention time drift between the runs
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum Correction {
+    /// Least squares line through the anchors
+    #[default]
+    Linear,
+    /// Broken line through the anchors, the end segments are extrapolated
+    Piecewise,
+}
+
+impl Correction {
+    pub const ALL: [Self; 2] = [Self::Linear, Self::Piecewise];
+
+    /// Maps the time of the run onto the consensus by the `(run, consensus)`
+    /// anchors sorted by the run time
+    ///
+    /// A single anchor shifts the run, no anchors leave it as is.
+    pub fn apply(self, anchors: &[(f64, f64)], time: f64) -> f64 {
+        match anchors {
+            [] => time,
+            [(run, consensus)] => time + consensus - run,
+            _ => match self {
+                Self::Linear => linear(anchors, time),
+                Self::Piecewise => {
+                    let index = anchors
+                        .partition_point(|&(run, _)| run <= time)
+                        .clamp(1, anchors.len() - 1);
+                    linear(&anchors[index - 1..=index], time)
+                }
+            },
+        }
+    }
+}
+
+/// Least squares line, the mean shift when the run times coincide
+fn linear(anchors: &[(f64, f64)], time: f64) -> f64 {
+    let count = anchors.len() as f64;
+    let (run, consensus) = anchors
+        .iter()
+        .fold((0.0, 0.0), |(x, y), &(run, consensus)| {
+            (x + run, y + consensus)
+        });
+    let (run, consensus) = (run / count, consensus / count);
+    let (covariance, variance) =
+        anchors
+            .iter()
+            .fold((0.0, 0.0), |(covariance, variance), &(x, y)| {
+                (
+                    covariance + (x - run) * (y - consensus),
+                    variance + (x - run).powi(2),
+                )
+            });
+    if variance > 0.0 {
+        consensus + covariance / variance * (time - run)
+    } else {
+        time + consensus - run
+    }
+}
+
+/// Aligns the runs of the retention times
+///
+/// A run is the replicate of the time, the list position without the
+/// replicate indices. Every run of the mode is mapped onto the consensus, the
+/// mean of the runs, by the anchor rows. Null times stay null in place.
+pub fn align(
+    mode: &Column,
+    retention_time: &Column,
+    replicate: Option<&Column>,
+    anchors: &BooleanChunked,
+    correction: Correction,
+) -> PolarsResult<Column> {
+    let list = retention_time.list()?;
+    let replicates = replicate.map(|replicate| replicate.list()).transpose()?;
+    // Run and time of every list position
+    let mut values = Vec::with_capacity(list.len());
+    for row in 0..list.len() {
+        let Some(series) = list.get_as_series(row) else {
+            values.push(None);
+            continue;
+        };
+        let times = series.cast(&DataType::Float64)?;
+        let runs: Vec<Option<usize>> =
+            match replicates.and_then(|replicates| replicates.get_as_series(row)) {
+                Some(runs) => runs
+                    .cast(&DataType::UInt32)?
+                    .u32()?
+                    .into_iter()
+                    .map(|run| run.map(|run| run as usize))
+                    .collect(),
+                None => (0..times.len()).map(Some).collect(),
+            };
+        values.push(Some(runs.into_iter().zip(times.f64()?).collect::<Vec<_>>()));
+    }
+    // Rows of every mode, compared by the whole program
+    let mut modes = Vec::<(Program, Vec<usize>)>::new();
+    for row in 0..list.len() {
+        let program = mode.mode().program(row)?;
+        match modes.iter_mut().find(|(mode, _)| *mode == program) {
+            Some((_, rows)) => rows.push(row),
+            None => modes.push((program, vec![row])),
+        }
+    }
+    let mut aligned: Vec<Option<Vec<Option<f64>>>> = values
+        .iter()
+        .map(|values| Some(values.as_ref()?.iter().map(|&(_, time)| time).collect()))
+        .collect();
+    for (_, rows) in &modes {
+        // Anchor points `(run time, consensus)` of every run
+        let mut points = BTreeMap::<usize, Vec<(f64, f64)>>::new();
+        for &row in rows {
+            if anchors.get(row) != Some(true) {
+                continue;
+            }
+            let Some(values) = &values[row] else {
+                continue;
+            };
+            let Some(consensus) = mean(values.iter().filter_map(|&(_, time)| time)) else {
+                continue;
+            };
+            let mut runs = BTreeMap::<usize, Vec<f64>>::new();
+            for &(run, time) in values {
+                if let (Some(run), Some(time)) = (run, time) {
+                    runs.entry(run).or_default().push(time);
+                }
+            }
+            for (run, times) in runs {
+                if let Some(time) = mean(times) {
+                    points.entry(run).or_default().push((time, consensus));
+                }
+            }
+        }
+        for points in points.values_mut() {
+            points.sort_by(|left, right| left.0.total_cmp(&right.0));
+        }
+        for &row in rows {
+            let (Some(values), Some(times)) = (&values[row], &mut aligned[row]) else {
+                continue;
+            };
+            for (&(run, _), time) in values.iter().zip(times) {
+                let (Some(run), Some(time)) = (run, time) else {
+                    continue;
+                };
+                if let Some(points) = points.get(&run) {
+                    *time = correction.apply(points, *time);
+                }
+            }
+        }
+    }
+    Ok(aligned
+        .into_iter()
+        .map(|values| values.map(|values| Series::new(PlSmallStr::EMPTY, values)))
+        .collect::<ListChunked>()
+        .with_name(retention_time.name().clone())
+        .into_column())
+}
+
+/// Mean of the finite values
+fn mean(values: impl IntoIterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values
+        .into_iter()
+        .fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    let mean = sum / count as f64;
+    mean.is_finite().then_some(mean)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-9;
+
+    #[test]
+    fn apply() {
+        for correction in Correction::ALL {
+            assert_eq!(correction.apply(&[], 5.0), 5.0);
+            assert_eq!(correction.apply(&[(4.0, 5.0)], 10.0), 11.0);
+            // Coincident run times shift by the mean
+            assert_eq!(correction.apply(&[(4.0, 5.0), (4.0, 6.0)], 10.0), 11.5);
+        }
+        let anchors = [(1.0, 1.0), (2.0, 3.0), (3.0, 3.0)];
+        // Least squares line y = 7/3 + (x - 2)
+        assert!((Correction::Linear.apply(&anchors, 2.0) - 2.333333333).abs() < 1e-6);
+        assert!((Correction::Linear.apply(&anchors, 4.0) - 4.333333333).abs() < 1e-6);
+        assert_eq!(Correction::Piecewise.apply(&anchors, 1.5), 2.0);
+        assert_eq!(Correction::Piecewise.apply(&anchors, 2.5), 3.0);
+        // The end segments are extrapolated
+        assert_eq!(Correction::Piecewise.apply(&anchors, 0.0), -1.0);
+        assert_eq!(Correction::Piecewise.apply(&anchors, 4.0), 3.0);
+    }
+
+    /// Modes of the same program up to the maximum temperature
+    fn mode(max_temperatures: &[f64]) -> PolarsResult<Column> {
+        let segments = df!("Hold" => [0.0], "Rate" => [2.0], "Target" => [250.0])?
+            .into_struct(PlSmallStr::EMPTY)
+            .into_series();
+        let height = max_temperatures.len();
+        let mut mode = df!(
+            "OnsetTemperature" => vec![150.0; height],
+            "TemperatureStep" => vec![2.0; height],
+        )?;
+        mode.with_column(Series::new("Segments".into(), vec![segments; height]))?;
+        mode.with_column(Series::new("MaxTemperature".into(), max_temperatures))?;
+        Ok(mode.into_struct("Mode".into()).into_column())
+    }
+
+    /// Single mode, two anchors and a fatty acid missing from a run
+    fn columns(times: Vec<Option<f64>>) -> PolarsResult<(Column, Column, BooleanChunked)> {
+        let mode = mode(&[250.0; 3])?;
+        let retention_time = Column::new(
+            "RetentionTime".into(),
+            [
+                Series::new(PlSmallStr::EMPTY, [10.0, 10.2, 9.8]),
+                Series::new(PlSmallStr::EMPTY, [20.0, 20.4, 19.6]),
+                Series::new(PlSmallStr::EMPTY, times),
+            ],
+        );
+        let anchors = BooleanChunked::new("Anchor".into(), [true, true, false]);
+        Ok((mode, retention_time, anchors))
+    }
+
+    fn values(column: &Column, row: usize) -> PolarsResult<Vec<Option<f64>>> {
+        let series = column.list()?.get_as_series(row).unwrap();
+        Ok(series.f64()?.into_iter().collect())
+    }
+
+    fn assert_close(values: Vec<Option<f64>>, expected: &[Option<f64>]) {
+        assert_eq!(values.len(), expected.len(), "{values:?}");
+        for (value, expected) in values.into_iter().zip(expected) {
+            match (value, expected) {
+                (Some(value), Some(expected)) => {
+                    assert!((value - expected).abs() < EPSILON, "{value} {expected}")
+                }
+                (value, expected) => assert_eq!(value, *expected),
+            }
+        }
+    }
+
+    #[test]
+    fn nulls() -> PolarsResult<()> {
+        let (mode, retention_time, anchors) = columns(vec![Some(15.0), None, Some(14.7)])?;
+        for correction in Correction::ALL {
+            let aligned = align(&mode, &retention_time, None, &anchors, correction)?;
+            assert_close(values(&aligned, 0)?, &[Some(10.0); 3]);
+            assert_close(values(&aligned, 1)?, &[Some(20.0); 3]);
+            assert_close(values(&aligned, 2)?, &[Some(15.0), None, Some(15.0)]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn replicates() -> PolarsResult<()> {
+        let (mode, retention_time, anchors) = columns(vec![Some(15.0), Some(14.7)])?;
+        let replicate = Column::new(
+            "Replicate".into(),
+            [
+                Series::new(PlSmallStr::EMPTY, [0u32, 1, 2]),
+                Series::new(PlSmallStr::EMPTY, [0u32, 1, 2]),
+                Series::new(PlSmallStr::EMPTY, [0u32, 2]),
+            ],
+        );
+        let aligned = align(
+            &mode,
+            &retention_time,
+            Some(&replicate),
+            &anchors,
+            Correction::Linear,
+        )?;
+        assert_close(values(&aligned, 2)?, &[Some(15.0), Some(15.0)]);
+        Ok(())
+    }
+
+    /// Modes that differ in the maximum temperature only are aligned apart
+    #[test]
+    fn modes() -> PolarsResult<()> {
+        let mode = mode(&[250.0, 250.0, 250.0, 240.0, 240.0, 240.0])?;
+        let retention_time = Column::new(
+            "RetentionTime".into(),
+            [
+                Series::new(PlSmallStr::EMPTY, [10.0, 10.2, 9.8]),
+                Series::new(PlSmallStr::EMPTY, [20.0, 20.4, 19.6]),
+                Series::new(PlSmallStr::EMPTY, [15.0, 15.3, 14.7]),
+                Series::new(PlSmallStr::EMPTY, [10.0, 11.0, 12.0]),
+                Series::new(PlSmallStr::EMPTY, [20.0, 21.0, 22.0]),
+                Series::new(PlSmallStr::EMPTY, [14.0, 15.0, 16.0]),
+            ],
+        );
+        let anchors = BooleanChunked::new("Anchor".into(), [true, true, false, true, true, false]);
+        for correction in Correction::ALL {
+            let aligned = align(&mode, &retention_time, None, &anchors, correction)?;
+            assert_close(values(&aligned, 2)?, &[Some(15.0), Some(15.0), Some(15.0)]);
+            assert_close(values(&aligned, 3)?, &[Some(11.0); 3]);
+            assert_close(values(&aligned, 5)?, &[Some(15.0); 3]);
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::special::program::{DEFAULT_MAX_TEMPERATURE, Program, Segment};
 use polars::prelude::*;
 use std::ops::RangeInclusive;
 
@@ -13,22 +14,67 @@ impl ColumnExt for Column {
 }
 
 /// Mode series
+///
+/// A two-field mode without `Segments` and `MaxTemperature` is a single ramp.
 #[derive(Clone)]
 pub struct ModeSeries {
     pub onset_temperature: Series,
     pub temperature_step: Series,
+    pub segments: Option<Series>,
+    pub max_temperature: Option<Series>,
 }
 
 impl ModeSeries {
     pub fn new(column: &Column) -> PolarsResult<Self> {
         let onset_temperature = column.struct_()?.field_by_name("OnsetTemperature")?;
         let temperature_step = column.struct_()?.field_by_name("TemperatureStep")?;
+        let segments = column.struct_()?.field_by_name("Segments").ok();
+        let max_temperature = column.struct_()?.field_by_name("MaxTemperature").ok();
         Ok(Self {
             onset_temperature,
             temperature_step,
+            segments,
+            max_temperature,
         })
     }
 
+    /// Temperature program of the row
+    pub fn program(&self, row: usize) -> PolarsResult<Program> {
+        let onset_temperature = self.onset_temperature.f64()?.get(row).unwrap_or_default();
+        let max_temperature = match &self.max_temperature {
+            Some(max_temperature) => max_temperature.f64()?.get(row),
+            None => None,
+        }
+        .unwrap_or(DEFAULT_MAX_TEMPERATURE);
+        let Some(segments) = &self.segments else {
+            let temperature_step = self.temperature_step.f64()?.get(row).unwrap_or_default();
+            return Ok(Program::ramp(
+                onset_temperature,
+                temperature_step,
+                max_temperature,
+            ));
+        };
+        let mut program = Program {
+            onset_temperature,
+            segments: Vec::new(),
+            max_temperature,
+        };
+        if let Some(segments) = segments.list()?.get_as_series(row) {
+            let segments = segments.struct_()?;
+            let hold = segments.field_by_name("Hold")?;
+            let rate = segments.field_by_name("Rate")?;
+            let target = segments.field_by_name("Target")?;
+            for ((hold, rate), target) in hold.f64()?.iter().zip(rate.f64()?).zip(target.f64()?) {
+                program.segments.push(Segment {
+                    hold: hold.unwrap_or_default(),
+                    rate: rate.unwrap_or_default(),
+                    target: target.unwrap_or(max_temperature),
+                });
+            }
+        }
+        Ok(program)
+    }
+
     pub fn onset_temperature_range(&self) -> RangeInclusive<f64> {
         if let Ok(onset_temperatures) = self.onset_temperature.f64() {
             if let Some((min, max)) = onset_temperatures.min_max() {
@@ -47,11 +93,11 @@ impl ModeSeries {
         0.0..=0.0
     }
 
-    pub fn onset_temperature(&self) -> PolarsResult<OnsetTemperature> {
+    pub fn onset_temperature(&self) -> PolarsResult<OnsetTemperature<'_>> {
         OnsetTemperature::new(&self.onset_temperature)
     }
 
-    pub fn temperature_step(&self) -> PolarsResult<TemperatureStep> {
+    pub fn temperature_step(&self) -> PolarsResult<TemperatureStep<'_>> {
         TemperatureStep::new(&self.temperature_step)
     }
 }
@@ -67,13 +113,6 @@ impl<'a> OnsetTemperature<'a> {
 }
 
 impl OnsetTemperature<'_> {
-    pub fn range(&self) -> RangeInclusive<f64> {
-        if let Some((min, max)) = self.0.min_max() {
-            return min..=max;
-        }
-        0.0..=0.0
-    }
-
     pub fn unique(&self) -> Float64Chunked {
         self.0.unique().unwrap()
     }
@@ -90,14 +129,142 @@ impl<'a> TemperatureStep<'a> {
 }
 
 impl TemperatureStep<'_> {
-    pub fn range(&self) -> RangeInclusive<f64> {
-        if let Some((min, max)) = self.0.min_max() {
-            return min..=max;
-        }
-        0.0..=0.0
-    }
-
     pub fn unique(&self) -> Float64Chunked {
         self.0.unique().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Modes with the same onset temperature and step that differ in the
+    /// segments only: the first and the third rows share a mode.
+    fn data_frame() -> PolarsResult<DataFrame> {
+        let segments = |segments: &[(f64, f64, f64)]| -> PolarsResult<Series> {
+            Ok(df!(
+                "Hold" => segments.iter().map(|segment| segment.0).collect::<Vec<_>>(),
+                "Rate" => segments.iter().map(|segment| segment.1).collect::<Vec<_>>(),
+                "Target" => segments.iter().map(|segment| segment.2).collect::<Vec<_>>(),
+            )?
+            .into_struct(PlSmallStr::EMPTY)
+            .into_series())
+        };
+        let first = segments(&[(1.0, 2.0, 200.0)])?;
+        let second = segments(&[(1.0, 2.0, 200.0), (5.0, 4.0, 240.0)])?;
+        let mut mode = df!(
+            "OnsetTemperature" => [150.0, 150.0, 150.0],
+            "TemperatureStep" => [2.0, 2.0, 2.0],
+        )?;
+        mode.with_column(Series::new(
+            "Segments".into(),
+            [first.clone(), second, first],
+        ))?;
+        mode.with_column(Series::new("MaxTemperature".into(), [250.0, 250.0, 250.0]))?;
+        let mode = mode.into_struct("Mode".into()).into_series();
+        DataFrame::new(vec![
+            mode.into_column(),
+            Column::new("Value".into(), [1.0, 2.0, 3.0]),
+        ])
+    }
+
+    #[test]
+    fn over() -> PolarsResult<()> {
+        let data_frame = data_frame()?
+            .lazy()
+            .select([col("Value").sum().over([col("Mode")])])
+            .collect()?;
+        let values: Vec<_> = data_frame["Value"].f64()?.into_no_null_iter().collect();
+        assert_eq!(values, [4.0, 2.0, 4.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn group_by() -> PolarsResult<()> {
+        let data_frame = data_frame()?
+            .lazy()
+            .group_by_stable([col("Mode")])
+            .agg([col("Value").sum()])
+            .collect()?;
+        let values: Vec<_> = data_frame["Value"].f64()?.into_no_null_iter().collect();
+        assert_eq!(values, [4.0, 2.0]);
+        let modes = data_frame["Mode"].mode();
+        assert_eq!(modes.program(0)?.segments.len(), 1);
+        assert_eq!(modes.program(1)?.segments.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn unique() -> PolarsResult<()> {
+        let data_frame = data_frame()?.unique_stable(
+            Some(&["Mode".to_owned()]),
+            UniqueKeepStrategy::First,
+            None,
+        )?;
+        let values: Vec<_> = data_frame["Value"].f64()?.into_no_null_iter().collect();
+        assert_eq!(values, [1.0, 2.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn sort() -> PolarsResult<()> {
+        let data_frame = data_frame()?.sort(["Mode"], Default::default())?;
+        let modes = data_frame["Mode"].mode();
+        let segments: Vec<_> = (0..data_frame.height())
+            .map(|row| Ok(modes.program(row)?.segments.len()))
+            .collect::<PolarsResult<_>>()?;
+        assert_eq!(segments, [1, 1, 2]);
+        Ok(())
+    }
+
+    /// Hash joins on nested structs are not implemented, two frames are
+    /// matched by the mode with a diagonal concatenation and a group by.
+    #[test]
+    fn concat() -> PolarsResult<()> {
+        let data_frame = data_frame()?;
+        let left = data_frame
+            .clone()
+            .lazy()
+            .select([col("Mode"), col("Value").alias("Left")]);
+        let right = data_frame
+            .lazy()
+            .slice(1, 1)
+            .select([col("Mode"), col("Value").alias("Right")]);
+        let data_frame = concat_lf_diagonal([left, right], UnionArgs::default())?
+            .group_by_stable([col("Mode")])
+            .agg([
+                col("Left").drop_nulls().sum(),
+                col("Right").drop_nulls().first(),
+            ])
+            .collect()?;
+        let left: Vec<_> = data_frame["Left"].f64()?.into_no_null_iter().collect();
+        assert_eq!(left, [4.0, 2.0]);
+        let right: Vec<_> = data_frame["Right"].f64()?.into_iter().collect();
+        assert_eq!(right, [None, Some(2.0)]);
+        Ok(())
+    }
+
+    #[test]
+    fn program() -> PolarsResult<()> {
+        let data_frame = data_frame()?;
+        let program = data_frame["Mode"].mode().program(1)?;
+        assert_eq!(program.onset_temperature, 150.0);
+        assert_eq!(program.max_temperature, 250.0);
+        assert_eq!(
+            program.segments,
+            [
+                Segment {
+                    hold: 1.0,
+                    rate: 2.0,
+                    target: 200.0,
+                },
+                Segment {
+                    hold: 5.0,
+                    rate: 4.0,
+                    target: 240.0,
+                },
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use super::{derivative::Derivative, isotope::Formula};
+
+/// Methyl ester of the 16:0 acid (C17H34O2), the reference of the response
+/// factors
+const PALMITATE: Formula = Formula {
+    c: 17,
+    h: 34,
+    n: 0,
+    o: 2,
+    si: 0,
+};
+
+/// Theoretical FID response factor of the methyl ester relative to the 16:0
+/// methyl ester
+///
+/// The FID responds to the carbons not bound to oxygen: the carboxyl carbon
+/// and the carbinol carbon of every hydroxy group are inactive, so an ester
+/// has `C - O + 1` active carbons. The factor is the mass per active carbon
+/// (Craske and Bannon, 1988), the area times the factor is proportional to
+/// the mass. Masses are the average molecular weights.
+pub fn response_factor(ester: Formula) -> f64 {
+    mass_per_carbon(ester) / mass_per_carbon(PALMITATE)
+}
+
+/// FAME to fatty acid conversion factor (RCOOH / RCOOCH3)
+pub fn conversion_factor(acid: Formula) -> f64 {
+    acid.weight() / Derivative::Methyl.formula(acid).weight()
+}
+
+fn mass_per_carbon(ester: Formula) -> f64 {
+    ester.weight() / (ester.c + 1).saturating_sub(ester.o).max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-4;
+
+    // C18:2
+    const LINOLEIC: Formula = Formula {
+        c: 18,
+        h: 32,
+        n: 0,
+        o: 2,
+        si: 0,
+    };
+
+    #[test]
+    fn conversion() {
+        // 280.446 / 294.473, average molecular weights
+        assert!((conversion_factor(LINOLEIC) - 0.952367).abs() < EPSILON);
+        let palmitic = Formula {
+            c: 16,
+            h: 32,
+            ..PALMITATE
+        };
+        assert!((conversion_factor(palmitic) - 0.948136).abs() < EPSILON);
+    }
+
+    #[test]
+    fn response() {
+        assert_eq!(response_factor(PALMITATE), 1.0);
+        // 18:2 methyl ester: 294.473 / 18 over 270.451 / 16
+        let ester = Derivative::Methyl.formula(LINOLEIC);
+        assert!((response_factor(ester) - 0.967840).abs() < EPSILON);
+    }
+}
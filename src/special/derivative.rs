@@ -0,0 +1,135 @@
+use super::{fragment::*, isotope::Formula};
+use crate::r#const::relative_atomic_mass::{C, H, N, O, SI};
+use serde::{Deserialize, Serialize};
+
+/// Derivative of the fatty acid analyzed by GC
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum Derivative {
+    /// Methyl ester (FAME)
+    #[default]
+    Methyl,
+    /// Ethyl ester (FAEE)
+    Ethyl,
+    /// 4,4-Dimethyloxazoline
+    Dmox,
+    /// Picolinyl (3-pyridylcarbinol) ester
+    Picolinyl,
+    /// Trimethylsilyl ester
+    Trimethylsilyl,
+}
+
+impl Derivative {
+    pub const ALL: [Self; 5] = [
+        Self::Methyl,
+        Self::Ethyl,
+        Self::Dmox,
+        Self::Picolinyl,
+        Self::Trimethylsilyl,
+    ];
+
+    /// Short name of the derivative for the table headers
+    pub const fn abbreviation(self) -> &'static str {
+        match self {
+            Self::Methyl => "RCOOCH3",
+            Self::Ethyl => "RCOOC2H5",
+            Self::Dmox => "DMOX",
+            Self::Picolinyl => "Picolinyl",
+            Self::Trimethylsilyl => "RCOOSi(CH3)3",
+        }
+    }
+
+    /// Formula of the derivative of the free fatty acid
+    pub fn formula(self, acid: Formula) -> Formula {
+        match self {
+            Self::Methyl => Formula {
+                c: acid.c + 1,
+                h: acid.h + 2,
+                ..acid
+            },
+            Self::Ethyl => Formula {
+                c: acid.c + 2,
+                h: acid.h + 4,
+                ..acid
+            },
+            // RCOOH + C4H11NO (2-amino-2-methylpropanol) - 2 H2O
+            Self::Dmox => Formula {
+                c: acid.c + 4,
+                h: acid.h + 7,
+                n: acid.n + 1,
+                o: acid.o - 1,
+                ..acid
+            },
+            // RCOOH + C6H7NO (3-pyridylcarbinol) - H2O
+            Self::Picolinyl => Formula {
+                c: acid.c + 6,
+                h: acid.h + 5,
+                n: acid.n + 1,
+                ..acid
+            },
+            Self::Trimethylsilyl => Formula {
+                c: acid.c + 3,
+                h: acid.h + 8,
+                si: acid.si + 1,
+                ..acid
+            },
+        }
+    }
+
+    /// Mass difference from the free fatty acid
+    pub fn mass(self) -> f64 {
+        match self {
+            Self::Methyl => C + 2.0 * H,
+            Self::Ethyl => 2.0 * C + 4.0 * H,
+            Self::Dmox => 4.0 * C + 7.0 * H + N - O,
+            Self::Picolinyl => 6.0 * C + 5.0 * H + N,
+            Self::Trimethylsilyl => 3.0 * C + 8.0 * H + SI,
+        }
+    }
+
+    /// Neutral losses from the molecular ion
+    pub const fn losses(self) -> &'static [(&'static str, f64)] {
+        match self {
+            Self::Methyl => &[("[M-31]+", METHOXY), ("[M-43]+", PROPYL)],
+            Self::Ethyl => &[("[M-45]+", ETHOXY), ("[M-43]+", PROPYL)],
+            Self::Dmox | Self::Trimethylsilyl => &[("[M-15]+", METHYL)],
+            Self::Picolinyl => &[],
+        }
+    }
+
+    /// Characteristic ions of the head group
+    pub const fn ions(self) -> &'static [(&'static str, f64)] {
+        match self {
+            Self::Methyl => &[
+                ("McLafferty", MC_LAFFERTY),
+                ("Methoxycarbonylpropyl", METHOXYCARBONYL),
+            ],
+            Self::Ethyl => &[
+                ("McLafferty", ETHYL_MC_LAFFERTY),
+                ("Ethoxycarbonylpropyl", ETHOXYCARBONYL),
+            ],
+            Self::Dmox => &[("McLafferty", DMOX_MC_LAFFERTY), ("Oxazoline", DMOX)],
+            Self::Picolinyl => &[
+                ("Pyridylmethyl", PYRIDYLMETHYL),
+                ("Pyridylmethoxy", PYRIDYLMETHOXY),
+                ("McLafferty", PICOLINYL_MC_LAFFERTY),
+                ("Picolinyl", PICOLINYL),
+            ],
+            Self::Trimethylsilyl => &[
+                ("Trimethylsilyl", TRIMETHYLSILYL),
+                ("Trimethylsilyloxycarbonyl", TRIMETHYLSILYLOXYCARBONYL),
+                (
+                    "Trimethylsilyloxycarbonylethenyl",
+                    TRIMETHYLSILYLOXYCARBONYLETHENYL,
+                ),
+                ("McLafferty", TRIMETHYLSILYL_MC_LAFFERTY),
+            ],
+        }
+    }
+
+    /// Polyene ions of the polyunsaturated acids are diagnostic for the
+    /// esters, the nitrogen derivatives localize the bounds by the gaps in
+    /// the ion series instead
+    pub const fn polyene(self) -> bool {
+        matches!(self, Self::Methyl | Self::Ethyl | Self::Trimethylsilyl)
+    }
+}
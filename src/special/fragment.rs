@@ -0,0 +1,173 @@
+use polars::prelude::*;
+
+/// Methyl radical (•CH3)
+pub const METHYL: f64 = 15.023_475;
+
+/// Methoxy radical (•OCH3)
+pub const METHOXY: f64 = 31.018_389;
+
+/// Propyl radical (•C3H7), C2-C4 lost by rearrangement
+pub const PROPYL: f64 = 43.054_775;
+
+/// Ethoxy radical (•OC2H5)
+pub const ETHOXY: f64 = 45.034_040;
+
+/// McLafferty ion of methyl esters ([C3H6O2]+•)
+pub const MC_LAFFERTY: f64 = 74.036_779;
+
+/// Methoxycarbonylpropyl ion of methyl esters ([C4H7O2]+)
+pub const METHOXYCARBONYL: f64 = 87.044_604;
+
+/// McLafferty ion of ethyl esters ([C4H8O2]+•)
+pub const ETHYL_MC_LAFFERTY: f64 = 88.052_429;
+
+/// Ethoxycarbonylpropyl ion of ethyl esters ([C5H9O2]+)
+pub const ETHOXYCARBONYL: f64 = 101.060_254;
+
+/// McLafferty ion of DMOX derivatives ([C6H11NO]+•)
+pub const DMOX_MC_LAFFERTY: f64 = 113.084_064;
+
+/// Oxazoline ring with C2-C3 ([C7H12NO]+)
+pub const DMOX: f64 = 126.091_889;
+
+/// Pyridylmethyl ion of picolinyl esters ([C6H6N]+)
+pub const PYRIDYLMETHYL: f64 = 92.050_024;
+
+/// Pyridylmethoxy ion of picolinyl esters ([C6H6NO]+)
+pub const PYRIDYLMETHOXY: f64 = 108.044_939;
+
+/// McLafferty ion of picolinyl esters ([C8H9NO2]+•)
+pub const PICOLINYL_MC_LAFFERTY: f64 = 151.063_329;
+
+/// Picolinyl ester with C2-C3 ([C9H10NO2]+)
+pub const PICOLINYL: f64 = 164.071_154;
+
+/// Trimethylsilyl ion ([C3H9Si]+)
+pub const TRIMETHYLSILYL: f64 = 73.047_352;
+
+/// Trimethylsilyloxycarbonyl ion ([C4H9O2Si]+)
+pub const TRIMETHYLSILYLOXYCARBONYL: f64 = 117.037_181;
+
+/// Trimethylsilyloxycarbonylethenyl ion ([C5H9O2Si]+)
+pub const TRIMETHYLSILYLOXYCARBONYLETHENYL: f64 = 129.037_181;
+
+/// McLafferty ion of trimethylsilyl esters ([C5H12O2Si]+•)
+pub const TRIMETHYLSILYL_MC_LAFFERTY: f64 = 132.060_656;
+
+/// Polyene ion of polyunsaturated esters ([C6H7]+)
+pub const POLYENE: f64 = 79.054_775;
+
+/// ω-3 diagnostic ion ([C8H12]+•)
+pub const OMEGA3: f64 = 108.093_900;
+
+/// ω-6 diagnostic ion ([C11H18]+•)
+pub const OMEGA6: f64 = 150.140_851;
+
+/// Diagnostic ions of a polyunsaturated fatty acid ester
+///
+/// m/z 79 for two and more double bounds, m/z 108 for the ω-3 and m/z 150
+/// for the ω-6 acids.
+pub fn diagnostic(carbons: u8, indices: &[u8]) -> Vec<f64> {
+    let mut ions = Vec::new();
+    if indices.len() < 2 {
+        return ions;
+    }
+    ions.push(POLYENE);
+    match indices
+        .iter()
+        .max()
+        .map(|index| carbons.saturating_sub(*index))
+    {
+        Some(3) => ions.push(OMEGA3),
+        Some(6) => ions.push(OMEGA6),
+        _ => {}
+    }
+    ions
+}
+
+/// Extension methods for [`Expr`]
+pub trait ExprExt {
+    /// Diagnostic ions of the fatty acid struct expression
+    fn diagnostic(self) -> Expr;
+}
+
+impl ExprExt for Expr {
+    fn diagnostic(self) -> Expr {
+        self.map(
+            |column| {
+                let fields = column.struct_()?;
+                let carbons = fields.field_by_name("Carbons")?;
+                let indices = fields.field_by_name("Indices")?;
+                let mut builder = ListPrimitiveChunkedBuilder::<Float64Type>::new(
+                    column.name().clone(),
+                    column.len(),
+                    column.len() * 2,
+                    DataType::Float64,
+                );
+                for (carbons, indices) in carbons.u8()?.into_iter().zip(indices.list()?) {
+                    match carbons.zip(indices) {
+                        Some((carbons, indices)) => {
+                            let indices: Vec<u8> = indices.u8()?.into_iter().flatten().collect();
+                            builder.append_slice(&diagnostic(carbons, &indices));
+                        }
+                        None => builder.append_null(),
+                    }
+                }
+                Ok(Some(builder.finish().into_column()))
+            },
+            GetOutput::from_type(DataType::List(Box::new(DataType::Float64))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#const::relative_atomic_mass::{C, H, N, O, SI};
+
+    fn mass(c: u8, h: u8, n: u8, o: u8, si: u8) -> f64 {
+        c as f64 * C + h as f64 * H + n as f64 * N + o as f64 * O + si as f64 * SI
+    }
+
+    #[test]
+    fn masses() {
+        for (ion, formula) in [
+            (METHYL, mass(1, 3, 0, 0, 0)),
+            (METHOXY, mass(1, 3, 0, 1, 0)),
+            (PROPYL, mass(3, 7, 0, 0, 0)),
+            (ETHOXY, mass(2, 5, 0, 1, 0)),
+            (MC_LAFFERTY, mass(3, 6, 0, 2, 0)),
+            (METHOXYCARBONYL, mass(4, 7, 0, 2, 0)),
+            (ETHYL_MC_LAFFERTY, mass(4, 8, 0, 2, 0)),
+            (ETHOXYCARBONYL, mass(5, 9, 0, 2, 0)),
+            (DMOX_MC_LAFFERTY, mass(6, 11, 1, 1, 0)),
+            (DMOX, mass(7, 12, 1, 1, 0)),
+            (PYRIDYLMETHYL, mass(6, 6, 1, 0, 0)),
+            (PYRIDYLMETHOXY, mass(6, 6, 1, 1, 0)),
+            (PICOLINYL_MC_LAFFERTY, mass(8, 9, 1, 2, 0)),
+            (PICOLINYL, mass(9, 10, 1, 2, 0)),
+            (TRIMETHYLSILYL, mass(3, 9, 0, 0, 1)),
+            (TRIMETHYLSILYLOXYCARBONYL, mass(4, 9, 0, 2, 1)),
+            (TRIMETHYLSILYLOXYCARBONYLETHENYL, mass(5, 9, 0, 2, 1)),
+            (TRIMETHYLSILYL_MC_LAFFERTY, mass(5, 12, 0, 2, 1)),
+            (POLYENE, mass(6, 7, 0, 0, 0)),
+            (OMEGA3, mass(8, 12, 0, 0, 0)),
+            (OMEGA6, mass(11, 18, 0, 0, 0)),
+        ] {
+            assert!((ion - formula).abs() < 1e-5, "{ion} != {formula}");
+        }
+    }
+
+    #[test]
+    fn diagnostic_ions() {
+        // 18:3 n-3
+        assert_eq!(diagnostic(18, &[9, 12, 15]), [POLYENE, OMEGA3]);
+        // 20:4 n-6
+        assert_eq!(diagnostic(20, &[5, 8, 11, 14]), [POLYENE, OMEGA6]);
+        // 20:3 n-9
+        assert_eq!(diagnostic(20, &[5, 8, 11]), [POLYENE]);
+        // 18:1 n-9 and 16:0
+        assert!(diagnostic(18, &[9]).is_empty());
+        assert!(diagnostic(16, &[]).is_empty());
+    }
+}
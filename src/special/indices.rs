@@ -0,0 +1,233 @@
+use super::substituent::ColumnExt as _;
+use polars::prelude::*;
+
+/// Fatty acid as seen by the indices
+#[derive(Clone, Debug, Default)]
+pub struct Acid {
+    pub carbons: u8,
+    /// Positions of the unsaturated bounds from the carboxyl carbon
+    pub indices: Vec<u8>,
+    /// Unsaturated bounds by the position: 2 cis, -2 trans double bound, 3
+    /// triple bound
+    pub bounds: Vec<i8>,
+    /// Straight chain without substituents
+    pub straight: bool,
+}
+
+impl Acid {
+    /// Number of the unsaturated bounds
+    pub fn unsaturated(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Position of the last unsaturated bound from the methyl end (n-x)
+    pub fn omega(&self) -> Option<u8> {
+        let index = self.indices.iter().max()?;
+        self.carbons.checked_sub(*index)
+    }
+
+    fn is(&self, carbons: u8) -> bool {
+        self.straight && self.carbons == carbons && self.indices.is_empty()
+    }
+
+    /// Hypocholesterolemic acids: 18:1Δ9c and the C18-C22 n-3 and n-6
+    /// polyunsaturated acids (Santos-Silva et al., 2002)
+    fn hypocholesterolemic(&self) -> bool {
+        match self.unsaturated() {
+            1 => self.straight && self.carbons == 18 && self.indices == [9] && self.bounds == [2],
+            2.. => (18..=22).contains(&self.carbons) && matches!(self.omega(), Some(3 | 6)),
+            _ => false,
+        }
+    }
+}
+
+/// Nutritional and lipid quality indices of a composition
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Indices {
+    pub saturated: f64,
+    pub monounsaturated: f64,
+    pub polyunsaturated: f64,
+    pub omega3: f64,
+    pub omega6: f64,
+    /// Unsaturation index: double bounds per 100 acids
+    pub unsaturation: f64,
+    /// Atherogenic index (Ulbricht and Southgate, 1991)
+    pub atherogenic: Option<f64>,
+    /// Thrombogenic index (Ulbricht and Southgate, 1991)
+    pub thrombogenic: Option<f64>,
+    /// Hypocholesterolemic to hypercholesterolemic ratio
+    pub hypocholesterolemic: Option<f64>,
+}
+
+impl Indices {
+    /// Indices of the acids and their percents
+    ///
+    /// The ratios are `None` without the acids of the denominator.
+    pub fn new<'a>(composition: impl IntoIterator<Item = (&'a Acid, f64)>) -> Self {
+        let mut indices = Self::default();
+        // 12:0, 14:0, 16:0, 18:0
+        let mut lauric = 0.0;
+        let mut myristic = 0.0;
+        let mut palmitic = 0.0;
+        let mut stearic = 0.0;
+        let mut hypocholesterolemic = 0.0;
+        for (acid, value) in composition {
+            match acid.unsaturated() {
+                0 => indices.saturated += value,
+                1 => indices.monounsaturated += value,
+                _ => {
+                    indices.polyunsaturated += value;
+                    match acid.omega() {
+                        Some(3) => indices.omega3 += value,
+                        Some(6) => indices.omega6 += value,
+                        _ => {}
+                    }
+                }
+            }
+            indices.unsaturation += acid.unsaturated() as f64 * value;
+            if acid.is(12) {
+                lauric += value;
+            } else if acid.is(14) {
+                myristic += value;
+            } else if acid.is(16) {
+                palmitic += value;
+            } else if acid.is(18) {
+                stearic += value;
+            }
+            if acid.hypocholesterolemic() {
+                hypocholesterolemic += value;
+            }
+        }
+        let unsaturated = indices.monounsaturated + indices.polyunsaturated;
+        indices.atherogenic = divide(lauric + 4.0 * myristic + palmitic, unsaturated);
+        indices.thrombogenic = divide(indices.omega3, indices.omega6).and_then(|ratio| {
+            divide(
+                myristic + palmitic + stearic,
+                0.5 * indices.monounsaturated + 0.5 * indices.omega6 + 3.0 * indices.omega3 + ratio,
+            )
+        });
+        indices.hypocholesterolemic = divide(hypocholesterolemic, myristic + palmitic);
+        indices
+    }
+
+    /// n-6/n-3 ratio
+    pub fn ratio(&self) -> Option<f64> {
+        divide(self.omega6, self.omega3)
+    }
+}
+
+/// Ratio with the positive denominator
+fn divide(numerator: f64, denominator: f64) -> Option<f64> {
+    (denominator > 0.0).then(|| numerator / denominator)
+}
+
+/// Extension methods for [`Column`]
+pub trait ColumnExt {
+    /// Fatty acid of the row as seen by the indices
+    fn acid(&self, row: usize) -> PolarsResult<Acid>;
+}
+
+impl ColumnExt for Column {
+    fn acid(&self, row: usize) -> PolarsResult<Acid> {
+        let fatty_acid = self.struct_()?;
+        let carbons = fatty_acid.field_by_name("Carbons")?;
+        let indices = fatty_acid.field_by_name("Indices")?;
+        let indices = indices
+            .list()?
+            .get_as_series(row)
+            .map(|indices| {
+                indices
+                    .u8()
+                    .map(|indices| indices.into_iter().flatten().collect())
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let bounds = fatty_acid.field_by_name("Bounds")?;
+        let bounds = bounds
+            .list()?
+            .get_as_series(row)
+            .map(|bounds| {
+                bounds
+                    .i8()
+                    .map(|bounds| bounds.into_iter().flatten().collect())
+            })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Acid {
+            carbons: carbons.u8()?.get(row).unwrap_or_default(),
+            indices,
+            bounds,
+            straight: self.substituents(row)?.is_empty(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-6;
+
+    fn acid(carbons: u8, bounds: &[(u8, i8)]) -> Acid {
+        Acid {
+            carbons,
+            indices: bounds.iter().map(|&(index, _)| index).collect(),
+            bounds: bounds.iter().map(|&(_, bound)| bound).collect(),
+            straight: true,
+        }
+    }
+
+    #[test]
+    fn omega() {
+        assert_eq!(acid(18, &[]).omega(), None);
+        assert_eq!(acid(18, &[(9, 2), (12, 2)]).omega(), Some(6));
+        assert_eq!(acid(18, &[(9, 2), (12, 2), (15, 2)]).omega(), Some(3));
+    }
+
+    #[test]
+    fn hypocholesterolemic() {
+        assert!(acid(18, &[(9, 2)]).hypocholesterolemic());
+        assert!(!acid(18, &[(9, -2)]).hypocholesterolemic());
+        assert!(!acid(18, &[(11, 2)]).hypocholesterolemic());
+        assert!(acid(20, &[(5, 2), (8, 2), (11, 2), (14, 2)]).hypocholesterolemic());
+        assert!(!acid(16, &[(9, 2)]).hypocholesterolemic());
+    }
+
+    #[test]
+    fn indices() {
+        let acids = [
+            (acid(12, &[]), 1.0),
+            (acid(14, &[]), 4.0),
+            (acid(16, &[]), 25.0),
+            (acid(18, &[]), 10.0),
+            (acid(18, &[(9, 2)]), 35.0),
+            (acid(18, &[(9, -2)]), 2.0),
+            (acid(18, &[(9, 2), (12, 2)]), 18.0),
+            (acid(18, &[(9, 2), (12, 2), (15, 2)]), 5.0),
+        ];
+        let indices = Indices::new(acids.iter().map(|(acid, value)| (acid, *value)));
+        assert_eq!(indices.saturated, 40.0);
+        assert_eq!(indices.monounsaturated, 37.0);
+        assert_eq!(indices.polyunsaturated, 23.0);
+        assert_eq!(indices.omega3, 5.0);
+        assert_eq!(indices.omega6, 18.0);
+        assert_eq!(indices.unsaturation, 88.0);
+        assert!((indices.ratio().unwrap() - 3.6).abs() < EPSILON);
+        // (1 + 4 × 4 + 25) / 60
+        assert!((indices.atherogenic.unwrap() - 0.7).abs() < EPSILON);
+        // 39 / (18.5 + 9 + 15 + 5 / 18)
+        assert!((indices.thrombogenic.unwrap() - 0.911688312).abs() < EPSILON);
+        // (35 + 18 + 5) / (4 + 25), the trans 18:1 is left out
+        assert!((indices.hypocholesterolemic.unwrap() - 2.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn denominators() {
+        let stearic = acid(18, &[]);
+        let indices = Indices::new([(&stearic, 100.0)]);
+        assert_eq!(indices.ratio(), None);
+        assert_eq!(indices.atherogenic, None);
+        assert_eq!(indices.thrombogenic, None);
+        assert_eq!(indices.hypocholesterolemic, None);
+    }
+}
@@ -0,0 +1,241 @@
+use super::substituent::{ColumnExt as _, Substituents};
+use crate::r#const::isotopes::{C, H, N, O, SI};
+use polars::prelude::*;
+use std::fmt::{self, Display, Formatter};
+
+/// Elemental formula
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Formula {
+    pub c: u32,
+    pub h: u32,
+    pub n: u32,
+    pub o: u32,
+    pub si: u32,
+}
+
+impl Formula {
+    /// Free fatty acid (RCOOH)
+    ///
+    /// A double bound and a ring take two hydrogens, a triple bound takes
+    /// four, a hydroxy group adds an oxygen.
+    pub fn rcooh(carbons: u8, bounds: &[i8], substituents: &Substituents) -> Self {
+        let unsaturation = bounds
+            .iter()
+            .map(|bound| match bound.unsigned_abs() {
+                2 => 1,
+                3 => 2,
+                _ => 0,
+            })
+            .sum::<u32>()
+            + substituents.rings() as u32;
+        let c = carbons as u32;
+        Self {
+            c,
+            h: (2 * c).saturating_sub(2 * unsaturation),
+            o: 2 + substituents.hydroxy() as u32,
+            ..Default::default()
+        }
+    }
+
+    /// Acylium ion ([RCO]+)
+    pub fn rco(self) -> Self {
+        Self {
+            h: self.h - 1,
+            o: self.o - 1,
+            ..self
+        }
+    }
+
+    /// Carboxylate anion ([RCOO]-)
+    pub fn rcoo(self) -> Self {
+        Self {
+            h: self.h - 1,
+            ..self
+        }
+    }
+
+    /// Molecular weight by the average atomic weights of the natural
+    /// isotopes
+    pub fn weight(self) -> f64 {
+        [
+            (&C[..], self.c),
+            (&H[..], self.h),
+            (&N[..], self.n),
+            (&O[..], self.o),
+            (&SI[..], self.si),
+        ]
+        .into_iter()
+        .map(|(isotopes, count)| {
+            count as f64
+                * isotopes
+                    .iter()
+                    .map(|&(mass, abundance)| mass * abundance)
+                    .sum::<f64>()
+        })
+        .sum()
+    }
+
+    /// Isotope pattern
+    ///
+    /// Peaks M, M+1, M+2… up to `len` nominal mass shifts, each with the
+    /// abundance weighted mean mass and the abundance relative to the most
+    /// abundant peak (%).
+    pub fn pattern(self, len: usize) -> Vec<Peak> {
+        // Abundance weighted mass sum and abundance by the nominal shift
+        let mut peaks = vec![(0.0, 0.0); len];
+        peaks[0].1 = 1.0;
+        for (isotopes, count) in [
+            (&C[..], self.c),
+            (&H[..], self.h),
+            (&N[..], self.n),
+            (&O[..], self.o),
+            (&SI[..], self.si),
+        ] {
+            for _ in 0..count {
+                let mut next = vec![(0.0, 0.0); len];
+                for (shift, &(mass, abundance)) in peaks.iter().enumerate() {
+                    for (delta, &(isotope, natural)) in isotopes.iter().enumerate() {
+                        let Some(peak) = next.get_mut(shift + delta) else {
+                            break;
+                        };
+                        peak.0 += natural * (mass + abundance * isotope);
+                        peak.1 += natural * abundance;
+                    }
+                }
+                peaks = next;
+            }
+        }
+        let max = peaks
+            .iter()
+            .map(|&(_, abundance)| abundance)
+            .fold(0.0, f64::max);
+        peaks
+            .into_iter()
+            .filter(|&(_, abundance)| abundance > 0.0)
+            .map(|(mass, abundance)| Peak {
+                mass: mass / abundance,
+                abundance: abundance / max * 100.0,
+            })
+            .collect()
+    }
+}
+
+impl Display for Formula {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (symbol, count) in [
+            ("C", self.c),
+            ("H", self.h),
+            ("N", self.n),
+            ("O", self.o),
+            ("Si", self.si),
+        ] {
+            match count {
+                0 => {}
+                1 => f.write_str(symbol)?,
+                _ => write!(f, "{symbol}{count}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Isotope peak
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Peak {
+    pub mass: f64,
+    pub abundance: f64,
+}
+
+/// Extension methods for [`Column`]
+pub trait ColumnExt {
+    /// Free fatty acid formula of the fatty acid in the row
+    fn formula(&self, row: usize) -> PolarsResult<Formula>;
+}
+
+impl ColumnExt for Column {
+    fn formula(&self, row: usize) -> PolarsResult<Formula> {
+        let fatty_acid = self.struct_()?;
+        let carbons = fatty_acid.field_by_name("Carbons")?;
+        let bounds = fatty_acid.field_by_name("Bounds")?;
+        let bounds: Vec<i8> = bounds
+            .list()?
+            .get_as_series(row)
+            .map(|bounds| {
+                bounds
+                    .i8()
+                    .map(|bounds| bounds.into_iter().flatten().collect())
+            })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Formula::rcooh(
+            carbons.u8()?.get(row).unwrap_or_default(),
+            &bounds,
+            &self.substituents(row)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-6;
+
+    // C16:0
+    const PALMITIC: Formula = Formula {
+        c: 16,
+        h: 32,
+        n: 0,
+        o: 2,
+        si: 0,
+    };
+
+    // C18:2
+    const LINOLEIC: Formula = Formula {
+        c: 18,
+        h: 32,
+        n: 0,
+        o: 2,
+        si: 0,
+    };
+
+    #[test]
+    fn rcooh() {
+        let substituents = Substituents::default();
+        assert_eq!(Formula::rcooh(16, &[], &substituents), PALMITIC);
+        assert_eq!(Formula::rcooh(18, &[2, -2], &substituents), LINOLEIC);
+        assert_eq!(Formula::rcooh(18, &[3], &substituents), LINOLEIC);
+        assert_eq!(PALMITIC.to_string(), "C16H32O2");
+        assert_eq!(PALMITIC.rco().to_string(), "C16H31O");
+        assert_eq!(PALMITIC.rcoo().to_string(), "C16H31O2");
+    }
+
+    /// The first peak is the monoisotopic mass
+    #[test]
+    fn mass() {
+        assert!((PALMITIC.pattern(1)[0].mass - 256.240230265).abs() < EPSILON);
+        assert!((LINOLEIC.pattern(1)[0].mass - 280.240230265).abs() < EPSILON);
+    }
+
+    #[test]
+    fn weight() {
+        // Standard atomic weights: C 12.011, H 1.008, O 15.999
+        assert!((PALMITIC.weight() - 256.42).abs() < 0.01);
+        assert!((LINOLEIC.weight() - 280.45).abs() < 0.01);
+    }
+
+    #[test]
+    fn pattern() {
+        for (formula, expected) in [
+            (PALMITIC, [100.0, 17.749392723, 1.892583779, 0.150047512]),
+            (LINOLEIC, [100.0, 19.912538382, 2.288226995, 0.193063179]),
+        ] {
+            let pattern = formula.pattern(4);
+            assert_eq!(pattern.len(), 4);
+            for (shift, (peak, expected)) in pattern.iter().zip(expected).enumerate() {
+                assert!((peak.abundance - expected).abs() < EPSILON, "M+{shift}");
+                assert!((peak.mass - pattern[0].mass - shift as f64).abs() < 0.01);
+            }
+        }
+    }
+}
@@ -1,2 +1,13 @@
+pub mod alignment;
 pub mod column;
+pub mod composition;
 pub mod data_frame;
+pub mod derivative;
+pub mod fragment;
+pub mod indices;
+pub mod isotope;
+pub mod peaks;
+pub mod program;
+pub mod reference;
+pub mod spectrum;
+pub mod substituent;
@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Peak picking options
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Options {
+    /// Half width of the moving average, points
+    pub smoothing: usize,
+    /// Width of the rolling minimum baseline, points
+    pub baseline: usize,
+    /// Minimum height, noise multiples
+    pub threshold: f64,
+}
+
+impl Options {
+    pub const fn new() -> Self {
+        Self {
+            smoothing: 2,
+            baseline: 200,
+            threshold: 10.0,
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Peak
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Peak {
+    /// Apex time
+    pub time: f64,
+    /// Apex height above the baseline
+    pub height: f64,
+    /// Area above the baseline
+    pub area: f64,
+    /// Full width at half height
+    pub width: f64,
+}
+
+/// Picks the peaks of the trace
+///
+/// The trace is smoothed by a moving average, the baseline is the smoothed
+/// rolling maximum of the rolling minimum, which follows a drifting
+/// baseline. Apexes are the local maxima of the corrected trace above the
+/// threshold times the noise, the noise is estimated by the median absolute
+/// first difference of the raw trace. A peak ends at the nearest minimum or where
+/// the trace falls to the noise.
+pub fn pick(times: &[f64], values: &[f64], options: &Options) -> Vec<Peak> {
+    let len = times.len().min(values.len());
+    if len < 3 {
+        return Vec::new();
+    }
+    let smoothed = average(&values[..len], options.smoothing);
+    let opening = rolling(
+        &rolling(&smoothed, options.baseline, f64::min),
+        options.baseline,
+        f64::max,
+    );
+    let baseline = average(&opening, options.baseline / 2);
+    let corrected: Vec<f64> = smoothed
+        .iter()
+        .zip(&baseline)
+        .map(|(value, baseline)| (value - baseline).max(0.0))
+        .collect();
+    let noise = noise(&values[..len]);
+    let mut peaks = Vec::new();
+    let mut index = 1;
+    while index < len - 1 {
+        let height = corrected[index];
+        if height <= options.threshold * noise
+            || height <= corrected[index - 1]
+            || height < corrected[index + 1]
+        {
+            index += 1;
+            continue;
+        }
+        let mut start = index;
+        while start > 0 && corrected[start - 1] < corrected[start] && corrected[start] > noise {
+            start -= 1;
+        }
+        let mut end = index;
+        while end < len - 1 && corrected[end + 1] < corrected[end] && corrected[end] > noise {
+            end += 1;
+        }
+        let area = (start..end)
+            .map(|index| {
+                (times[index + 1] - times[index]) * (corrected[index] + corrected[index + 1]) / 2.0
+            })
+            .sum();
+        let half = height / 2.0;
+        let left = (start..index)
+            .rev()
+            .find(|&index| corrected[index] <= half)
+            .map_or(times[start], |left| {
+                interpolate(times, &corrected, left, left + 1, half)
+            });
+        let right = (index + 1..=end)
+            .find(|&index| corrected[index] <= half)
+            .map_or(times[end], |right| {
+                interpolate(times, &corrected, right - 1, right, half)
+            });
+        peaks.push(Peak {
+            time: apex(times, &corrected, index),
+            height,
+            area,
+            width: right - left,
+        });
+        index = end.max(index + 1);
+    }
+    peaks
+}
+
+/// Centered moving average
+fn average(values: &[f64], half: usize) -> Vec<f64> {
+    let mut sums = Vec::with_capacity(values.len() + 1);
+    sums.push(0.0);
+    for value in values {
+        sums.push(sums[sums.len() - 1] + value);
+    }
+    (0..values.len())
+        .map(|index| {
+            let Range { start, end } = window(index, half, values.len());
+            (sums[end] - sums[start]) / (end - start) as f64
+        })
+        .collect()
+}
+
+/// Centered rolling minimum or maximum
+fn rolling(values: &[f64], width: usize, fold: fn(f64, f64) -> f64) -> Vec<f64> {
+    (0..values.len())
+        .map(|index| {
+            values[window(index, width / 2, values.len())]
+                .iter()
+                .copied()
+                .reduce(fold)
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Window centered at the index, narrowed at the ends to stay centered, so
+/// a linear trace is kept by the rolling functions
+fn window(index: usize, half: usize, len: usize) -> Range<usize> {
+    let half = half.min(index).min(len - 1 - index);
+    index - half..index + half + 1
+}
+
+/// Median absolute first difference, scaled to the standard deviation of a
+/// normal noise
+fn noise(values: &[f64]) -> f64 {
+    let mut differences: Vec<f64> = values
+        .windows(2)
+        .map(|window| (window[1] - window[0]).abs())
+        .collect();
+    differences.sort_by(f64::total_cmp);
+    let median = differences[differences.len() / 2];
+    median * 1.4826 / 2f64.sqrt()
+}
+
+/// Time where the trace crosses the value between two points
+fn interpolate(times: &[f64], values: &[f64], left: usize, right: usize, value: f64) -> f64 {
+    let delta = values[right] - values[left];
+    if delta == 0.0 {
+        return times[left];
+    }
+    times[left] + (value - values[left]) / delta * (times[right] - times[left])
+}
+
+/// Apex time refined by the parabola through three points
+fn apex(times: &[f64], values: &[f64], index: usize) -> f64 {
+    let (left, center, right) = (values[index - 1], values[index], values[index + 1]);
+    let denominator = left - 2.0 * center + right;
+    if denominator == 0.0 {
+        return times[index];
+    }
+    let offset = (0.5 * (left - right) / denominator).clamp(-0.5, 0.5);
+    let step = if offset < 0.0 {
+        times[index] - times[index - 1]
+    } else {
+        times[index + 1] - times[index]
+    };
+    times[index] + offset * step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    const SIGMA: f64 = 0.05;
+
+    /// Baseline slopes: flat, rising and falling
+    const SLOPES: [f64; 3] = [0.0, 1.0, -1.0];
+
+    /// Gaussian peaks (apex, height) on a noisy baseline, sampled every 0.01
+    fn trace(peaks: &[(f64, f64)], slope: f64) -> (Vec<f64>, Vec<f64>) {
+        let times: Vec<f64> = (0..1000).map(|index| index as f64 * 0.01).collect();
+        let values = times
+            .iter()
+            .map(|&time| {
+                let noise = (time * 997.0).sin() * 0.05;
+                let gaussians = peaks
+                    .iter()
+                    .map(|&(apex, height)| {
+                        height * (-(time - apex).powi(2) / (2.0 * SIGMA.powi(2))).exp()
+                    })
+                    .sum::<f64>();
+                20.0 + slope * time + noise + gaussians
+            })
+            .collect();
+        (times, values)
+    }
+
+    #[test]
+    fn gaussian() {
+        for slope in SLOPES {
+            let (times, values) = trace(&[(5.0, 100.0)], slope);
+            let peaks = pick(&times, &values, &Options::new());
+            assert_eq!(peaks.len(), 1, "{slope}: {peaks:?}");
+            let peak = peaks[0];
+            assert!((peak.time - 5.0).abs() < 0.005, "{peak:?}");
+            assert!((peak.height - 100.0).abs() < 5.0, "{peak:?}");
+            // Full width at half height of a Gaussian is 2√(2ln2)σ
+            let width = 2.0 * (2.0 * 2f64.ln()).sqrt() * SIGMA;
+            assert!((peak.width - width).abs() < 0.02, "{peak:?}");
+            let area = 100.0 * SIGMA * (2.0 * PI).sqrt();
+            assert!((peak.area - area).abs() / area < 0.05, "{peak:?}");
+        }
+    }
+
+    #[test]
+    fn separated() {
+        for slope in SLOPES {
+            let (times, values) = trace(&[(3.0, 50.0), (6.0, 100.0)], slope);
+            let peaks = pick(&times, &values, &Options::new());
+            assert_eq!(peaks.len(), 2, "{slope}: {peaks:?}");
+            assert!((peaks[0].time - 3.0).abs() < 0.005);
+            assert!((peaks[1].time - 6.0).abs() < 0.005);
+            assert!((peaks[1].area / peaks[0].area - 2.0).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn baseline() {
+        for slope in SLOPES {
+            let (times, values) = trace(&[], slope);
+            assert!(pick(&times, &values, &Options::new()).is_empty(), "{slope}");
+        }
+        assert!(pick(&[0.0, 1.0], &[0.0, 1.0], &Options::new()).is_empty());
+        assert!(pick(&[0.0; 100], &[1.0; 100], &Options::new()).is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Maximum temperature of a two-field mode
+pub const DEFAULT_MAX_TEMPERATURE: f64 = 250.0;
+
+/// Temperature program
+///
+/// The oven starts at the onset temperature and runs the segments in order,
+/// the last temperature is held until the end of the run. The temperature
+/// never exceeds the maximum temperature.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Program {
+    pub onset_temperature: f64,
+    pub segments: Vec<Segment>,
+    pub max_temperature: f64,
+}
+
+impl Program {
+    /// Single ramp from the onset temperature up to the maximum temperature
+    pub fn ramp(onset_temperature: f64, temperature_step: f64, max_temperature: f64) -> Self {
+        Self {
+            onset_temperature,
+            segments: vec![Segment {
+                hold: 0.0,
+                rate: temperature_step,
+                target: max_temperature,
+            }],
+            max_temperature,
+        }
+    }
+
+    /// Temperature at the time (min)
+    pub fn temperature(&self, mut time: f64) -> f64 {
+        let mut temperature = self.onset_temperature;
+        for segment in &self.segments {
+            if time <= segment.hold {
+                break;
+            }
+            time -= segment.hold;
+            let duration = segment.duration(temperature);
+            if time <= duration {
+                temperature += segment.rate * time;
+                break;
+            }
+            time -= duration;
+            if duration > 0.0 {
+                temperature = segment.target;
+            }
+        }
+        temperature.min(self.max_temperature)
+    }
+
+    fn is_ramp(&self) -> bool {
+        match &*self.segments {
+            [segment] => segment.hold == 0.0 && segment.target >= self.max_temperature,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Program {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.is_ramp() {
+            return write!(f, "{}/{}", self.onset_temperature, self.segments[0].rate);
+        }
+        write!(f, "{}°C", self.onset_temperature)?;
+        for segment in &self.segments {
+            if segment.hold > 0.0 {
+                write!(f, " {} min", segment.hold)?;
+            }
+            if segment.rate > 0.0 {
+                write!(f, ", {}°C/min → {}°C", segment.rate, segment.target)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Temperature program segment
+///
+/// Holds the current temperature for `hold` minutes, then heats at `rate`
+/// °C/min up to the `target` temperature.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Segment {
+    pub hold: f64,
+    pub rate: f64,
+    pub target: f64,
+}
+
+impl Segment {
+    /// Ramp duration (min) from the temperature
+    fn duration(&self, temperature: f64) -> f64 {
+        if self.rate > 0.0 && self.target > temperature {
+            (self.target - temperature) / self.rate
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ramp() {
+        let program = Program::ramp(70.0, 2.0, 250.0);
+        assert_eq!(program.temperature(0.0), 70.0);
+        assert_eq!(program.temperature(10.0), 90.0);
+        assert_eq!(program.temperature(90.0), 250.0);
+        assert_eq!(program.temperature(200.0), 250.0);
+        assert_eq!(program.to_string(), "70/2");
+    }
+
+    #[test]
+    fn segments() {
+        // 60 °C for 1 min, 10 °C/min to 150 °C, 5 min hold, 2 °C/min to 220 °C
+        let program = Program {
+            onset_temperature: 60.0,
+            segments: vec![
+                Segment {
+                    hold: 1.0,
+                    rate: 10.0,
+                    target: 150.0,
+                },
+                Segment {
+                    hold: 5.0,
+                    rate: 2.0,
+                    target: 220.0,
+                },
+            ],
+            max_temperature: 250.0,
+        };
+        assert_eq!(program.temperature(0.5), 60.0);
+        assert_eq!(program.temperature(1.0), 60.0);
+        assert_eq!(program.temperature(4.0), 90.0);
+        assert_eq!(program.temperature(10.0), 150.0);
+        assert_eq!(program.temperature(15.0), 150.0);
+        assert_eq!(program.temperature(20.0), 160.0);
+        assert_eq!(program.temperature(100.0), 220.0);
+        assert_eq!(
+            program.to_string(),
+            "60°C 1 min, 10°C/min → 150°C 5 min, 2°C/min → 220°C"
+        );
+    }
+
+    #[test]
+    fn max_temperature() {
+        let program = Program::ramp(200.0, 10.0, 230.0);
+        assert_eq!(program.temperature(5.0), 230.0);
+    }
+
+    #[test]
+    fn isothermal() {
+        let program = Program {
+            onset_temperature: 180.0,
+            segments: Vec::new(),
+            max_temperature: 250.0,
+        };
+        assert_eq!(program.temperature(30.0), 180.0);
+        assert_eq!(program.to_string(), "180°C");
+    }
+}
@@ -0,0 +1,189 @@
+use super::indices::Acid;
+use polars::prelude::*;
+
+/// Interquartile range of the standard normal distribution
+const NORMAL_INTERQUARTILE_RANGE: f64 = 1.349;
+
+/// Reference range of a fatty acid
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Range {
+    pub carbons: u8,
+    /// Positions of the unsaturated bounds, unknown positions are null
+    pub indices: Vec<Option<u8>>,
+    pub median: Option<f64>,
+    pub interquartile_range: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl Range {
+    /// Straight-chain acid with the same carbons and bounds, a null
+    /// position matches any
+    pub fn matches(&self, acid: &Acid) -> bool {
+        acid.straight
+            && acid.carbons == self.carbons
+            && acid.indices.len() == self.indices.len()
+            && acid
+                .indices
+                .iter()
+                .zip(&self.indices)
+                .all(|(index, reference)| reference.is_none_or(|reference| reference == *index))
+    }
+
+    /// Outside the reference range
+    pub fn out(&self, value: f64) -> bool {
+        self.min.is_some_and(|min| value < min) || self.max.is_some_and(|max| value > max)
+    }
+
+    /// Robust z-score: the distance from the median in the standard
+    /// deviations estimated by the interquartile range
+    pub fn deviation(&self, value: f64) -> Option<f64> {
+        let median = self.median?;
+        let interquartile_range = self.interquartile_range.filter(|&range| range > 0.0)?;
+        Some((value - median) / (interquartile_range / NORMAL_INTERQUARTILE_RANGE))
+    }
+}
+
+/// Reference ranges of the `FattyAcid`, `Median`, `InterquartileRange` and
+/// `ReferenceRange {Min, Max}` data frame
+pub fn ranges(data_frame: &DataFrame) -> PolarsResult<Vec<Range>> {
+    let fatty_acid = data_frame["FattyAcid"].struct_()?;
+    let carbons = fatty_acid.field_by_name("Carbons")?;
+    let carbons = carbons.cast(&DataType::UInt8)?;
+    let indices = fatty_acid.field_by_name("Indices")?;
+    let indices = indices.cast(&DataType::List(Box::new(DataType::UInt8)))?;
+    let median = data_frame["Median"].f64()?;
+    let interquartile_range = data_frame["InterquartileRange"].f64()?;
+    let reference_range = data_frame["ReferenceRange"].struct_()?;
+    let min = reference_range.field_by_name("Min")?;
+    let max = reference_range.field_by_name("Max")?;
+    let mut ranges = Vec::with_capacity(data_frame.height());
+    for row in 0..data_frame.height() {
+        ranges.push(Range {
+            carbons: carbons.u8()?.get(row).unwrap_or_default(),
+            indices: match indices.list()?.get_as_series(row) {
+                Some(indices) => indices.u8()?.into_iter().collect(),
+                None => Vec::new(),
+            },
+            median: median.get(row),
+            interquartile_range: interquartile_range.get(row),
+            min: min.f64()?.get(row),
+            max: max.f64()?.get(row),
+        });
+    }
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oleic() -> Range {
+        Range {
+            carbons: 18,
+            indices: vec![Some(9)],
+            median: Some(40.0),
+            interquartile_range: Some(1.349),
+            min: Some(35.0),
+            max: Some(45.0),
+        }
+    }
+
+    #[test]
+    fn matches() {
+        let acid = Acid {
+            carbons: 18,
+            indices: vec![9],
+            bounds: vec![2],
+            straight: true,
+        };
+        assert!(oleic().matches(&acid));
+        let any = Range {
+            indices: vec![None],
+            ..oleic()
+        };
+        assert!(any.matches(&Acid {
+            indices: vec![11],
+            ..acid.clone()
+        }));
+        assert!(!oleic().matches(&Acid {
+            indices: vec![11],
+            ..acid.clone()
+        }));
+        assert!(!oleic().matches(&Acid {
+            straight: false,
+            ..acid
+        }));
+    }
+
+    #[test]
+    fn deviation() {
+        let range = oleic();
+        assert_eq!(range.deviation(42.0), Some(2.0));
+        assert!(!range.out(45.0));
+        assert!(range.out(45.5));
+        assert!(range.out(34.0));
+        let degenerate = Range {
+            interquartile_range: Some(0.0),
+            ..oleic()
+        };
+        assert_eq!(degenerate.deviation(42.0), None);
+    }
+
+    #[test]
+    fn data_frame() -> PolarsResult<()> {
+        let fatty_acid = StructChunked::from_series(
+            "FattyAcid".into(),
+            2,
+            [
+                Series::new("Carbons".into(), [16i64, 18]),
+                Series::new(
+                    "Indices".into(),
+                    [
+                        Series::new_empty(PlSmallStr::EMPTY, &DataType::Int64),
+                        Series::new(PlSmallStr::EMPTY, [9i64]),
+                    ],
+                ),
+            ]
+            .iter(),
+        )?;
+        let reference_range = StructChunked::from_series(
+            "ReferenceRange".into(),
+            2,
+            [
+                Series::new("Min".into(), [Some(20.0), None]),
+                Series::new("Max".into(), [30.0, 45.0]),
+            ]
+            .iter(),
+        )?;
+        let data_frame = DataFrame::new(vec![
+            fatty_acid.into_column(),
+            Column::new("Median".into(), [25.0, 40.0]),
+            Column::new("InterquartileRange".into(), [Some(3.0), None]),
+            reference_range.into_column(),
+        ])?;
+        let ranges = ranges(&data_frame)?;
+        assert_eq!(
+            ranges,
+            [
+                Range {
+                    carbons: 16,
+                    indices: Vec::new(),
+                    median: Some(25.0),
+                    interquartile_range: Some(3.0),
+                    min: Some(20.0),
+                    max: Some(30.0),
+                },
+                Range {
+                    carbons: 18,
+                    indices: vec![Some(9)],
+                    median: Some(40.0),
+                    interquartile_range: None,
+                    min: None,
+                    max: Some(45.0),
+                },
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+
+/// Spectrum similarity
+///
+/// The squared cosine of the measured spectrum and the predicted ions on the
+/// nominal mass scale, scaled to 1000 as the NIST match factors. Measured
+/// intensities are weighted by the square root, predicted ions have a unit
+/// intensity. The reverse match ignores the measured peaks that are not
+/// predicted.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Similarity {
+    pub forward: f64,
+    pub reverse: f64,
+}
+
+impl Similarity {
+    pub fn new(peaks: &[(f64, f64)], predicted: &[f64]) -> Self {
+        let mut measured = BTreeMap::new();
+        for &(mass_to_charge, intensity) in peaks {
+            *measured.entry(nominal(mass_to_charge)).or_insert(0.0) += intensity;
+        }
+        let mut predicted: Vec<_> = predicted.iter().map(|&value| nominal(value)).collect();
+        predicted.sort();
+        predicted.dedup();
+        let weight = |nominal: &i64| {
+            measured
+                .get(nominal)
+                .map_or(0.0, |intensity: &f64| intensity.sqrt())
+        };
+        let product: f64 = predicted.iter().map(weight).sum();
+        let predicted_norm = predicted.len() as f64;
+        let measured_norm: f64 = measured.values().sum();
+        let reverse_norm: f64 = predicted
+            .iter()
+            .map(|nominal| weight(nominal).powi(2))
+            .sum();
+        let score = |norm: f64| {
+            if norm * predicted_norm == 0.0 {
+                0.0
+            } else {
+                product.powi(2) / (norm * predicted_norm) * 1000.0
+            }
+        };
+        Self {
+            forward: score(measured_norm),
+            reverse: score(reverse_norm),
+        }
+    }
+}
+
+fn nominal(mass_to_charge: f64) -> i64 {
+    mass_to_charge.round() as _
+}
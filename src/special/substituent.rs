@@ -0,0 +1,235 @@
+use crate::r#const::relative_atomic_mass::{H, O};
+use polars::prelude::*;
+use std::fmt::{self, Display, Formatter};
+
+/// Substituent of the acyl chain
+///
+/// Positions are counted from the carboxyl carbon. `Carbons` of a
+/// substituted acid is the total number of carbons, branches and rings
+/// included (`i-15:0` is 13-methyltetradecanoic acid).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Substituent {
+    /// Methyl branch at the penultimate carbon
+    Iso,
+    /// Methyl branch at the antepenultimate carbon
+    Anteiso,
+    /// Methyl branch in the middle of the chain
+    Methyl(u8),
+    /// Hydroxy group
+    Hydroxy(u8),
+    /// Cyclopropane ring (methylene bridge)
+    Cyclopropane(Option<u8>),
+}
+
+impl Display for Substituent {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Iso => f.write_str("i-"),
+            Self::Anteiso => f.write_str("ai-"),
+            Self::Methyl(index) => write!(f, "{index}-Me-"),
+            Self::Hydroxy(index) => write!(f, "{index}-OH-"),
+            Self::Cyclopropane(_) => f.write_str("cy-"),
+        }
+    }
+}
+
+/// Substituents of the acyl chain parsed from the label
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Substituents(pub Vec<Substituent>);
+
+impl Substituents {
+    /// Parses short (`i-15:0`, `ai-15:0`, `3-OH-14:0`, `10Me-16:0`,
+    /// `cy17:0`) and systematic (`13-methyltetradecanoate`,
+    /// `3-hydroxytetradecanoate`, `9,10-methylenehexadecanoate`) notations.
+    pub fn parse(label: &str, carbons: u8) -> Self {
+        let label = label.to_lowercase();
+        let mut substituents = Vec::new();
+        // Systematic
+        for (index, _) in label.match_indices("methylene") {
+            substituents.push(Substituent::Cyclopropane(locant(&label[..index])));
+        }
+        if substituents.is_empty() && label.contains("cyclopropane") {
+            substituents.push(Substituent::Cyclopropane(None));
+        }
+        for (index, _) in label.match_indices("hydroxy") {
+            if let Some(locant) = locant(&label[..index]) {
+                substituents.push(Substituent::Hydroxy(locant));
+            }
+        }
+        for (index, _) in label.match_indices("methyl") {
+            if label[index..].starts_with("methylene") {
+                continue;
+            }
+            // "Methyl octanoate" is the ester, not a branch
+            if let Some(locant) = locant(&label[..index]) {
+                substituents.push(branch(locant, carbons));
+            }
+        }
+        // Short
+        if substituents.is_empty() {
+            for token in label.split(|c: char| c.is_whitespace() || "[](),".contains(c)) {
+                substituents.extend(short(token, carbons));
+            }
+        }
+        Self(substituents)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of hydroxy groups
+    pub fn hydroxy(&self) -> u8 {
+        self.count(|substituent| matches!(substituent, Substituent::Hydroxy(_)))
+    }
+
+    /// Number of cyclopropane rings
+    pub fn rings(&self) -> u8 {
+        self.count(|substituent| matches!(substituent, Substituent::Cyclopropane(_)))
+    }
+
+    /// Mass difference from the straight-chain acid with the same carbons
+    /// and bounds: a ring takes two hydrogens, a hydroxy group adds an
+    /// oxygen, branches do not change the formula.
+    pub fn mass(&self) -> f64 {
+        self.hydroxy() as f64 * O - self.rings() as f64 * 2.0 * H
+    }
+
+    fn count(&self, f: impl Fn(&&Substituent) -> bool) -> u8 {
+        self.0.iter().filter(f).count() as _
+    }
+}
+
+impl Display for Substituents {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for substituent in &self.0 {
+            Display::fmt(substituent, f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Extension methods for [`Expr`]
+pub trait ExprExt {
+    /// Substituents of the fatty acid struct expression
+    ///
+    /// A struct with `Branches`, `Hydroxy` and `Rings` counts and the `Mass`
+    /// difference from the straight-chain acid.
+    fn substituents(self) -> Expr;
+}
+
+impl ExprExt for Expr {
+    fn substituents(self) -> Expr {
+        self.map(
+            substituents,
+            GetOutput::from_type(DataType::Struct(vec![
+                Field::new("Branches".into(), DataType::UInt8),
+                Field::new("Hydroxy".into(), DataType::UInt8),
+                Field::new("Rings".into(), DataType::UInt8),
+                Field::new("Mass".into(), DataType::Float64),
+            ])),
+        )
+    }
+}
+
+/// Extension methods for [`Column`]
+pub trait ColumnExt {
+    /// Substituents of the fatty acid in the row
+    fn substituents(&self, row: usize) -> PolarsResult<Substituents>;
+}
+
+impl ColumnExt for Column {
+    fn substituents(&self, row: usize) -> PolarsResult<Substituents> {
+        let fields = self.struct_()?;
+        let carbons = fields.field_by_name("Carbons")?;
+        let label = fields.field_by_name("Label")?;
+        Ok(Substituents::parse(
+            label.str()?.get(row).unwrap_or_default(),
+            carbons.u8()?.get(row).unwrap_or_default(),
+        ))
+    }
+}
+
+fn substituents(column: Column) -> PolarsResult<Option<Column>> {
+    let fields = column.struct_()?;
+    let carbons = fields.field_by_name("Carbons")?;
+    let labels = fields.field_by_name("Label")?;
+    let mut branches = Vec::with_capacity(column.len());
+    let mut hydroxy = Vec::with_capacity(column.len());
+    let mut rings = Vec::with_capacity(column.len());
+    let mut mass = Vec::with_capacity(column.len());
+    for (carbons, label) in carbons.u8()?.into_iter().zip(labels.str()?) {
+        let substituents =
+            Substituents::parse(label.unwrap_or_default(), carbons.unwrap_or_default());
+        branches.push(substituents.0.len() as u8 - substituents.hydroxy() - substituents.rings());
+        hydroxy.push(substituents.hydroxy());
+        rings.push(substituents.rings());
+        mass.push(substituents.mass());
+    }
+    Ok(Some(
+        StructChunked::from_series(
+            column.name().clone(),
+            column.len(),
+            [
+                Series::new("Branches".into(), branches),
+                Series::new("Hydroxy".into(), hydroxy),
+                Series::new("Rings".into(), rings),
+                Series::new("Mass".into(), mass),
+            ]
+            .iter(),
+        )?
+        .into_column(),
+    ))
+}
+
+/// Branch by the position of the methyl group
+fn branch(locant: u8, carbons: u8) -> Substituent {
+    // The main chain is one carbon shorter than the acid
+    match carbons.checked_sub(locant) {
+        Some(2) => Substituent::Iso,
+        Some(3) => Substituent::Anteiso,
+        _ => Substituent::Methyl(locant),
+    }
+}
+
+/// First locant of the `9,10-` prefix before the group name
+fn locant(prefix: &str) -> Option<u8> {
+    let prefix = prefix.strip_suffix('-')?;
+    let start = prefix
+        .rfind(|c: char| !c.is_ascii_digit() && c != ',')
+        .map_or(0, |index| index + 1);
+    prefix[start..].split(',').next()?.parse().ok()
+}
+
+/// Short notation token
+fn short(token: &str, carbons: u8) -> Option<Substituent> {
+    let rest = |prefix: &str| {
+        token
+            .strip_prefix(prefix)
+            .map(|rest| rest.trim_start_matches('-'))
+            .filter(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+    };
+    if rest("anteiso")
+        .or_else(|| rest("ai"))
+        .or_else(|| rest("a"))
+        .is_some()
+    {
+        return Some(Substituent::Anteiso);
+    }
+    if rest("iso").or_else(|| rest("i")).is_some() {
+        return Some(Substituent::Iso);
+    }
+    if rest("cy").is_some() {
+        return Some(Substituent::Cyclopropane(None));
+    }
+    let digits = token.find(|c: char| !c.is_ascii_digit())?;
+    let locant = token[..digits].parse().ok()?;
+    let group = token[digits..].trim_start_matches('-');
+    if group.starts_with("oh") {
+        Some(Substituent::Hydroxy(locant))
+    } else if group.starts_with("me") {
+        Some(branch(locant, carbons))
+    } else {
+        None
+    }
+}
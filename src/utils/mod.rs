@@ -1,4 +1,5 @@
-pub(crate) use self::polars::ChunkedArrayExt;
+pub(crate) use self::polars::HashedDataFrame;
 
 // mod egui_tiles;
 mod polars;
+pub(crate) mod statistics;
@@ -1,81 +1,155 @@
-use egui::WidgetText;
-use polars::{chunked_array::ChunkedArray, datatypes::PolarsDataType, series::Series};
-use std::fmt::{Display, Formatter, Result};
-
-// /// Extension methods for [`Series`]
-// trait SeriesExt {
-//     fn display(&self, fmt: impl Fn(T) -> U) -> FirstAndLast<T, U, impl Fn(T::Physical<'_>) -> U>;
-// }
-
-// impl<T: PolarsDataType, U: Display> ChunkedArrayExt<T, U> for &Series {
-//     fn display(&self, fmt: impl Fn(T) -> U) -> FirstAndLast<T, U, impl Fn(T::Physical<'_>) -> U> {
-//         FirstAndLast {
-//             chunked_array: self,
-//             fmt,
-//         }
-//     }
-// }
-
-// struct DisplayList {
-//     chunked_array: self,
-//     fmt,
-// }
-
-/// Extension methods for [`ChunkedArray`]
-pub trait ChunkedArrayExt<T: PolarsDataType, U: Display> {
-    fn display(
-        &self,
-        fmt: impl Fn(T::Physical<'_>) -> U,
-    ) -> FirstAndLast<T, U, impl Fn(T::Physical<'_>) -> U>;
+use polars::{datatypes::AnyValue, frame::DataFrame};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::Deref,
+};
+
+/// Data frame with the hash of its contents
+///
+/// The contents are hashed once when the data frame changes, the keys of the
+/// computers hash the stored value instead of every cell on every frame.
+#[derive(Clone, Debug)]
+pub(crate) struct HashedDataFrame {
+    data_frame: DataFrame,
+    hash: u64,
 }
 
-impl<T: PolarsDataType, U: Display> ChunkedArrayExt<T, U> for &ChunkedArray<T> {
-    fn display(
-        &self,
-        fmt: impl Fn(T::Physical<'_>) -> U,
-    ) -> FirstAndLast<T, U, impl Fn(T::Physical<'_>) -> U> {
-        FirstAndLast {
-            chunked_array: self,
-            fmt,
+impl HashedDataFrame {
+    pub(crate) fn new(data_frame: DataFrame) -> Self {
+        let mut hasher = DefaultHasher::new();
+        hash_data_frame(&data_frame, &mut hasher);
+        Self {
+            data_frame,
+            hash: hasher.finish(),
         }
     }
+
+    /// Data frame with a known hash, the computed data frames take the hash of
+    /// their key
+    pub(crate) const fn with_hash(data_frame: DataFrame, hash: u64) -> Self {
+        Self { data_frame, hash }
+    }
+
+    /// Changes the data frame and hashes it again
+    pub(crate) fn update<T>(&mut self, f: impl FnOnce(&mut DataFrame) -> T) -> T {
+        let output = f(&mut self.data_frame);
+        *self = Self::new(std::mem::take(&mut self.data_frame));
+        output
+    }
+
+    pub(crate) fn into_inner(self) -> DataFrame {
+        self.data_frame
+    }
+}
+
+impl Default for HashedDataFrame {
+    fn default() -> Self {
+        Self::new(DataFrame::empty())
+    }
+}
+
+impl Deref for HashedDataFrame {
+    type Target = DataFrame;
+
+    fn deref(&self) -> &DataFrame {
+        &self.data_frame
+    }
+}
+
+impl From<DataFrame> for HashedDataFrame {
+    fn from(data_frame: DataFrame) -> Self {
+        Self::new(data_frame)
+    }
+}
+
+impl Hash for HashedDataFrame {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+impl PartialEq for HashedDataFrame {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+    }
+}
+
+impl Serialize for HashedDataFrame {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.data_frame.serialize(serializer)
+    }
 }
 
-/// Display first and last
-pub struct FirstAndLast<'a, T: PolarsDataType, U: Display, F: Fn(T::Physical<'a>) -> U> {
-    chunked_array: &'a ChunkedArray<T>,
-    fmt: F,
+impl<'de> Deserialize<'de> for HashedDataFrame {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        DataFrame::deserialize(deserializer).map(Self::new)
+    }
 }
 
-impl<T: PolarsDataType, U: Display, F: Fn(T::Physical<'_>) -> U> Display
-    for FirstAndLast<'_, T, U, F>
-{
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "[")?;
-        if !self.chunked_array.is_empty() {
-            let first = self.chunked_array.get(0).unwrap();
-            Display::fmt(&(self.fmt)(first), f)?;
-            if self.chunked_array.len() > 1 {
-                write!(f, ", ")?;
-                if self.chunked_array.len() > 2 {
-                    write!(f, "… ")?;
-                }
-                let last = self
-                    .chunked_array
-                    .get(self.chunked_array.len() - 1)
-                    .unwrap();
-                Display::fmt(&(self.fmt)(last), f)?;
+/// Hashes the contents of the data frame: the column names, types and values
+fn hash_data_frame<H: Hasher>(data_frame: &DataFrame, state: &mut H) {
+    data_frame.height().hash(state);
+    for column in data_frame.get_columns() {
+        column.name().hash(state);
+        column.dtype().to_string().hash(state);
+        for value in column.as_materialized_series().iter() {
+            hash_any_value(&value, state);
+        }
+    }
+}
+
+fn hash_any_value<H: Hasher>(value: &AnyValue, state: &mut H) {
+    match value {
+        AnyValue::List(series) => {
+            series.len().hash(state);
+            for value in series.iter() {
+                hash_any_value(&value, state);
             }
         }
-        write!(f, "]")?;
-        Ok(())
+        AnyValue::Struct(..) => {
+            for value in value._iter_struct_av() {
+                hash_any_value(&value, state);
+            }
+        }
+        value => value.hash(state),
     }
 }
 
-impl<T: PolarsDataType, U: Display, F: Fn(T::Physical<'_>) -> U> From<FirstAndLast<'_, T, U, F>>
-    for WidgetText
-{
-    fn from(value: FirstAndLast<T, U, F>) -> Self {
-        value.to_string().into()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    fn data_frame(times: &[Option<f64>]) -> PolarsResult<DataFrame> {
+        df! {
+            "FattyAcid" => ["16:0", "18:0", "18:1"],
+            "RetentionTime" => times,
+        }
+    }
+
+    #[test]
+    fn hash() -> PolarsResult<()> {
+        let left = HashedDataFrame::new(data_frame(&[Some(1.0), None, Some(3.0)])?);
+        let right = HashedDataFrame::new(data_frame(&[Some(1.0), None, Some(3.0)])?);
+        assert_eq!(left, right);
+        let other = HashedDataFrame::new(data_frame(&[Some(1.0), Some(2.0), Some(3.0)])?);
+        assert_ne!(left, other);
+        Ok(())
+    }
+
+    #[test]
+    fn update() -> PolarsResult<()> {
+        let mut hashed = HashedDataFrame::new(data_frame(&[Some(1.0), None, Some(3.0)])?);
+        hashed.update(|data_frame| {
+            data_frame
+                .with_column(Column::new("RetentionTime".into(), [1.0, 2.0, 3.0]))
+                .map(drop)
+        })?;
+        assert_eq!(
+            hashed,
+            HashedDataFrame::new(data_frame(&[Some(1.0), Some(2.0), Some(3.0)])?),
+        );
+        Ok(())
     }
 }
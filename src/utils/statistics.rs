@@ -0,0 +1,374 @@
+use std::{f64::consts::PI, iter::zip};
+
+const EPSILON: f64 = 1e-14;
+const TINY: f64 = 1e-300;
+const ITERATIONS: usize = 300;
+
+/// Lanczos approximation coefficients (g = 7, n = 9)
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Welch's t-test
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Welch {
+    pub(crate) t: f64,
+    pub(crate) df: f64,
+    /// Two-tailed p-value
+    pub(crate) p: f64,
+}
+
+/// Welch's t-test of two samples with unequal variances
+///
+/// Both samples need at least two values.
+pub(crate) fn welch(left: &[f64], right: &[f64]) -> Option<Welch> {
+    let (left_n, left_mean, left_variance) = describe(left)?;
+    let (right_n, right_mean, right_variance) = describe(right)?;
+    let left_error = left_variance / left_n;
+    let right_error = right_variance / right_n;
+    let error = left_error + right_error;
+    if error == 0.0 {
+        return None;
+    }
+    let t = (left_mean - right_mean) / error.sqrt();
+    let df = error.powi(2)
+        / (left_error.powi(2) / (left_n - 1.0) + right_error.powi(2) / (right_n - 1.0));
+    let p = student_t(t, df);
+    Some(Welch { t, df, p })
+}
+
+/// Two-tailed p-value of Student's t distribution
+pub(crate) fn student_t(t: f64, df: f64) -> f64 {
+    incomplete_beta(df / 2.0, 0.5, df / (df + t * t))
+}
+
+/// Upper tail p-value of the F distribution
+pub(crate) fn fisher_f(f: f64, df1: f64, df2: f64) -> f64 {
+    if f <= 0.0 {
+        return 1.0;
+    }
+    incomplete_beta(df2 / 2.0, df1 / 2.0, df2 / (df2 + df1 * f))
+}
+
+/// Sample size, mean and sample variance
+pub(crate) fn describe(values: &[f64]) -> Option<(f64, f64, f64)> {
+    if values.len() < 2 {
+        return None;
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / (n - 1.0);
+    Some((n, mean, variance))
+}
+
+/// Ordinary least squares fit
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct LeastSquares {
+    /// Coefficients, `None` for the not estimable (collinear) ones
+    pub(crate) coefficients: Vec<Option<f64>>,
+    /// Standard errors, `None` without residual degrees of freedom
+    pub(crate) standard_errors: Vec<Option<f64>>,
+    /// Residual standard deviation
+    pub(crate) residual: Option<f64>,
+}
+
+/// Ordinary least squares without an intercept
+///
+/// Columns that are linear combinations of the previous ones are not
+/// estimable and are left out of the fit.
+pub(crate) fn least_squares(rows: &[Vec<f64>], values: &[f64]) -> LeastSquares {
+    let columns = rows.first().map_or(0, Vec::len);
+    let product =
+        |left: usize, right: usize| -> f64 { rows.iter().map(|row| row[left] * row[right]).sum() };
+    // Greedy selection of the estimable columns
+    let mut selected: Vec<usize> = Vec::new();
+    let mut inverse = Vec::new();
+    for column in 0..columns {
+        let mut candidate = selected.clone();
+        candidate.push(column);
+        let gram: Vec<Vec<f64>> = candidate
+            .iter()
+            .map(|&left| {
+                candidate
+                    .iter()
+                    .map(|&right| product(left, right))
+                    .collect()
+            })
+            .collect();
+        if let Some(candidate_inverse) = invert(gram) {
+            selected = candidate;
+            inverse = candidate_inverse;
+        }
+    }
+    let mut least_squares = LeastSquares {
+        coefficients: vec![None; columns],
+        standard_errors: vec![None; columns],
+        residual: None,
+    };
+    if selected.is_empty() {
+        return least_squares;
+    }
+    let moments: Vec<f64> = selected
+        .iter()
+        .map(|&column| {
+            zip(rows, values)
+                .map(|(row, value)| row[column] * value)
+                .sum()
+        })
+        .collect();
+    let coefficients: Vec<f64> = inverse
+        .iter()
+        .map(|row| zip(row, &moments).map(|(left, right)| left * right).sum())
+        .collect();
+    let residuals: f64 = zip(rows, values)
+        .map(|(row, value)| {
+            let fitted: f64 = zip(&selected, &coefficients)
+                .map(|(&column, coefficient)| row[column] * coefficient)
+                .sum();
+            (value - fitted).powi(2)
+        })
+        .sum();
+    let df = values.len().saturating_sub(selected.len());
+    let variance = (df > 0).then(|| residuals / df as f64);
+    for (index, &column) in selected.iter().enumerate() {
+        least_squares.coefficients[column] = Some(coefficients[index]);
+        least_squares.standard_errors[column] =
+            variance.map(|variance| (variance * inverse[index][index]).sqrt());
+    }
+    least_squares.residual = variance.map(f64::sqrt);
+    least_squares
+}
+
+/// Inverse of the symmetric matrix (Gauss-Jordan elimination)
+fn invert(mut matrix: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let size = matrix.len();
+    let scale = (0..size)
+        .map(|index| matrix[index][index])
+        .fold(0.0, f64::max);
+    let mut inverse: Vec<Vec<f64>> = (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|column| (row == column) as u8 as f64)
+                .collect()
+        })
+        .collect();
+    for column in 0..size {
+        let pivot = (column..size).max_by(|&left, &right| {
+            matrix[left][column]
+                .abs()
+                .total_cmp(&matrix[right][column].abs())
+        })?;
+        if matrix[pivot][column].abs() <= scale * 1e-10 {
+            return None;
+        }
+        matrix.swap(column, pivot);
+        inverse.swap(column, pivot);
+        let divisor = matrix[column][column];
+        for index in 0..size {
+            matrix[column][index] /= divisor;
+            inverse[column][index] /= divisor;
+        }
+        for row in 0..size {
+            if row != column {
+                let factor = matrix[row][column];
+                for index in 0..size {
+                    matrix[row][index] -= factor * matrix[column][index];
+                    inverse[row][index] -= factor * inverse[column][index];
+                }
+            }
+        }
+    }
+    Some(inverse)
+}
+
+/// Regularized incomplete beta function
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Continued fraction of the incomplete beta function (modified Lentz's
+/// method)
+fn continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let tiny = |value: f64| if value.abs() < TINY { TINY } else { value };
+    let mut c = 1.0;
+    let mut d = 1.0 / tiny(1.0 - (a + b) * x / (a + 1.0));
+    let mut h = d;
+    for m in 1..=ITERATIONS {
+        let m = m as f64;
+        // Even step
+        let numerator = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 / tiny(1.0 + numerator * d);
+        c = tiny(1.0 + numerator / c);
+        h *= d * c;
+        // Odd step
+        let numerator = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 / tiny(1.0 + numerator * d);
+        c = tiny(1.0 + numerator / c);
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    h
+}
+
+/// Natural logarithm of the gamma function (Lanczos approximation)
+fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection formula
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let mut a = LANCZOS[0];
+    for (index, coefficient) in LANCZOS.iter().enumerate().skip(1) {
+        a += coefficient / (x + index as f64);
+    }
+    let t = x + 7.5;
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(left: f64, right: f64, tolerance: f64) {
+        assert!(
+            (left - right).abs() <= tolerance,
+            "{left} is not within {tolerance} of {right}",
+        );
+    }
+
+    #[test]
+    fn gamma() {
+        assert_close(ln_gamma(1.0), 0.0, 1e-12);
+        assert_close(ln_gamma(5.0), 24f64.ln(), 1e-12);
+        assert_close(ln_gamma(0.5), PI.sqrt().ln(), 1e-12);
+        assert_close(ln_gamma(0.25), 3.625_609_908_221_908_f64.ln(), 1e-12);
+    }
+
+    #[test]
+    fn beta() {
+        // I(x; a, 1) = x^a, I(x; 1, b) = 1 - (1 - x)^b
+        for x in [0.1, 0.5, 0.9] {
+            assert_close(incomplete_beta(3.0, 1.0, x), x.powi(3), 1e-12);
+            assert_close(incomplete_beta(1.0, 4.0, x), 1.0 - (1.0 - x).powi(4), 1e-12);
+        }
+        assert_eq!(incomplete_beta(2.0, 3.0, 0.0), 0.0);
+        assert_eq!(incomplete_beta(2.0, 3.0, 1.0), 1.0);
+        // Symmetry I(x; a, b) = 1 - I(1 - x; b, a)
+        assert_close(
+            incomplete_beta(2.5, 7.0, 0.3),
+            1.0 - incomplete_beta(7.0, 2.5, 0.7),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn student() {
+        // One degree of freedom is the Cauchy distribution
+        for t in [0.5, 1.0, 3.0] {
+            assert_close(student_t(t, 1.0), 1.0 - 2.0 / PI * t.atan(), 1e-10);
+        }
+        // Two degrees of freedom
+        for t in [0.5, 1.0, 3.0] {
+            assert_close(student_t(t, 2.0), 1.0 - t / (2.0 + t * t).sqrt(), 1e-10);
+        }
+        assert_close(student_t(0.0, 10.0), 1.0, 1e-12);
+        assert_close(student_t(2.0, 10.0), 0.073_388_034_770_736, 1e-10);
+        assert_close(student_t(-2.0, 10.0), 0.073_388_034_770_736, 1e-10);
+        // Critical value of the 5 % two-tailed test
+        assert_close(student_t(2.228_138_851_986, 10.0), 0.05, 1e-10);
+    }
+
+    #[test]
+    fn fisher() {
+        // F(2, 2) upper tail is 1 / (1 + f)
+        for f in [0.5, 1.0, 4.0] {
+            assert_close(fisher_f(f, 2.0, 2.0), 1.0 / (1.0 + f), 1e-10);
+        }
+        // F(2, n) upper tail is (1 + 2 f / n)^(-n / 2)
+        assert_close(fisher_f(3.0, 2.0, 10.0), 1.6f64.powi(-5), 1e-10);
+        // Critical value of the 5 % test
+        assert_close(fisher_f(4.964_602_743_730_711, 1.0, 10.0), 0.05, 1e-10);
+        assert_eq!(fisher_f(0.0, 3.0, 10.0), 1.0);
+    }
+
+    #[test]
+    fn welch_test() {
+        let test = welch(&[1.0, 2.0, 3.0, 4.0, 5.0], &[2.0, 4.0, 6.0, 8.0, 10.0]).unwrap();
+        assert_close(test.t, -3.0 / 2.5f64.sqrt(), 1e-12);
+        assert_close(test.df, 6.25 / 1.0625, 1e-12);
+        assert_close(test.p, 0.107_531_194_930, 1e-9);
+        // Too few values or no variance
+        assert_eq!(welch(&[1.0], &[1.0, 2.0]), None);
+        assert_eq!(welch(&[1.0, 1.0], &[2.0, 2.0]), None);
+    }
+
+    #[test]
+    fn ordinary_least_squares() {
+        let rows: Vec<_> = (1..=5).map(|x| vec![1.0, x as f64]).collect();
+        let least_squares = least_squares(&rows, &[1.1, 1.9, 3.2, 3.9, 5.1]);
+        let [Some(intercept), Some(slope)] = least_squares.coefficients[..] else {
+            panic!("not estimable");
+        };
+        assert_close(intercept, 0.04, 1e-12);
+        assert_close(slope, 1.0, 1e-12);
+        let [Some(intercept), Some(slope)] = least_squares.standard_errors[..] else {
+            panic!("no standard errors");
+        };
+        assert_close(intercept, 0.162_480_768_092_719, 1e-12);
+        assert_close(slope, 0.048_989_794_855_664, 1e-12);
+        assert_close(
+            least_squares.residual.unwrap(),
+            0.154_919_333_848_297,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn collinear() {
+        // The third column is twice the second one
+        let rows: Vec<_> = (1..=4)
+            .map(|x| vec![1.0, x as f64, 2.0 * x as f64])
+            .collect();
+        let least_squares = least_squares(&rows, &[3.0, 5.0, 7.0, 9.0]);
+        assert_eq!(least_squares.coefficients[2], None);
+        assert_close(least_squares.coefficients[0].unwrap(), 1.0, 1e-12);
+        assert_close(least_squares.coefficients[1].unwrap(), 2.0, 1e-12);
+        // Exact fit
+        assert_close(least_squares.residual.unwrap(), 0.0, 1e-12);
+    }
+
+    #[test]
+    fn saturated() {
+        // No residual degrees of freedom
+        let least_squares = least_squares(&[vec![1.0, 1.0], vec![1.0, 2.0]], &[1.0, 3.0]);
+        assert_eq!(least_squares.standard_errors, [None, None]);
+        assert_eq!(least_squares.residual, None);
+    }
+}